@@ -1,4 +1,5 @@
 use assert_cmd::Command;
+use httpmock::MockServer;
 use std::path::PathBuf;
 use tempfile::TempDir;
 
@@ -19,20 +20,80 @@ pub fn read_fixture(name: &str) -> String {
 pub fn temp_home_with_config(config_content: Option<&str>) -> TempDir {
     let dir = TempDir::new().unwrap();
     if let Some(content) = config_content {
-        let config_dir = dir.path().join(".claude").join("glm-plan-usage");
-        std::fs::create_dir_all(&config_dir).unwrap();
-        std::fs::write(config_dir.join("config.toml"), content).unwrap();
+        write_config(&dir, content);
     }
     dir
 }
 
+/// Writes config.toml into a HOME created by [`temp_home_with_config`].
+pub fn write_config(home: &TempDir, content: &str) {
+    let config_dir = home.path().join(".claude").join("glm-plan-usage");
+    std::fs::create_dir_all(&config_dir).unwrap();
+    std::fs::write(config_dir.join("config.toml"), content).unwrap();
+}
+
 /// Creates a `Command` for the binary with HOME isolated to a temp dir.
+///
+/// The color environment is pinned so auto color depth resolves to 256 colors,
+/// `COLUMNS` is set so the status line is never shortened, and messages
+/// default to English whatever the host locale.
 pub fn bin_cmd(home: &TempDir) -> Command {
     let mut cmd = Command::cargo_bin("glm-plan-usage").unwrap();
-    cmd.env("HOME", home.path());
+    cmd.env("HOME", home.path())
+        .env("TERM", "xterm-256color")
+        .env("COLUMNS", "500")
+        .env_remove("COLORTERM")
+        .env_remove("NO_COLOR")
+        .env_remove("LC_ALL")
+        .env("LC_MESSAGES", "en_US.UTF-8");
+    cmd
+}
+
+/// Serves the `api_response.json` fixture from the monitor quota endpoint.
+pub fn mock_api(server: &MockServer) {
+    let api_response = read_fixture("api_response.json");
+    server.mock(|when, then| {
+        when.path("/zhipu/api/monitor/usage/quota/limit");
+        then.status(200).body(&api_response);
+    });
+}
+
+/// Creates a `Command` for the binary that fetches usage from the monitor
+/// API mocked by `server`.
+pub fn mocked_api_cmd(home: &TempDir, server: &MockServer) -> Command {
+    let mut cmd = bin_cmd(home);
+    cmd.env("ANTHROPIC_AUTH_TOKEN", "test-token").env(
+        "ANTHROPIC_BASE_URL",
+        format!("http://127.0.0.1:{}/zhipu/api/anthropic", server.port()),
+    );
     cmd
 }
 
+/// Returns the stdout of a command that must succeed.
+pub fn success_stdout(cmd: &mut Command) -> String {
+    let output = cmd.assert().success().get_output().stdout.clone();
+    String::from_utf8(output).unwrap()
+}
+
+/// Renders the status line against the monitor API mocked by `server` and
+/// returns stdout.
+pub fn run_with_mocked_api(home: &TempDir, server: &MockServer) -> String {
+    success_stdout(
+        mocked_api_cmd(home, server)
+            .arg("--no-cache")
+            .write_stdin("{}"),
+    )
+}
+
+/// Renders the status line with `config` against a mocked monitor API
+/// serving the `api_response.json` fixture, and returns stdout.
+pub fn run_with_config(config: &str) -> String {
+    let server = MockServer::start();
+    mock_api(&server);
+    let home = temp_home_with_config(Some(config));
+    run_with_mocked_api(&home, &server)
+}
+
 /// Minimal valid config in ASCII mode for predictable assertions.
 pub const ASCII_CONFIG: &str = r##"
 [style]
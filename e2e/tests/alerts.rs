@@ -0,0 +1,47 @@
+use crate::helpers::{mock_api, run_with_mocked_api, temp_home_with_config, write_config};
+use httpmock::prelude::*;
+use std::time::{Duration, Instant};
+
+fn wait_for_file(path: &std::path::Path) -> String {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while Instant::now() < deadline {
+        if let Ok(contents) = std::fs::read_to_string(path) {
+            if contents.ends_with('\n') {
+                return contents;
+            }
+        }
+        std::thread::sleep(Duration::from_millis(20));
+    }
+    panic!("alert command did not write {}", path.display());
+}
+
+#[cfg(unix)]
+#[test]
+fn alert_command_fires_once_per_window() {
+    let server = MockServer::start();
+    mock_api(&server);
+
+    let home = temp_home_with_config(None);
+    let out = home.path().join("alerts.log");
+    let config = format!(
+        r#"
+[style]
+mode = "ascii"
+
+[[alerts]]
+quota = "token_usage"
+threshold = 40
+notify = false
+command = "echo \"$GLM_ALERT_QUOTA $GLM_ALERT_PERCENTAGE\" >> '{}'"
+"#,
+        out.display()
+    );
+    write_config(&home, &config);
+
+    run_with_mocked_api(&home, &server);
+    assert_eq!(wait_for_file(&out), "token_usage 50\n");
+
+    run_with_mocked_api(&home, &server);
+    std::thread::sleep(Duration::from_millis(200));
+    assert_eq!(std::fs::read_to_string(&out).unwrap(), "token_usage 50\n");
+}
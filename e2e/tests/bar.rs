@@ -0,0 +1,52 @@
+use crate::helpers::{
+    mock_api, mocked_api_cmd, success_stdout, temp_home_with_config, ASCII_CONFIG,
+};
+use httpmock::prelude::*;
+use std::time::Duration;
+
+#[test]
+fn bar_waybar_json() {
+    let server = MockServer::start();
+    mock_api(&server);
+    let home = temp_home_with_config(Some(ASCII_CONFIG));
+    let output = success_stdout(mocked_api_cmd(&home, &server).args(["bar", "--no-cache"]));
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+
+    let text = json["text"].as_str().unwrap();
+    assert!(!text.contains('\x1b'), "unexpected text: {text:?}");
+    assert!(text.starts_with("$ 50%"), "unexpected text: {text:?}");
+    assert!(text.ends_with(" | # 30/100"), "unexpected text: {text:?}");
+    assert_eq!(json["class"], "ok");
+    assert_eq!(json["percentage"], 50);
+    let tooltip = json["tooltip"].as_str().unwrap();
+    assert!(
+        tooltip.contains("Weekly tokens: 50% (250000 / 500000)"),
+        "unexpected tooltip: {tooltip}"
+    );
+    assert!(
+        tooltip.contains("MCP calls: 30% (30 / 100)"),
+        "unexpected tooltip: {tooltip}"
+    );
+}
+
+#[test]
+fn bar_i3bar_streams_blocks() {
+    let server = MockServer::start();
+    mock_api(&server);
+    let home = temp_home_with_config(Some(ASCII_CONFIG));
+    let assert = mocked_api_cmd(&home, &server)
+        .args(["bar", "--protocol", "i3bar"])
+        .timeout(Duration::from_secs(2))
+        .assert()
+        .interrupted();
+    let output = String::from_utf8(assert.get_output().stdout.clone()).unwrap();
+
+    let mut lines = output.lines();
+    assert_eq!(lines.next(), Some("{\"version\":1}"));
+    assert_eq!(lines.next(), Some("["));
+    let update = lines.next().unwrap().trim_end_matches(',');
+    let blocks: serde_json::Value = serde_json::from_str(update).unwrap();
+    assert_eq!(blocks[0]["name"], "token_usage");
+    assert_eq!(blocks[0]["color"], "#00ff00");
+    assert_eq!(blocks[2]["full_text"], "# 30/100");
+}
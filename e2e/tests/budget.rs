@@ -0,0 +1,65 @@
+use crate::helpers::{mock_api, run_with_config, run_with_mocked_api, temp_home_with_config};
+use httpmock::prelude::*;
+
+const BUDGET_SEGMENT: &str = r#"
+[style]
+mode = "ascii"
+
+[[segments]]
+id = "budget"
+enabled = true
+[segments.icon]
+emoji = "🎯"
+ascii = "b"
+"#;
+
+#[test]
+fn budget_segment_shows_remaining_budget() {
+    let output = run_with_config(&format!("{BUDGET_SEGMENT}\n[budget]\nweekly = 70\n"));
+    assert!(output.contains("b 20% left"), "unexpected output: {output}");
+    assert!(output.contains("week"));
+}
+
+#[test]
+fn budget_segment_shows_exceeded_budget() {
+    let output = run_with_config(&format!(
+        "{BUDGET_SEGMENT}\n[budget]\nfive_hour = 40\nweekly = 90\n"
+    ));
+    assert!(
+        output.contains("b +10% over"),
+        "unexpected output: {output}"
+    );
+    assert!(output.contains("5h"));
+}
+
+#[test]
+fn budget_segment_hidden_without_budget() {
+    let output = run_with_config(BUDGET_SEGMENT);
+    assert!(output.contains("$ 50%"));
+    assert!(!output.contains("b "), "unexpected output: {output}");
+}
+
+#[test]
+fn daily_usage_recorded_while_segment_hidden() {
+    let server = MockServer::start();
+    mock_api(&server);
+    let config = format!(
+        "{BUDGET_SEGMENT}[segments.show_when]\nmin_percentage = 100\n\n[budget]\ndaily = 10\n"
+    );
+    let home = temp_home_with_config(Some(&config));
+
+    let output = run_with_mocked_api(&home, &server);
+    assert!(!output.contains("b "), "unexpected output: {output}");
+
+    let budget_path = home
+        .path()
+        .join(".claude")
+        .join("glm-plan-usage")
+        .join("state")
+        .join("budget.json");
+    let daily = std::fs::read_to_string(budget_path).unwrap();
+    assert!(
+        daily.contains("\"last_percentage\""),
+        "unexpected state: {daily}"
+    );
+}
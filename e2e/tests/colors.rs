@@ -0,0 +1,114 @@
+use crate::helpers::{
+    bin_cmd, mock_api, mocked_api_cmd, run_with_config, success_stdout, temp_home_with_config,
+    ASCII_CONFIG,
+};
+use httpmock::prelude::*;
+
+#[test]
+fn style_colors_apply_truecolor_thresholds() {
+    let output = run_with_config(
+        r##"
+[style]
+mode = "ascii"
+color_depth = "truecolor"
+[style.colors]
+thresholds = [{ from = 0, color = "#00ff00" }, { from = 40, color = "#ff8800" }]
+"##,
+    );
+    assert!(
+        output.contains("\x1b[38;2;255;136;0m$ 50%"),
+        "unexpected output: {output:?}"
+    );
+}
+
+#[test]
+fn segment_colors_override_style_colors() {
+    let output = run_with_config(
+        r##"
+[style]
+mode = "ascii"
+[style.colors]
+thresholds = [{ from = 0, color = "red" }]
+
+[[segments]]
+id = "mcp_usage"
+[segments.icon]
+emoji = "🌐"
+ascii = "#"
+[segments.colors]
+thresholds = [{ from = 0, color = "blue" }]
+"##,
+    );
+    assert!(
+        output.contains("\x1b[31m$ 50%"),
+        "unexpected output: {output:?}"
+    );
+    assert!(
+        output.contains("\x1b[34m# "),
+        "unexpected output: {output:?}"
+    );
+}
+
+#[test]
+fn check_rejects_invalid_color() {
+    let home = temp_home_with_config(Some(
+        r#"
+[style.colors]
+secondary = "purple"
+"#,
+    ));
+    bin_cmd(&home)
+        .arg("check")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("invalid color \"purple\""));
+}
+
+fn run_with_env(config: &str, envs: &[(&str, &str)]) -> String {
+    let server = MockServer::start();
+    mock_api(&server);
+    let home = temp_home_with_config(Some(config));
+    let mut cmd = mocked_api_cmd(&home, &server);
+    cmd.envs(envs.iter().copied())
+        .arg("--no-cache")
+        .write_stdin("{}");
+    success_stdout(&mut cmd)
+}
+
+#[test]
+fn no_color_env_disables_escapes() {
+    let output = run_with_env(ASCII_CONFIG, &[("NO_COLOR", "1")]);
+    assert!(!output.contains('\x1b'), "unexpected output: {output:?}");
+    assert!(output.starts_with("$ 50%"), "unexpected output: {output:?}");
+}
+
+#[test]
+fn explicit_color_depth_overrides_no_color() {
+    let config = ASCII_CONFIG.replace(
+        "separator = \" | \"",
+        "separator = \" | \"\ncolor_depth = \"16\"",
+    );
+    let output = run_with_env(&config, &[("NO_COLOR", "1")]);
+    assert!(
+        output.starts_with("\x1b[92m$ 50%"),
+        "unexpected output: {output:?}"
+    );
+    assert!(!output.contains("38;5;"), "unexpected output: {output:?}");
+}
+
+#[test]
+fn powerline_nerd_font_style() {
+    let output = run_with_config(
+        r#"
+[style]
+mode = "nerd_font"
+powerline = true
+"#,
+    );
+    assert!(
+        output.starts_with("\x1b[48;5;46m\x1b[30m \u{f0d6} 50%"),
+        "unexpected output: {output:?}"
+    );
+    assert!(output.contains('\u{e0b0}'), "unexpected output: {output:?}");
+    assert!(!output.contains(" | "), "unexpected output: {output:?}");
+}
@@ -0,0 +1,62 @@
+use crate::helpers::{bin_cmd, read_fixture, temp_home_with_config};
+use predicates::prelude::*;
+
+const COST_CONFIG: &str = r#"
+[style]
+mode = "ascii"
+
+[[segments]]
+id = "cost"
+[segments.icon]
+ascii = "="
+"#;
+
+#[test]
+fn cost_segment_from_full_payload() {
+    let home = temp_home_with_config(Some(COST_CONFIG));
+
+    bin_cmd(&home)
+        .env_remove("ANTHROPIC_AUTH_TOKEN")
+        .env_remove("ANTHROPIC_BASE_URL")
+        .write_stdin(read_fixture("stdin_full.json"))
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("= $0.01"))
+        .stdout(predicates::str::contains("+156/-23"));
+}
+
+#[test]
+fn cost_segment_with_currency_and_precision() {
+    let config = format!(
+        r#"{COST_CONFIG}
+[segments.options]
+currency_symbol = "¥"
+precision = 1
+exchange_rate = 7.0
+show_lines = false
+"#
+    );
+    let home = temp_home_with_config(Some(&config));
+
+    bin_cmd(&home)
+        .env_remove("ANTHROPIC_AUTH_TOKEN")
+        .env_remove("ANTHROPIC_BASE_URL")
+        .write_stdin(read_fixture("stdin_cost_partial.json"))
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("= ¥10.5"))
+        .stdout(predicates::str::contains("+10").not());
+}
+
+#[test]
+fn cost_segment_hidden_without_cost_payload() {
+    let home = temp_home_with_config(Some(COST_CONFIG));
+
+    bin_cmd(&home)
+        .env_remove("ANTHROPIC_AUTH_TOKEN")
+        .env_remove("ANTHROPIC_BASE_URL")
+        .write_stdin(read_fixture("stdin_minimal.json"))
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("$").not());
+}
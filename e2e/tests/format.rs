@@ -0,0 +1,91 @@
+use crate::helpers::{bin_cmd, run_with_config, temp_home_with_config};
+
+const FORMAT_CONFIG: &str = r##"
+[style]
+mode = "ascii"
+color_depth = "none"
+
+[[segments]]
+id = "token_usage"
+format = "{icon} {percent}% {bar} {used}/{limit}"
+[segments.icon]
+emoji = "🪙"
+ascii = "$"
+
+[[segments]]
+id = "mcp_usage"
+format = "MCP {used} of {limit}"
+[segments.icon]
+emoji = "🌐"
+ascii = "#"
+"##;
+
+#[test]
+fn segment_format_templates_are_rendered() {
+    let output = run_with_config(FORMAT_CONFIG);
+    assert!(
+        output.starts_with("$ 50% [#####-----] 50K/100K | "),
+        "unexpected output: {output}"
+    );
+    assert!(
+        output.contains("MCP 30 of 100"),
+        "unexpected output: {output}"
+    );
+}
+
+#[test]
+fn check_rejects_unknown_placeholder() {
+    let config = FORMAT_CONFIG.replace("{percent}%", "{pct}%");
+    let home = temp_home_with_config(Some(&config));
+    bin_cmd(&home)
+        .arg("check")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "unknown placeholder {pct} at column 8",
+        ));
+}
+
+#[test]
+fn show_bar_replaces_percentage() {
+    let config = r#"
+[style]
+mode = "ascii"
+color_depth = "none"
+
+[[segments]]
+id = "weekly_usage"
+[segments.icon]
+emoji = "🗓️"
+ascii = "*"
+[segments.options]
+show_bar = true
+bar_width = 8
+"#;
+    let output = run_with_config(config);
+    assert!(
+        output.starts_with("* [####----] | "),
+        "unexpected output: {output}"
+    );
+}
+
+#[test]
+fn weekly_reset_placeholder_is_filled() {
+    let config = r#"
+[style]
+mode = "ascii"
+color_depth = "none"
+
+[[segments]]
+id = "weekly_usage"
+format = "{icon} {percent}% (resets {reset})"
+[segments.icon]
+emoji = "🗓️"
+ascii = "*"
+"#;
+    let output = run_with_config(config);
+    assert!(
+        output.starts_with("* 50% (resets ") && !output.contains("(resets )"),
+        "unexpected output: {output}"
+    );
+}
@@ -0,0 +1,58 @@
+use crate::helpers::{bin_cmd, run_with_config, temp_home_with_config};
+
+const ICON_CONFIG: &str = r##"
+[style]
+mode = "ascii"
+color_depth = "none"
+
+[[segments]]
+id = "token_usage"
+enabled = false
+
+[[segments]]
+id = "weekly_usage"
+[segments.icon]
+emoji = "🔋"
+ascii = "*"
+levels = [{ from = 0, ascii = "_" }, { from = 50, ascii = "=" }, { from = 80, ascii = "#" }]
+error = { ascii = "!" }
+
+[[segments]]
+id = "mcp_usage"
+[segments.icon]
+emoji = "🌐"
+ascii = "m"
+levels = [{ from = 50, ascii = "M" }]
+"##;
+
+#[test]
+fn level_icons_follow_usage_percentage() {
+    let output = run_with_config(ICON_CONFIG);
+    assert_eq!(output, "= 50% | m 30/100");
+}
+
+#[test]
+fn error_icon_shows_segment_without_usage() {
+    let home = temp_home_with_config(Some(ICON_CONFIG));
+    bin_cmd(&home)
+        .arg("--no-cache")
+        .env_remove("ANTHROPIC_AUTH_TOKEN")
+        .env_remove("ANTHROPIC_BASE_URL")
+        .write_stdin("{}")
+        .assert()
+        .success()
+        .stdout("! --");
+}
+
+#[test]
+fn check_rejects_invalid_icon_level() {
+    let config = ICON_CONFIG.replace("from = 80", "from = 180");
+    let home = temp_home_with_config(Some(&config));
+    bin_cmd(&home)
+        .arg("check")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "icon level of segment weekly_usage: from = 180",
+        ));
+}
@@ -0,0 +1,99 @@
+use crate::helpers::{mock_api, mocked_api_cmd, success_stdout, temp_home_with_config};
+use httpmock::prelude::*;
+
+const LAYOUT_CONFIG: &str = r##"
+[style]
+mode = "ascii"
+color_depth = "none"
+
+[[segments]]
+id = "token_usage"
+[segments.icon]
+emoji = "🪙"
+ascii = "$"
+
+[[segments]]
+id = "weekly_usage"
+[segments.icon]
+emoji = "🗓️"
+ascii = "*"
+
+[[segments]]
+id = "mcp_usage"
+priority = 90
+[segments.icon]
+emoji = "🌐"
+ascii = "#"
+"##;
+
+fn run_with_columns(config: &str, columns: &str) -> String {
+    let server = MockServer::start();
+    mock_api(&server);
+    let home = temp_home_with_config(Some(config));
+    success_stdout(
+        mocked_api_cmd(&home, &server)
+            .arg("--no-cache")
+            .env("COLUMNS", columns)
+            .write_stdin("{}"),
+    )
+}
+
+#[test]
+fn full_line_when_it_fits() {
+    let output = run_with_columns(LAYOUT_CONFIG, "500");
+    assert!(
+        output.starts_with("$ 50% · @ ") && output.ends_with(" | * 50% | # 30/100"),
+        "unexpected output: {output}"
+    );
+}
+
+#[test]
+fn narrow_columns_compact_then_drop_low_priority_segments() {
+    let output = run_with_columns(LAYOUT_CONFIG, "30");
+    assert_eq!(output, "$ 50% | * 50% | # 30/100");
+
+    let output = run_with_columns(LAYOUT_CONFIG, "16");
+    assert_eq!(output, "$ 50% | # 30/100");
+
+    let output = run_with_columns(LAYOUT_CONFIG, "10");
+    assert_eq!(output, "# 30/100");
+}
+
+#[test]
+fn max_width_overrides_columns() {
+    let config = LAYOUT_CONFIG.replace(
+        "color_depth = \"none\"",
+        "color_depth = \"none\"\nmax_width = 16",
+    );
+    let output = run_with_columns(&config, "500");
+    assert_eq!(output, "$ 50% | # 30/100");
+
+    let config = LAYOUT_CONFIG.replace(
+        "color_depth = \"none\"",
+        "color_depth = \"none\"\nmax_width = 0",
+    );
+    let output = run_with_columns(&config, "10");
+    assert!(
+        output.ends_with(" | * 50% | # 30/100"),
+        "unexpected output: {output}"
+    );
+}
+
+#[test]
+fn segments_are_split_into_lines() {
+    let config = LAYOUT_CONFIG
+        .replace(
+            "color_depth = \"none\"",
+            "color_depth = \"none\"\nline_separators = [\" | \", \" / \"]",
+        )
+        .replace("id = \"weekly_usage\"", "id = \"weekly_usage\"\nline = 2")
+        .replace("priority = 90", "priority = 90\nline = 2");
+    let output = run_with_columns(&config, "500");
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines.len(), 2, "unexpected output: {output}");
+    assert!(
+        lines[0].starts_with("$ 50% · @ "),
+        "unexpected output: {output}"
+    );
+    assert_eq!(lines[1], "* 50% / # 30/100");
+}
@@ -0,0 +1,90 @@
+use crate::helpers::{bin_cmd, run_with_config, temp_home_with_config, ASCII_CONFIG};
+
+const CHINESE: &str = "zh_CN.UTF-8";
+
+#[test]
+fn init_writes_chinese_template_for_chinese_locale() {
+    let home = temp_home_with_config(None);
+    bin_cmd(&home)
+        .arg("init")
+        .env("LC_ALL", CHINESE)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("已创建配置文件"));
+
+    let config_path = home
+        .path()
+        .join(".claude")
+        .join("glm-plan-usage")
+        .join("config.toml");
+    let contents = std::fs::read_to_string(config_path).unwrap();
+    assert!(contents.contains("# 样式配置"));
+    assert!(contents.contains("# locale = \"auto\""));
+}
+
+#[test]
+fn init_writes_english_template_by_default() {
+    let home = temp_home_with_config(None);
+    bin_cmd(&home).arg("init").assert().success();
+
+    let config_path = home
+        .path()
+        .join(".claude")
+        .join("glm-plan-usage")
+        .join("config.toml");
+    let contents = std::fs::read_to_string(config_path).unwrap();
+    assert!(contents.contains("# STYLE CONFIGURATION"));
+}
+
+#[test]
+fn check_reports_in_chinese() {
+    let home = temp_home_with_config(None);
+    bin_cmd(&home)
+        .arg("check")
+        .env("LC_ALL", CHINESE)
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("未找到配置文件"));
+}
+
+#[test]
+fn config_locale_overrides_environment() {
+    let config = ASCII_CONFIG.replace("[style]\n", "[style]\nlocale = \"zh\"\n");
+    let home = temp_home_with_config(Some(&config));
+    bin_cmd(&home)
+        .arg("check")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("✓ 配置有效"));
+
+    let config = ASCII_CONFIG.replace("[style]\n", "[style]\nlocale = \"en\"\n");
+    let home = temp_home_with_config(Some(&config));
+    bin_cmd(&home)
+        .arg("check")
+        .env("LC_ALL", CHINESE)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("✓ Configuration valid"));
+}
+
+#[test]
+fn segment_labels_follow_locale() {
+    let config = r#"
+[style]
+mode = "ascii"
+locale = "zh"
+
+[[segments]]
+id = "budget"
+enabled = true
+[segments.icon]
+emoji = "🎯"
+ascii = "b"
+
+[budget]
+weekly = 70
+"#;
+    let output = run_with_config(config);
+    assert!(output.contains("b 剩余 20%"), "unexpected output: {output}");
+    assert!(output.contains("本周"));
+}
@@ -1,5 +1,23 @@
+mod alerts;
+mod bar;
+mod budget;
 mod check;
+mod colors;
+mod cost;
 mod error_cases;
+mod format;
+mod icons;
 mod init;
+mod layout;
+mod locale;
+mod output_format;
 mod print;
+mod prompt;
+mod report;
+mod roi;
+mod show_when;
+mod spike;
 mod stdin_mode;
+mod theme;
+mod value_display;
+mod webhook;
@@ -0,0 +1,64 @@
+use crate::helpers::{
+    bin_cmd, mock_api, mocked_api_cmd, success_stdout, temp_home_with_config, ASCII_CONFIG,
+};
+use httpmock::prelude::*;
+
+fn run_with_format(format: &str) -> String {
+    let server = MockServer::start();
+    mock_api(&server);
+    let home = temp_home_with_config(Some(ASCII_CONFIG));
+    success_stdout(
+        mocked_api_cmd(&home, &server)
+            .args(["--no-cache", "--format", format])
+            .write_stdin("{}"),
+    )
+}
+
+#[test]
+fn ansi_format_keeps_colors() {
+    let output = run_with_format("ansi");
+    assert!(output.contains("\x1b["), "unexpected output: {output:?}");
+    assert!(output.contains("$ 50%"), "unexpected output: {output:?}");
+}
+
+#[test]
+fn plain_format_has_no_escape_codes() {
+    let output = run_with_format("plain");
+    assert!(!output.contains('\x1b'), "unexpected output: {output:?}");
+    assert!(output.starts_with("$ 50%"), "unexpected output: {output:?}");
+    assert!(
+        output.ends_with("# 30/100"),
+        "unexpected output: {output:?}"
+    );
+}
+
+#[test]
+fn json_format_lists_segments_and_usage() {
+    let output = run_with_format("json");
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+
+    let segments = json["segments"].as_array().unwrap();
+    let ids: Vec<&str> = segments.iter().map(|s| s["id"].as_str().unwrap()).collect();
+    assert_eq!(ids, ["token_usage", "weekly_usage", "mcp_usage"]);
+    assert_eq!(segments[0]["primary"], "50%");
+    assert_eq!(segments[0]["line"], 1);
+    assert_eq!(segments[0]["metadata"]["percentage"], "50");
+    assert_eq!(segments[2]["primary"], "30/100");
+
+    let usage = &json["usage"];
+    assert_eq!(usage["token_usage"]["used"], 50000);
+    assert_eq!(usage["token_usage"]["limit"], 100_000);
+    assert_eq!(usage["weekly_usage"]["percentage"], 50);
+    assert_eq!(usage["mcp_usage"]["used"], 30);
+}
+
+#[test]
+fn unknown_format_is_rejected() {
+    let home = temp_home_with_config(Some(ASCII_CONFIG));
+    bin_cmd(&home)
+        .args(["--format", "xml"])
+        .write_stdin("{}")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("invalid value 'xml'"));
+}
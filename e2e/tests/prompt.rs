@@ -0,0 +1,60 @@
+use crate::helpers::{
+    mock_api, mocked_api_cmd, success_stdout, temp_home_with_config, ASCII_CONFIG,
+};
+use httpmock::prelude::*;
+
+fn run_prompt(target: &str) -> String {
+    let server = MockServer::start();
+    mock_api(&server);
+    let home = temp_home_with_config(Some(ASCII_CONFIG));
+    success_stdout(mocked_api_cmd(&home, &server).args([
+        "prompt",
+        "--no-cache",
+        "--target",
+        target,
+    ]))
+}
+
+#[test]
+fn prompt_tmux_uses_style_codes() {
+    let output = run_prompt("tmux");
+    assert!(!output.contains('\x1b'), "unexpected output: {output:?}");
+    assert!(
+        output.starts_with("#[fg=colour46]$ 50%"),
+        "unexpected output: {output:?}"
+    );
+    assert!(
+        output.ends_with("#[fg=colour46]## 30/100#[default]"),
+        "unexpected output: {output:?}"
+    );
+}
+
+#[test]
+fn prompt_zsh_and_bash_wrap_escapes() {
+    let zsh = run_prompt("zsh");
+    assert!(
+        zsh.starts_with("%{\x1b[38;5;46m%}$ 50%%"),
+        "unexpected output: {zsh:?}"
+    );
+
+    let bash = run_prompt("bash");
+    assert!(
+        bash.starts_with("\\[\x1b[38;5;46m\\]\\\\$ 50%"),
+        "unexpected output: {bash:?}"
+    );
+}
+
+#[test]
+fn prompt_plain_by_default_without_stdin() {
+    let server = MockServer::start();
+    mock_api(&server);
+    let home = temp_home_with_config(Some(ASCII_CONFIG));
+    let output = success_stdout(mocked_api_cmd(&home, &server).arg("prompt"));
+
+    assert!(!output.contains('\x1b'), "unexpected output: {output:?}");
+    assert!(output.starts_with("$ 50%"), "unexpected output: {output:?}");
+    assert!(
+        output.ends_with(" | # 30/100"),
+        "unexpected output: {output:?}"
+    );
+}
@@ -0,0 +1,81 @@
+use crate::helpers::{bin_cmd, read_fixture, temp_home_with_config, ASCII_CONFIG};
+use httpmock::prelude::*;
+
+fn ledger_path(home: &tempfile::TempDir) -> std::path::PathBuf {
+    home.path()
+        .join(".claude")
+        .join("glm-plan-usage")
+        .join("state")
+        .join("attribution.json")
+}
+
+#[test]
+fn report_without_data() {
+    let home = temp_home_with_config(None);
+    bin_cmd(&home)
+        .arg("report")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("No quota consumption"));
+}
+
+#[test]
+fn report_by_workspace_and_model() {
+    let home = temp_home_with_config(None);
+    let path = ledger_path(&home);
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(
+        &path,
+        r#"{
+  "since": 1800000000,
+  "workspaces": {
+    "/home/user/heavy": { "five_hour_pct": 42, "weekly_pct": 12 },
+    "/home/user/light": { "five_hour_pct": 3, "weekly_pct": 1 }
+  },
+  "models": {
+    "glm-5.1": { "five_hour_pct": 45, "weekly_pct": 13 }
+  }
+}"#,
+    )
+    .unwrap();
+
+    bin_cmd(&home)
+        .args(["report", "--by", "workspace"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("/home/user/heavy"))
+        .stdout(predicates::str::contains("+42%"));
+
+    bin_cmd(&home)
+        .args(["report", "--by", "model"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("glm-5.1"))
+        .stdout(predicates::str::contains("+45%"));
+}
+
+#[test]
+fn stdin_mode_records_attribution_baseline() {
+    let server = MockServer::start();
+    let api_response = read_fixture("api_response.json");
+    server.mock(|when, then| {
+        when.path("/zhipu/api/monitor/usage/quota/limit");
+        then.status(200).body(&api_response);
+    });
+
+    let home = temp_home_with_config(Some(ASCII_CONFIG));
+    bin_cmd(&home)
+        .arg("--no-cache")
+        .env("ANTHROPIC_AUTH_TOKEN", "test-token")
+        .env(
+            "ANTHROPIC_BASE_URL",
+            format!("http://127.0.0.1:{}/zhipu/api/anthropic", server.port()),
+        )
+        .write_stdin(read_fixture("stdin_full.json"))
+        .assert()
+        .success();
+
+    let ledger: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(ledger_path(&home)).unwrap()).unwrap();
+    assert_eq!(ledger["last_five_hour"]["percentage"], 50);
+}
@@ -0,0 +1,87 @@
+use crate::helpers::{bin_cmd, temp_home_with_config};
+
+const VALUE_CONFIG: &str = r#"
+[style]
+mode = "ascii"
+
+[pricing]
+plan_price = 7.0
+plan_period_days = 7
+
+[[segments]]
+id = "value"
+[segments.icon]
+ascii = "v"
+"#;
+
+fn write_transcript(home: &tempfile::TempDir) -> std::path::PathBuf {
+    let transcript = home.path().join("transcript.jsonl");
+    let line = serde_json::json!({
+        "type": "assistant",
+        "message": {
+            "id": "msg_1",
+            "model": "glm-5.1",
+            "usage": { "input_tokens": 2_000_000, "output_tokens": 0 }
+        }
+    });
+    std::fs::write(&transcript, format!("{line}\n")).unwrap();
+    transcript
+}
+
+#[test]
+fn roi_without_data() {
+    let home = temp_home_with_config(None);
+    bin_cmd(&home)
+        .arg("roi")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("No token usage recorded yet"));
+}
+
+#[test]
+fn value_segment_and_roi_report() {
+    let home = temp_home_with_config(Some(VALUE_CONFIG));
+    let transcript = write_transcript(&home);
+    let stdin_input =
+        serde_json::json!({ "transcript_path": transcript.to_string_lossy() }).to_string();
+
+    bin_cmd(&home)
+        .env_remove("ANTHROPIC_AUTH_TOKEN")
+        .env_remove("ANTHROPIC_BASE_URL")
+        .write_stdin(stdin_input)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("v $2.00"))
+        .stdout(predicates::str::contains("0.3x plan"));
+
+    bin_cmd(&home)
+        .arg("roi")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("glm-5.1"))
+        .stdout(predicates::str::contains("$2.00"))
+        .stdout(predicates::str::contains("$7.00"))
+        .stdout(predicates::str::contains("0.29x"));
+}
+
+#[test]
+fn roi_records_usage_without_transcript_segments() {
+    let home = temp_home_with_config(Some("[[segments]]\nid = \"token_usage\"\n"));
+    let transcript = write_transcript(&home);
+    let stdin_input =
+        serde_json::json!({ "transcript_path": transcript.to_string_lossy() }).to_string();
+
+    bin_cmd(&home)
+        .env_remove("ANTHROPIC_AUTH_TOKEN")
+        .env_remove("ANTHROPIC_BASE_URL")
+        .write_stdin(stdin_input)
+        .assert()
+        .success();
+
+    bin_cmd(&home)
+        .arg("roi")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("glm-5.1"))
+        .stdout(predicates::str::contains("$2.00"));
+}
@@ -0,0 +1,69 @@
+use crate::helpers::{bin_cmd, mock_api, mocked_api_cmd, success_stdout, temp_home_with_config};
+use httpmock::prelude::*;
+
+const SHOW_WHEN_CONFIG: &str = r##"
+[style]
+mode = "ascii"
+color_depth = "none"
+
+[[segments]]
+id = "token_usage"
+enabled = false
+
+[[segments]]
+id = "weekly_usage"
+[segments.icon]
+emoji = "🗓️"
+ascii = "*"
+[segments.show_when]
+SHOW_WHEN
+
+[[segments]]
+id = "mcp_usage"
+[segments.icon]
+emoji = "🌐"
+ascii = "#"
+"##;
+
+fn run_with_show_when(show_when: &str, stdin: &str) -> String {
+    let server = MockServer::start();
+    mock_api(&server);
+    let config = SHOW_WHEN_CONFIG.replace("SHOW_WHEN", show_when);
+    let home = temp_home_with_config(Some(&config));
+    success_stdout(
+        mocked_api_cmd(&home, &server)
+            .arg("--no-cache")
+            .write_stdin(stdin.to_string()),
+    )
+}
+
+#[test]
+fn min_percentage_hides_segment_below_threshold() {
+    let output = run_with_show_when("min_percentage = 60", "{}");
+    assert_eq!(output, "# 30/100");
+
+    let output = run_with_show_when("min_percentage = 50", "{}");
+    assert_eq!(output, "* 50% | # 30/100");
+}
+
+#[test]
+fn premium_model_follows_current_model() {
+    let output = run_with_show_when("premium_model = true", "{}");
+    assert_eq!(output, "# 30/100");
+
+    let output = run_with_show_when("premium_model = true", r#"{"model":{"id":"glm-5"}}"#);
+    assert_eq!(output, "* 50% | # 30/100");
+}
+
+#[test]
+fn check_rejects_invalid_min_percentage() {
+    let config = SHOW_WHEN_CONFIG.replace("SHOW_WHEN", "min_percentage = 120");
+    let home = temp_home_with_config(Some(&config));
+    bin_cmd(&home)
+        .arg("check")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "show_when.min_percentage of segment weekly_usage",
+        ));
+}
@@ -0,0 +1,80 @@
+use crate::helpers::{mock_api, run_with_mocked_api, temp_home_with_config};
+use httpmock::prelude::*;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Reset time of the 5-hour quota in `api_response.json`.
+const FIXTURE_RESET_AT: i64 = 1_800_000_000;
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| i64::try_from(d.as_secs()).unwrap_or(0))
+}
+
+fn seed_samples(home: &tempfile::TempDir, samples: &[(i64, u8)]) {
+    let samples: Vec<_> = samples
+        .iter()
+        .map(|(ago, pct)| {
+            serde_json::json!({
+                "at": now() - ago,
+                "percentage": pct,
+                "reset_at": FIXTURE_RESET_AT,
+            })
+        })
+        .collect();
+    let state_dir = home.path().join(".claude/glm-plan-usage/state");
+    std::fs::create_dir_all(&state_dir).unwrap();
+    std::fs::write(
+        state_dir.join("spike.json"),
+        serde_json::json!({ "samples": samples }).to_string(),
+    )
+    .unwrap();
+}
+
+#[test]
+fn spike_marker_and_webhook_on_fast_consumption() {
+    let server = MockServer::start();
+    mock_api(&server);
+    let hook = server.mock(|when, then| {
+        when.method(POST)
+            .path("/hook")
+            .json_body_partial(r#"{"kind":"spike","quota":"token_usage","percentage":50}"#);
+        then.status(200);
+    });
+
+    let home = temp_home_with_config(Some(&format!(
+        r#"
+[style]
+mode = "ascii"
+
+[spike]
+notify = false
+
+[webhook]
+url = "{}"
+"#,
+        server.url("/hook")
+    )));
+    // 2 points over the first 25 minutes, then 48 points in the last 5
+    seed_samples(&home, &[(1_800, 0), (900, 1), (300, 2)]);
+
+    let output = run_with_mocked_api(&home, &server);
+    assert!(output.contains("^ 9."), "missing spike marker: {output}");
+    hook.assert_hits(1);
+
+    run_with_mocked_api(&home, &server);
+    hook.assert_hits(1);
+}
+
+#[test]
+fn steady_consumption_has_no_spike_marker() {
+    let server = MockServer::start();
+    mock_api(&server);
+
+    let home = temp_home_with_config(Some(crate::helpers::ASCII_CONFIG));
+    seed_samples(&home, &[(3_000, 40), (600, 48), (300, 49)]);
+
+    let output = run_with_mocked_api(&home, &server);
+    assert!(output.contains("50%"));
+    assert!(!output.contains("%/m"));
+}
@@ -78,3 +78,127 @@ fn stdin_api_returns_empty_limits() {
         .assert()
         .success();
 }
+
+#[test]
+fn stdin_session_segment_tracks_session() {
+    let server = MockServer::start();
+    let api_response = read_fixture("api_response.json");
+
+    server.mock(|when, then| {
+        when.path(mock_api_path());
+        then.status(200).body(&api_response);
+    });
+
+    let config = r#"
+[style]
+mode = "ascii"
+
+[[segments]]
+id = "session"
+[segments.icon]
+ascii = "~"
+"#;
+    let home = temp_home_with_config(Some(config));
+    let stdin_input = r#"{"session_id":"abc-123","model":{"id":"glm-5.1"}}"#;
+
+    bin_cmd(&home)
+        .arg("--no-cache")
+        .env("ANTHROPIC_AUTH_TOKEN", "test-token")
+        .env("ANTHROPIC_BASE_URL", mock_zhipu_url(&server))
+        .write_stdin(stdin_input)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("~ +0% this session"));
+
+    let sessions_path = home
+        .path()
+        .join(".claude")
+        .join("glm-plan-usage")
+        .join("state")
+        .join("sessions.json");
+    let sessions = std::fs::read_to_string(sessions_path).unwrap();
+    assert!(sessions.contains("abc-123"));
+}
+
+#[test]
+fn stdin_context_segment_reads_transcript() {
+    let config = r#"
+[style]
+mode = "ascii"
+
+[[segments]]
+id = "context"
+[segments.icon]
+ascii = "c"
+"#;
+    let home = temp_home_with_config(Some(config));
+    let transcript = home.path().join("transcript.jsonl");
+    let lines = [
+        r#"{"type":"user","message":{"role":"user","content":"hi"}}"#,
+        r#"{"type":"assistant","message":{"id":"msg_1","model":"glm-5.1","usage":{"input_tokens":1500,"output_tokens":500,"cache_creation_input_tokens":0,"cache_read_input_tokens":48500}}}"#,
+    ];
+    std::fs::write(&transcript, format!("{}\n", lines.join("\n"))).unwrap();
+
+    let stdin_input = serde_json::json!({
+        "session_id": "ctx-1",
+        "transcript_path": transcript.to_string_lossy(),
+    })
+    .to_string();
+
+    bin_cmd(&home)
+        .arg("--no-cache")
+        .env_remove("ANTHROPIC_AUTH_TOKEN")
+        .env_remove("ANTHROPIC_BASE_URL")
+        .write_stdin(stdin_input)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("c 25%"))
+        .stdout(predicates::str::contains("50.5K tok"));
+}
+
+#[test]
+fn stdin_mcp_usage_shows_session_calls() {
+    let server = MockServer::start();
+    let api_response = read_fixture("api_response.json");
+
+    server.mock(|when, then| {
+        when.path(mock_api_path());
+        then.status(200).body(&api_response);
+    });
+
+    let home = temp_home_with_config(Some(ASCII_CONFIG));
+    let transcript = home.path().join("transcript.jsonl");
+    let lines: Vec<String> = [
+        "mcp__web-reader__webReader",
+        "mcp__zread__search",
+        "mcp__github__create_issue",
+        "Bash",
+    ]
+    .iter()
+    .enumerate()
+    .map(|(i, name)| {
+        serde_json::json!({
+            "type": "assistant",
+            "message": {
+                "id": format!("msg_{i}"),
+                "content": [{ "type": "tool_use", "id": format!("toolu_{i}"), "name": name }],
+                "usage": { "input_tokens": 10, "output_tokens": 5 }
+            }
+        })
+        .to_string()
+    })
+    .collect();
+    std::fs::write(&transcript, format!("{}\n", lines.join("\n"))).unwrap();
+
+    let stdin_input =
+        serde_json::json!({ "transcript_path": transcript.to_string_lossy() }).to_string();
+
+    bin_cmd(&home)
+        .arg("--no-cache")
+        .env("ANTHROPIC_AUTH_TOKEN", "test-token")
+        .env("ANTHROPIC_BASE_URL", mock_zhipu_url(&server))
+        .write_stdin(stdin_input)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("# 30/100 (+2)"));
+}
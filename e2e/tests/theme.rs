@@ -0,0 +1,102 @@
+use crate::helpers::{bin_cmd, mock_api, run_with_mocked_api, temp_home_with_config, ASCII_CONFIG};
+use httpmock::prelude::*;
+
+fn plain_config(theme: &str) -> String {
+    ASCII_CONFIG.replace(
+        "[style]\n",
+        &format!("[style]\ncolor_depth = \"none\"\ntheme = \"{theme}\"\n"),
+    )
+}
+
+fn write_theme(home: &tempfile::TempDir, name: &str, contents: &str) {
+    let dir = home
+        .path()
+        .join(".claude")
+        .join("glm-plan-usage")
+        .join("themes");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join(format!("{name}.toml")), contents).unwrap();
+}
+
+#[test]
+fn list_shows_builtin_and_user_themes() {
+    let home = temp_home_with_config(Some(&plain_config("ocean")));
+    write_theme(&home, "ocean", "description = \"Deep blue\"\n");
+    let output = bin_cmd(&home)
+        .args(["theme", "list"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let output = String::from_utf8(output).unwrap();
+    for name in ["default", "minimal", "powerline", "monochrome"] {
+        assert!(output.contains(name), "missing {name}: {output}");
+    }
+    assert!(
+        output
+            .lines()
+            .any(|l| l.starts_with("* ocean") && l.contains("Deep blue")),
+        "unexpected output: {output}"
+    );
+}
+
+#[test]
+fn preview_renders_sample_usage() {
+    let home = temp_home_with_config(Some(&plain_config("default")));
+    let output = bin_cmd(&home)
+        .args(["theme", "preview", "minimal"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let output = String::from_utf8(output).unwrap();
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines.len(), 3, "unexpected output: {output}");
+    assert!(
+        lines[0].starts_with("5h 25%"),
+        "unexpected output: {output}"
+    );
+    assert!(
+        lines[2].ends_with("wk 45%  mcp 90/100"),
+        "unexpected output: {output}"
+    );
+}
+
+#[test]
+fn preview_rejects_unknown_theme() {
+    let home = temp_home_with_config(Some(&plain_config("default")));
+    bin_cmd(&home)
+        .args(["theme", "preview", "nope"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("Unknown theme: nope"));
+}
+
+#[test]
+fn configured_theme_styles_status_line() {
+    let server = MockServer::start();
+    mock_api(&server);
+    let home = temp_home_with_config(Some(&plain_config("ocean")));
+    write_theme(
+        &home,
+        "ocean",
+        "separator = \" ~ \"\n[segments.mcp_usage.icon]\nascii = \"m\"\n",
+    );
+    let output = run_with_mocked_api(&home, &server);
+    assert!(
+        output.ends_with(" ~ * 50% ~ m 30/100"),
+        "unexpected output: {output}"
+    );
+}
+
+#[test]
+fn check_rejects_unknown_theme() {
+    let home = temp_home_with_config(Some(&plain_config("nope")));
+    bin_cmd(&home)
+        .arg("check")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("Invalid style.theme"));
+}
@@ -0,0 +1,76 @@
+use crate::helpers::{bin_cmd, run_with_config, temp_home_with_config};
+
+const VALUE_DISPLAY_CONFIG: &str = r##"
+[style]
+mode = "ascii"
+color_depth = "none"
+LOCALE
+
+[[segments]]
+id = "token_usage"
+enabled = false
+
+[[segments]]
+id = "weekly_usage"
+[segments.icon]
+emoji = "🗓️"
+ascii = "*"
+[segments.options]
+display = "DISPLAY"
+
+[[segments]]
+id = "mcp_usage"
+[segments.icon]
+emoji = "🌐"
+ascii = "#"
+"##;
+
+fn config_with(display: &str, locale: &str) -> String {
+    VALUE_DISPLAY_CONFIG
+        .replace("DISPLAY", display)
+        .replace("LOCALE", locale)
+}
+
+fn run_with_display(display: &str, locale: &str) -> String {
+    run_with_config(&config_with(display, locale))
+}
+
+#[test]
+fn used_limit_shows_compact_amounts() {
+    let output = run_with_display("used_limit", r#"locale = "en""#);
+    assert_eq!(output, "* 250K/500K | # 30/100");
+
+    let output = run_with_display("used_limit", r#"locale = "zh""#);
+    assert_eq!(output, "* 25万/50万 | # 30/100");
+}
+
+#[test]
+fn remaining_modes_show_what_is_left() {
+    let output = run_with_display("remaining", r#"locale = "en""#);
+    assert_eq!(output, "* 250K left | # 30/100");
+
+    let output = run_with_display("remaining_percent", r#"locale = "zh""#);
+    assert_eq!(output, "* 剩余 50% | # 30/100");
+}
+
+#[test]
+fn format_template_amounts_follow_locale() {
+    let config = config_with("percent", r#"locale = "zh""#).replace(
+        "id = \"weekly_usage\"",
+        "id = \"weekly_usage\"\nformat = \"{icon} {used}/{limit}\"",
+    );
+    let output = run_with_config(&config);
+    assert_eq!(output, "* 25万/50万 | # 30/100");
+}
+
+#[test]
+fn check_rejects_unknown_display() {
+    let home = temp_home_with_config(Some(&config_with("tokens", "")));
+    bin_cmd(&home)
+        .arg("check")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "Invalid display of segment weekly_usage",
+        ));
+}
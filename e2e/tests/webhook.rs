@@ -0,0 +1,91 @@
+use crate::helpers::{mock_api, run_with_mocked_api, temp_home_with_config};
+use httpmock::prelude::*;
+
+#[test]
+fn webhook_posts_threshold_event() {
+    let server = MockServer::start();
+    mock_api(&server);
+    let hook = server.mock(|when, then| {
+        when.method(POST)
+            .path("/hook")
+            .json_body_partial(r#"{"kind":"threshold","quota":"token_usage","threshold":40}"#);
+        then.status(200);
+    });
+
+    let home = temp_home_with_config(Some(&format!(
+        r#"
+[[alerts]]
+quota = "token_usage"
+threshold = 40
+notify = false
+
+[webhook]
+url = "{}"
+"#,
+        server.url("/hook")
+    )));
+
+    run_with_mocked_api(&home, &server);
+    hook.assert();
+}
+
+#[test]
+fn webhook_reports_auth_failure_in_slack_format() {
+    let server = MockServer::start();
+    server.mock(|when, then| {
+        when.path("/zhipu/api/monitor/usage/quota/limit");
+        then.status(401);
+    });
+    let hook = server.mock(|when, then| {
+        when.method(POST).path("/hook").body_contains("auth token");
+        then.status(200);
+    });
+
+    let home = temp_home_with_config(Some(&format!(
+        r#"
+[webhook]
+url = "{}"
+format = "slack"
+"#,
+        server.url("/hook")
+    )));
+
+    run_with_mocked_api(&home, &server);
+    run_with_mocked_api(&home, &server);
+    hook.assert_hits(1);
+}
+
+#[test]
+fn webhook_retries_failed_delivery_on_next_run() {
+    let server = MockServer::start();
+    mock_api(&server);
+    let mut failing = server.mock(|when, then| {
+        when.method(POST).path("/hook");
+        then.status(500);
+    });
+
+    let home = temp_home_with_config(Some(&format!(
+        r#"
+[[alerts]]
+quota = "token_usage"
+threshold = 40
+notify = false
+
+[webhook]
+url = "{}"
+retry_interval_secs = 0
+"#,
+        server.url("/hook")
+    )));
+
+    run_with_mocked_api(&home, &server);
+    failing.assert_hits(1);
+    failing.delete();
+
+    let hook = server.mock(|when, then| {
+        when.method(POST).path("/hook").body_contains("threshold");
+        then.status(200);
+    });
+    run_with_mocked_api(&home, &server);
+    hook.assert_hits(1);
+}
@@ -0,0 +1,101 @@
+//! Alert actions: user commands and desktop notifications.
+//!
+//! Actions are spawned without waiting so a slow hook never delays the
+//! status line.
+
+use crate::alerts::rules::TriggeredAlert;
+use std::io;
+use std::process::{Command, Stdio};
+
+/// Notification title used for desktop notifications.
+const NOTIFICATION_TITLE: &str = "GLM plan usage";
+
+/// Runs the actions configured for a triggered alert.
+///
+/// # Errors
+///
+/// Returns the first error encountered while spawning an action.
+pub fn dispatch(alert: &TriggeredAlert) -> io::Result<()> {
+    let message = alert.message();
+    let mut result = Ok(());
+    if let Some(command) = alert.rule.command.as_deref() {
+        result = run_command(command, alert, &message);
+    }
+    if alert.rule.notify {
+        result = result.and(notify(&message));
+    }
+    result
+}
+
+/// Spawns the user command through the platform shell.
+///
+/// Alert details are passed as `GLM_ALERT_*` environment variables.
+fn run_command(command: &str, alert: &TriggeredAlert, message: &str) -> io::Result<()> {
+    let mut cmd = if cfg!(windows) {
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/C", command]);
+        cmd
+    } else {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", command]);
+        cmd
+    };
+    cmd.env("GLM_ALERT_KIND", alert.kind())
+        .env("GLM_ALERT_QUOTA", alert.rule.quota.as_str())
+        .env("GLM_ALERT_PERCENTAGE", alert.percentage.to_string())
+        .env("GLM_ALERT_THRESHOLD", alert.rule.threshold.to_string())
+        .env(
+            "GLM_ALERT_RESET_AT",
+            alert.reset_at.map(|t| t.to_string()).unwrap_or_default(),
+        )
+        .env("GLM_ALERT_MESSAGE", message);
+    spawn_detached(&mut cmd)
+}
+
+/// Sends a desktop notification with the platform notifier.
+fn notify(message: &str) -> io::Result<()> {
+    let mut cmd = if cfg!(target_os = "macos") {
+        let script = format!(
+            "display notification {} with title {}",
+            applescript_string(message),
+            applescript_string(NOTIFICATION_TITLE)
+        );
+        let mut cmd = Command::new("osascript");
+        cmd.args(["-e", &script]);
+        cmd
+    } else if cfg!(windows) {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "desktop notifications are not supported on Windows, use `command` instead",
+        ));
+    } else {
+        let mut cmd = Command::new("notify-send");
+        cmd.args([NOTIFICATION_TITLE, message]);
+        cmd
+    };
+    spawn_detached(&mut cmd)
+}
+
+/// Quotes a string as an `AppleScript` string literal.
+fn applescript_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Spawns a command with all standard streams detached.
+fn spawn_detached(cmd: &mut Command) -> io::Result<()> {
+    cmd.stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map(drop)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_applescript_string_escapes_quotes() {
+        assert_eq!(applescript_string(r#"a "b" \c"#), r#""a \"b\" \\c""#);
+    }
+}
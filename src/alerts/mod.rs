@@ -0,0 +1,99 @@
+//! Threshold alerts for quota usage.
+//!
+//! `[[alerts]]` rules are evaluated against every fresh fetch. When a
+//! threshold is crossed, the rule's command runs and/or a desktop
+//! notification is sent, once per quota window.
+
+mod dispatch;
+mod rules;
+
+#[doc(inline)]
+#[allow(unused_imports, reason = "re-exported for public API")]
+pub use dispatch::dispatch;
+#[doc(inline)]
+#[allow(unused_imports, reason = "re-exported for public API")]
+pub use rules::{watched_quota, AlertState, FiredAlert, TriggeredAlert, ALERTS_FILE};
+
+use crate::api::UsageStats;
+use crate::config::Config;
+use std::path::Path;
+
+/// Evaluates alert rules against `stats` and returns the alerts that fired.
+///
+/// Callers run the actions with [`dispatch`]. Returns nothing when no
+/// alerts are configured.
+///
+/// # Errors
+///
+/// Returns an error if the alert state cannot be written.
+pub fn check_alerts(config: &Config, stats: &UsageStats) -> anyhow::Result<Vec<TriggeredAlert>> {
+    if config.alerts.is_empty() {
+        return Ok(Vec::new());
+    }
+    let Some(path) = crate::state::state_path(ALERTS_FILE) else {
+        return Ok(Vec::new());
+    };
+    check_alerts_at(&path, config, stats, crate::util::unix_now())
+}
+
+/// Evaluates alerts using the state file at `path`.
+fn check_alerts_at(
+    path: &Path,
+    config: &Config,
+    stats: &UsageStats,
+    now: i64,
+) -> anyhow::Result<Vec<TriggeredAlert>> {
+    let mut alert_state: AlertState = crate::state::load(path);
+    let previous = alert_state.clone();
+    let triggered = alert_state.evaluate(&config.alerts, stats, now);
+    if alert_state != previous {
+        crate::state::save(path, &alert_state)?;
+    }
+    Ok(triggered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::QuotaUsage;
+    use crate::config::{AlertDirection, AlertQuota, AlertRule};
+
+    #[test]
+    fn test_check_alerts_at_persists_fired_state() {
+        let dir = std::env::temp_dir().join("glm-plan-usage-test-alerts");
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = dir.join(ALERTS_FILE);
+
+        let config = Config {
+            alerts: vec![AlertRule {
+                quota: AlertQuota::WeeklyUsage,
+                threshold: 50,
+                direction: AlertDirection::Below,
+                command: None,
+                notify: false,
+            }],
+            ..Config::default()
+        };
+        let stats = UsageStats {
+            token_usage: None,
+            weekly_usage: Some(QuotaUsage {
+                used: 10,
+                limit: 100,
+                percentage: 10,
+                time_window: "weekly".to_string(),
+                reset_at: Some(5_000),
+            }),
+            mcp_usage: None,
+        };
+
+        let fired = check_alerts_at(&path, &config, &stats, 1_000).unwrap();
+        assert_eq!(fired.len(), 1);
+        assert!(check_alerts_at(&path, &config, &stats, 1_001)
+            .unwrap()
+            .is_empty());
+        let saved: AlertState = crate::state::load(&path);
+        assert_eq!(saved.fired["weekly_usage:below:50"].window, Some(5_000));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
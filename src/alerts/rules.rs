@@ -0,0 +1,300 @@
+//! Alert rule evaluation.
+//!
+//! Each rule fires once per quota window. The window is identified by the
+//! quota's `reset_at`, so a rule re-arms once the API reports the next
+//! window. A stale window whose reset time has passed does not re-arm it.
+
+use crate::api::{QuotaUsage, UsageStats};
+use crate::config::{AlertDirection, AlertQuota, AlertRule, SpikeConfig};
+use crate::state::Spike;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// State file name for fired alerts.
+pub const ALERTS_FILE: &str = "alerts.json";
+
+/// Record of an alert that already fired.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FiredAlert {
+    /// Reset timestamp (seconds) of the window the alert fired in.
+    #[serde(default)]
+    pub window: Option<i64>,
+    /// Timestamp (seconds) when the alert fired.
+    #[serde(default)]
+    pub fired_at: i64,
+}
+
+/// Persisted alert state, keyed by [`AlertRule::key`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AlertState {
+    /// Alerts that fired and have not re-armed yet.
+    #[serde(default)]
+    pub fired: BTreeMap<String, FiredAlert>,
+}
+
+/// An alert whose threshold was crossed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TriggeredAlert {
+    /// The rule that fired.
+    pub rule: AlertRule,
+    /// Current usage percentage of the watched quota.
+    pub percentage: u8,
+    /// Reset timestamp (seconds) of the watched quota.
+    pub reset_at: Option<i64>,
+    /// The usage spike that fired the alert, for spike alerts.
+    pub spike: Option<Spike>,
+}
+
+impl TriggeredAlert {
+    /// Creates an alert for a usage spike of the 5-hour quota.
+    ///
+    /// The alert runs the `[spike]` command and notification; its rule
+    /// threshold is the usage percentage at the time of the spike.
+    #[must_use]
+    pub fn from_spike(config: &SpikeConfig, spike: Spike) -> Self {
+        Self {
+            rule: AlertRule {
+                quota: AlertQuota::TokenUsage,
+                threshold: spike.percentage,
+                direction: AlertDirection::Above,
+                command: config.command.clone(),
+                notify: config.notify,
+            },
+            percentage: spike.percentage,
+            reset_at: spike.reset_at,
+            spike: Some(spike),
+        }
+    }
+
+    /// Returns the alert kind: `"spike"` or `"threshold"`.
+    #[must_use]
+    pub fn kind(&self) -> &'static str {
+        if self.spike.is_some() {
+            "spike"
+        } else {
+            "threshold"
+        }
+    }
+
+    /// Returns a human-readable alert message.
+    #[must_use]
+    pub fn message(&self) -> String {
+        let quota = self.rule.quota.label();
+        if let Some(spike) = &self.spike {
+            let baseline = spike
+                .baseline
+                .map(|b| format!(", baseline {b:.1}%/min"))
+                .unwrap_or_default();
+            return format!(
+                "GLM {quota} usage spike: {:.1}%/min{baseline}, now at {}%",
+                spike.rate, self.percentage
+            );
+        }
+        let verb = match self.rule.direction {
+            AlertDirection::Above => "reached",
+            AlertDirection::Below => "dropped to",
+        };
+        format!(
+            "GLM {quota} {verb} {}% (threshold {}%)",
+            self.percentage, self.rule.threshold
+        )
+    }
+}
+
+/// Returns the quota watched by `quota`, if the API reported it.
+#[must_use]
+pub fn watched_quota(stats: &UsageStats, quota: AlertQuota) -> Option<&QuotaUsage> {
+    match quota {
+        AlertQuota::TokenUsage => stats.token_usage.as_ref(),
+        AlertQuota::WeeklyUsage => stats.weekly_usage.as_ref(),
+        AlertQuota::McpUsage => stats.mcp_usage.as_ref(),
+    }
+}
+
+/// Returns `true` if `percentage` is past the rule's threshold.
+fn is_crossed(rule: &AlertRule, percentage: u8) -> bool {
+    match rule.direction {
+        AlertDirection::Above => percentage >= rule.threshold,
+        AlertDirection::Below => percentage <= rule.threshold,
+    }
+}
+
+impl AlertState {
+    /// Evaluates `rules` against fresh stats and returns the alerts to fire.
+    ///
+    /// Fired alerts re-arm when the quota window changes, even if its reset
+    /// time passed while the API still reports the old window. Quotas
+    /// without a reset time re-arm once usage moves back across the
+    /// threshold.
+    pub fn evaluate(
+        &mut self,
+        rules: &[AlertRule],
+        stats: &UsageStats,
+        now: i64,
+    ) -> Vec<TriggeredAlert> {
+        let keys: Vec<String> = rules.iter().map(AlertRule::key).collect();
+        self.fired.retain(|key, _| keys.contains(key));
+
+        let mut triggered = Vec::new();
+        for (rule, key) in rules.iter().zip(keys) {
+            let Some(quota) = watched_quota(stats, rule.quota) else {
+                continue;
+            };
+            let crossed = is_crossed(rule, quota.percentage);
+
+            if let Some(fired) = self.fired.get(&key) {
+                let rearm = match fired.window {
+                    Some(window) => quota.reset_at != Some(window),
+                    None => quota.reset_at.is_some() || !crossed,
+                };
+                if !rearm {
+                    continue;
+                }
+                self.fired.remove(&key);
+            }
+
+            if crossed {
+                self.fired.insert(
+                    key,
+                    FiredAlert {
+                        window: quota.reset_at,
+                        fired_at: now,
+                    },
+                );
+                triggered.push(TriggeredAlert {
+                    rule: rule.clone(),
+                    percentage: quota.percentage,
+                    reset_at: quota.reset_at,
+                    spike: None,
+                });
+            }
+        }
+        triggered
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quota(percentage: u8, reset_at: Option<i64>) -> QuotaUsage {
+        QuotaUsage {
+            used: i64::from(percentage),
+            limit: 100,
+            percentage,
+            time_window: "5h".to_string(),
+            reset_at,
+        }
+    }
+
+    fn stats(five_hour: u8, reset_at: i64) -> UsageStats {
+        UsageStats {
+            token_usage: Some(quota(five_hour, Some(reset_at))),
+            weekly_usage: None,
+            mcp_usage: None,
+        }
+    }
+
+    fn rule(threshold: u8, direction: AlertDirection) -> AlertRule {
+        AlertRule {
+            quota: AlertQuota::TokenUsage,
+            threshold,
+            direction,
+            command: None,
+            notify: true,
+        }
+    }
+
+    #[test]
+    fn test_fires_once_per_window() {
+        let mut state = AlertState::default();
+        let rules = [rule(80, AlertDirection::Above)];
+
+        assert!(state.evaluate(&rules, &stats(70, 1_000), 100).is_empty());
+        assert_eq!(state.evaluate(&rules, &stats(85, 1_000), 200).len(), 1);
+        assert!(state.evaluate(&rules, &stats(90, 1_000), 300).is_empty());
+    }
+
+    #[test]
+    fn test_rearms_after_reset() {
+        let mut state = AlertState::default();
+        let rules = [rule(80, AlertDirection::Above)];
+
+        assert_eq!(state.evaluate(&rules, &stats(85, 1_000), 100).len(), 1);
+        // Reset time passed but the API still reports the old window.
+        assert!(state.evaluate(&rules, &stats(85, 1_000), 1_000).is_empty());
+        assert!(state.evaluate(&rules, &stats(85, 1_000), 1_001).is_empty());
+        // New window below the threshold: nothing fires, rule stays armed.
+        assert!(state.evaluate(&rules, &stats(10, 19_000), 1_100).is_empty());
+        assert_eq!(state.evaluate(&rules, &stats(81, 19_000), 1_200).len(), 1);
+    }
+
+    #[test]
+    fn test_below_direction() {
+        let mut state = AlertState::default();
+        let rules = [rule(10, AlertDirection::Below)];
+
+        let fired = state.evaluate(&rules, &stats(5, 1_000), 100);
+        assert_eq!(fired.len(), 1);
+        assert!(fired[0].message().contains("dropped to 5%"));
+        assert!(state.evaluate(&rules, &stats(3, 1_000), 200).is_empty());
+    }
+
+    #[test]
+    fn test_quota_without_reset_rearms_when_uncrossed() {
+        let mut state = AlertState::default();
+        let rules = [AlertRule {
+            quota: AlertQuota::McpUsage,
+            ..rule(50, AlertDirection::Above)
+        }];
+        let mcp = |percentage| UsageStats {
+            token_usage: None,
+            weekly_usage: None,
+            mcp_usage: Some(quota(percentage, None)),
+        };
+
+        assert_eq!(state.evaluate(&rules, &mcp(60), 100).len(), 1);
+        assert!(state.evaluate(&rules, &mcp(70), 200).is_empty());
+        assert!(state.evaluate(&rules, &mcp(0), 300).is_empty());
+        assert_eq!(state.evaluate(&rules, &mcp(55), 400).len(), 1);
+    }
+
+    #[test]
+    fn test_removed_rules_are_pruned() {
+        let mut state = AlertState::default();
+        state.evaluate(&[rule(80, AlertDirection::Above)], &stats(85, 1_000), 100);
+        assert_eq!(state.fired.len(), 1);
+        state.evaluate(&[], &stats(85, 1_000), 200);
+        assert!(state.fired.is_empty());
+    }
+
+    #[test]
+    fn test_message() {
+        let alert = TriggeredAlert {
+            rule: rule(90, AlertDirection::Above),
+            percentage: 92,
+            reset_at: None,
+            spike: None,
+        };
+        assert_eq!(
+            alert.message(),
+            "GLM 5-hour token quota reached 92% (threshold 90%)"
+        );
+    }
+
+    #[test]
+    fn test_spike_message() {
+        let spike = Spike {
+            rate: 3.0,
+            baseline: Some(0.4),
+            percentage: 41,
+            reset_at: Some(1_000),
+        };
+        let alert = TriggeredAlert::from_spike(&SpikeConfig::default(), spike);
+        assert_eq!(alert.kind(), "spike");
+        assert_eq!(
+            alert.message(),
+            "GLM 5-hour token quota usage spike: 3.0%/min, baseline 0.4%/min, now at 41%"
+        );
+    }
+}
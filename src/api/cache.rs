@@ -4,6 +4,7 @@
 //! to reduce API calls and improve performance.
 
 use super::types::UsageStats;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::Instant;
 
@@ -12,6 +13,10 @@ use std::time::Instant;
 pub struct SharedCache {
     /// Cached data with expiration time.
     data: Arc<Mutex<Option<(UsageStats, Instant)>>>,
+    /// Whether a fetch was rejected with an authentication error.
+    auth_failed: Arc<AtomicBool>,
+    /// Whether the cached data was returned after the last fetch failed.
+    stale: Arc<AtomicBool>,
 }
 
 impl SharedCache {
@@ -20,12 +25,15 @@ impl SharedCache {
     pub fn new() -> Self {
         Self {
             data: Arc::new(Mutex::new(None)),
+            auth_failed: Arc::new(AtomicBool::new(false)),
+            stale: Arc::new(AtomicBool::new(false)),
         }
     }
 
     /// Get cached stats if fresh, otherwise fetch and cache.
     ///
-    /// Returns stale data if the fetch fails.
+    /// Returns stale data if the fetch fails, and marks the cache as
+    /// [stale](Self::is_stale) until the next successful fetch.
     pub fn get_or_fetch<F>(&self, ttl_seconds: u64, fetch: F) -> Option<UsageStats>
     where
         F: FnOnce() -> Option<UsageStats>,
@@ -43,12 +51,50 @@ impl SharedCache {
         if let Some(stats) = fetched {
             let to_return = stats.clone();
             *cache = Some((stats, Instant::now()));
+            self.stale.store(false, Ordering::Relaxed);
             Some(to_return)
         } else {
-            cache.as_ref().map(|(stats, _)| stats.clone())
+            let stale = cache.as_ref().map(|(stats, _)| stats.clone());
+            self.stale.store(stale.is_some(), Ordering::Relaxed);
+            stale
         }
     }
 
+    /// Store freshly fetched stats, replacing any cached value.
+    pub fn put(&self, stats: UsageStats) {
+        if let Ok(mut cache) = self.data.lock() {
+            *cache = Some((stats, Instant::now()));
+        }
+        self.stale.store(false, Ordering::Relaxed);
+    }
+
+    /// Return the cached stats, if any, without fetching.
+    #[must_use]
+    pub fn peek(&self) -> Option<UsageStats> {
+        self.data
+            .lock()
+            .ok()?
+            .as_ref()
+            .map(|(stats, _)| stats.clone())
+    }
+
+    /// Record that a fetch was rejected with an authentication error.
+    pub fn mark_auth_failed(&self) {
+        self.auth_failed.store(true, Ordering::Relaxed);
+    }
+
+    /// Return `true` if a fetch was rejected with an authentication error.
+    #[must_use]
+    pub fn auth_failed(&self) -> bool {
+        self.auth_failed.load(Ordering::Relaxed)
+    }
+
+    /// Return `true` if the last fetch failed and cached stats were returned.
+    #[must_use]
+    pub fn is_stale(&self) -> bool {
+        self.stale.load(Ordering::Relaxed)
+    }
+
     /// Clear the cache.
     #[allow(dead_code, reason = "public API for cache management")]
     pub fn clear(&self) {
@@ -131,6 +177,30 @@ mod tests {
         assert!(call_count.load(std::sync::atomic::Ordering::SeqCst) >= 2);
     }
 
+    #[test]
+    fn test_cache_peek() {
+        let cache = SharedCache::new();
+        assert!(cache.peek().is_none());
+        cache.get_or_fetch(300, || Some(empty_stats()));
+        assert!(cache.peek().is_some());
+    }
+
+    #[test]
+    fn test_cache_put() {
+        let cache = SharedCache::new();
+        cache.put(empty_stats());
+        assert!(cache.peek().is_some());
+    }
+
+    #[test]
+    fn test_cache_auth_failed_shared_between_clones() {
+        let cache = SharedCache::new();
+        let clone = cache.clone();
+        assert!(!cache.auth_failed());
+        clone.mark_auth_failed();
+        assert!(cache.auth_failed());
+    }
+
     #[test]
     fn test_cache_fallback_to_stale() {
         let cache = SharedCache::new();
@@ -141,5 +211,9 @@ mod tests {
 
         let result = cache.get_or_fetch(0, || None);
         assert!(result.is_some());
+        assert!(cache.is_stale());
+
+        cache.get_or_fetch(0, || Some(empty_stats()));
+        assert!(!cache.is_stale());
     }
 }
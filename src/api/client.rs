@@ -94,6 +94,8 @@ impl GlmApiClient {
         for _ in 0..self.retry_attempts {
             match self.try_fetch_usage_stats() {
                 Ok(stats) => return Ok(stats),
+                // Retrying cannot fix a rejected token
+                Err(e) if is_unauthorized(&e) => return Err(e),
                 Err(_) => std::thread::sleep(Duration::from_millis(RETRY_DELAY_MS)),
             }
         }
@@ -108,7 +110,10 @@ impl GlmApiClient {
         let response = self
             .authenticated_request(&url)
             .call()
-            .map_err(|e| ApiError::HttpError(e.to_string()))?;
+            .map_err(|e| match e {
+                ureq::Error::Status(code @ (401 | 403), _) => ApiError::Unauthorized(code),
+                e => ApiError::HttpError(e.to_string()),
+            })?;
 
         if response.status() != 200 {
             return Err(ApiError::ApiResponse(format!(
@@ -152,6 +157,15 @@ impl GlmApiClient {
     }
 }
 
+/// Returns `true` if `error` is an [`ApiError::Unauthorized`].
+#[must_use]
+pub fn is_unauthorized(error: &anyhow::Error) -> bool {
+    matches!(
+        error.downcast_ref::<ApiError>(),
+        Some(ApiError::Unauthorized(_))
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -205,4 +219,12 @@ mod tests {
         std::env::remove_var(AUTH_TOKEN_ENV);
         std::env::remove_var(BASE_URL_ENV);
     }
+
+    #[test]
+    fn test_is_unauthorized() {
+        assert!(is_unauthorized(&ApiError::Unauthorized(401).into()));
+        assert!(!is_unauthorized(
+            &ApiError::HttpError("timeout".to_string()).into()
+        ));
+    }
 }
@@ -7,6 +7,6 @@ mod types;
 #[doc(inline)]
 pub use cache::SharedCache;
 #[doc(inline)]
-pub use client::GlmApiClient;
+pub use client::{is_unauthorized, GlmApiClient};
 #[doc(inline)]
-pub use types::UsageStats;
+pub use types::{QuotaUsage, UsageStats};
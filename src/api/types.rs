@@ -3,7 +3,7 @@
 //! This module defines types for API responses, error handling,
 //! and platform detection for the GLM/ZHIPU API.
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 use thiserror::Error;
 
@@ -42,6 +42,9 @@ pub enum ApiError {
     #[error("HTTP request failed: {0}")]
     HttpError(String),
 
+    #[error("Authentication failed: HTTP {0}")]
+    Unauthorized(u16),
+
     #[error("API returned error: {0}")]
     ApiResponse(String),
 
@@ -165,7 +168,7 @@ pub struct ToolUsageData {
 }
 
 /// Combined usage statistics from all API endpoints.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 #[allow(
     clippy::struct_field_names,
     reason = "field names match API domain terminology"
@@ -180,7 +183,7 @@ pub struct UsageStats {
 }
 
 /// Individual quota usage with normalized percentage.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct QuotaUsage {
     /// Amount used.
     pub used: i64,
@@ -189,7 +192,6 @@ pub struct QuotaUsage {
     /// Usage percentage (0-100).
     pub percentage: u8,
     /// Time window for this quota (e.g., "5h", "weekly", "30d").
-    #[allow(dead_code, reason = "kept for documentation and future use")]
     pub time_window: String,
     /// Reset timestamp in seconds (converted from milliseconds).
     pub reset_at: Option<i64>,
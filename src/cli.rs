@@ -1,6 +1,6 @@
 //! CLI argument definitions using clap derive macros.
 
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 
 /// GLM plan usage plugin for Claude Code.
 ///
@@ -21,6 +21,10 @@ pub struct Args {
     /// Disable cache
     #[arg(long, global = true)]
     pub no_cache: bool,
+
+    /// Status line output format
+    #[arg(long, value_enum, default_value_t = OutputFormat::Ansi)]
+    pub format: OutputFormat,
 }
 
 #[derive(Subcommand, Debug)]
@@ -43,4 +47,92 @@ pub enum Commands {
         #[arg(long)]
         with_ccline: bool,
     },
+
+    /// Show which workspaces or models consumed the most quota.
+    Report {
+        /// Group consumption by workspace or model.
+        #[arg(long, value_enum, default_value_t = ReportBy::Workspace)]
+        by: ReportBy,
+    },
+
+    /// Print the status line for tmux or a shell prompt (no stdin needed).
+    Prompt {
+        /// Escape syntax of the output.
+        #[arg(long, value_enum, default_value_t = PromptTarget::Plain)]
+        target: PromptTarget,
+    },
+
+    /// Print usage for a desktop bar (Waybar JSON or an i3bar stream).
+    Bar {
+        /// Bar protocol of the output.
+        #[arg(long, value_enum, default_value_t = BarProtocol::Waybar)]
+        protocol: BarProtocol,
+    },
+
+    /// List or preview status line themes.
+    Theme {
+        #[command(subcommand)]
+        action: ThemeAction,
+    },
+
+    /// Compare the metered value of recent usage with the plan price.
+    Roi {
+        /// Number of days to cover (defaults to the plan period).
+        #[arg(long)]
+        days: Option<u32>,
+    },
+}
+
+/// Actions of the `theme` command.
+#[derive(Subcommand, Debug)]
+pub enum ThemeAction {
+    /// List built-in themes and theme files.
+    List,
+    /// Render the status line with a theme and sample usage.
+    Preview {
+        /// Theme to preview (defaults to `style.theme`).
+        name: Option<String>,
+    },
+}
+
+/// Output format of the status line.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Text with ANSI colors, as configured.
+    Ansi,
+    /// Text without any escape codes.
+    Plain,
+    /// Segment data and usage statistics as JSON.
+    Json,
+}
+
+/// Output syntax of the `prompt` command.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromptTarget {
+    /// tmux `#[fg=…]` style codes, for `status-right`.
+    Tmux,
+    /// ANSI escapes wrapped in `%{ %}` for zsh prompts.
+    Zsh,
+    /// ANSI escapes wrapped in `\[ \]` for bash prompts.
+    Bash,
+    /// Text without colors (e.g. for a Starship custom module).
+    Plain,
+}
+
+/// Output protocol of the `bar` command.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BarProtocol {
+    /// One JSON update for a Waybar custom module.
+    Waybar,
+    /// An endless i3bar protocol stream, refreshed every cache TTL.
+    I3bar,
+}
+
+/// Grouping key for the `report` command.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportBy {
+    /// Group by workspace directory.
+    Workspace,
+    /// Group by model ID.
+    Model,
 }
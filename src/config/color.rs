@@ -0,0 +1,302 @@
+//! Color values used in `[style.colors]` and `[segments.colors]`.
+//!
+//! Colors are written as strings: a name (`"red"`, `"bright_blue"`), a
+//! 256-color palette index (`"196"`), or a hex truecolor (`"#ff8800"`).
+//! Colors are downgraded to the terminal's [`ColorDepth`] when rendered.
+
+use super::types::ColorDepth;
+use std::fmt;
+
+/// Named colors and their ANSI foreground codes.
+const NAMED_COLORS: [(&str, u8); 17] = [
+    ("black", 30),
+    ("red", 31),
+    ("green", 32),
+    ("yellow", 33),
+    ("blue", 34),
+    ("magenta", 35),
+    ("cyan", 36),
+    ("white", 37),
+    ("gray", 90),
+    ("grey", 90),
+    ("bright_red", 91),
+    ("bright_green", 92),
+    ("bright_yellow", 93),
+    ("bright_blue", 94),
+    ("bright_magenta", 95),
+    ("bright_cyan", 96),
+    ("bright_white", 97),
+];
+
+/// RGB values of the 16 basic colors (xterm defaults), indexed 0-15.
+const BASIC_RGB: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+/// Channel levels of the 6x6x6 color cube (indexes 16-231).
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// Squared distance between two colors.
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let d = |x: u8, y: u8| u32::from(x.abs_diff(y)).pow(2);
+    d(a.0, b.0) + d(a.1, b.1) + d(a.2, b.2)
+}
+
+/// Converts a basic color index (0-15) to its SGR foreground code.
+fn basic_code(index: u8) -> u8 {
+    if index < 8 {
+        30 + index
+    } else {
+        90 + index - 8
+    }
+}
+
+/// Returns the cube level index (0-5) closest to a channel value.
+fn cube_level(value: u8) -> u8 {
+    CUBE_LEVELS
+        .iter()
+        .zip(0u8..)
+        .min_by_key(|(level, _)| level.abs_diff(value))
+        .map_or(0, |(_, i)| i)
+}
+
+/// A terminal foreground color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    /// Basic ANSI color, stored as its SGR foreground code (30-37, 90-97).
+    Named(u8),
+    /// 256-color palette index.
+    Indexed(u8),
+    /// 24-bit truecolor.
+    Rgb(u8, u8, u8),
+}
+
+/// Error returned for an unrecognized color string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColorParseError(String);
+
+impl fmt::Display for ColorParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid color \"{}\" (use a name like \"red\", a 256-color index 0-255, or \"#rrggbb\")",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ColorParseError {}
+
+impl Color {
+    /// Parses a color name, 256-color index, or `#rrggbb` hex value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the string is not a recognized color.
+    pub fn parse(value: &str) -> Result<Self, ColorParseError> {
+        let value = value.trim();
+        let err = || ColorParseError(value.to_string());
+
+        if let Some(hex) = value.strip_prefix('#') {
+            if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(err());
+            }
+            let Ok(rgb) = u32::from_str_radix(hex, 16) else {
+                return Err(err());
+            };
+            let [_, r, g, b] = rgb.to_be_bytes();
+            return Ok(Color::Rgb(r, g, b));
+        }
+        if let Ok(index) = value.parse::<u8>() {
+            return Ok(Color::Indexed(index));
+        }
+        let name = value.to_ascii_lowercase().replace('-', "_");
+        NAMED_COLORS
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, code)| Color::Named(*code))
+            .ok_or_else(err)
+    }
+
+    /// Returns the approximate RGB value of this color.
+    #[must_use]
+    pub fn rgb(self) -> (u8, u8, u8) {
+        match self {
+            Color::Named(code) => {
+                let index = if code >= 90 { code - 90 + 8 } else { code - 30 };
+                BASIC_RGB[usize::from(index.min(15))]
+            }
+            Color::Indexed(index @ 0..=15) => BASIC_RGB[usize::from(index)],
+            Color::Indexed(index @ 16..=231) => {
+                let i = index - 16;
+                let level = |n: u8| CUBE_LEVELS[usize::from(n)];
+                (level(i / 36), level(i / 6 % 6), level(i % 6))
+            }
+            Color::Indexed(index) => {
+                let gray = 8 + (index - 232) * 10;
+                (gray, gray, gray)
+            }
+            Color::Rgb(r, g, b) => (r, g, b),
+        }
+    }
+
+    /// Returns the closest color available at `depth`, or `None` when
+    /// colors are disabled.
+    #[must_use]
+    pub fn downgrade(self, depth: ColorDepth) -> Option<Self> {
+        match (depth, self) {
+            (ColorDepth::None, _) => None,
+            (ColorDepth::Auto | ColorDepth::Truecolor, color)
+            | (ColorDepth::Ansi256, color @ (Color::Named(_) | Color::Indexed(_)))
+            | (ColorDepth::Ansi16, color @ Color::Named(_)) => Some(color),
+            (ColorDepth::Ansi256, Color::Rgb(r, g, b)) => {
+                let cube = 16 + 36 * cube_level(r) + 6 * cube_level(g) + cube_level(b);
+                let gray_step =
+                    ((u16::from(r) + u16::from(g) + u16::from(b)) / 3).saturating_sub(3) / 10;
+                let gray = 232 + u8::try_from(gray_step.min(23)).unwrap_or(23);
+                let rgb = (r, g, b);
+                let closest = [cube, gray]
+                    .into_iter()
+                    .min_by_key(|i| distance(Color::Indexed(*i).rgb(), rgb))
+                    .unwrap_or(cube);
+                Some(Color::Indexed(closest))
+            }
+            (ColorDepth::Ansi16, color) => {
+                let rgb = color.rgb();
+                let closest = (0u8..16)
+                    .min_by_key(|i| distance(BASIC_RGB[usize::from(*i)], rgb))
+                    .unwrap_or(7);
+                Some(Color::Named(basic_code(closest)))
+            }
+        }
+    }
+
+    /// Returns the approximate color as `#rrggbb`.
+    #[must_use]
+    pub fn hex(self) -> String {
+        let (r, g, b) = self.rgb();
+        format!("#{r:02x}{g:02x}{b:02x}")
+    }
+
+    /// Returns the ANSI escape sequence that sets this foreground color.
+    #[must_use]
+    pub fn fg(self) -> String {
+        match self {
+            Color::Named(code) => format!("\x1b[{code}m"),
+            Color::Indexed(index) => format!("\x1b[38;5;{index}m"),
+            Color::Rgb(r, g, b) => format!("\x1b[38;2;{r};{g};{b}m"),
+        }
+    }
+
+    /// Returns the ANSI escape sequence that sets this background color.
+    #[must_use]
+    pub fn bg(self) -> String {
+        match self {
+            Color::Named(code) => format!("\x1b[{}m", code + 10),
+            Color::Indexed(index) => format!("\x1b[48;5;{index}m"),
+            Color::Rgb(r, g, b) => format!("\x1b[48;2;{r};{g};{b}m"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_named() {
+        assert_eq!(Color::parse("red"), Ok(Color::Named(31)));
+        assert_eq!(Color::parse("Bright-Blue"), Ok(Color::Named(94)));
+    }
+
+    #[test]
+    fn test_parse_indexed() {
+        assert_eq!(Color::parse("196"), Ok(Color::Indexed(196)));
+        Color::parse("256").unwrap_err();
+    }
+
+    #[test]
+    fn test_parse_hex() {
+        assert_eq!(Color::parse("#ff8800"), Ok(Color::Rgb(255, 136, 0)));
+        Color::parse("#ff88").unwrap_err();
+        Color::parse("#gg0000").unwrap_err();
+    }
+
+    #[test]
+    fn test_parse_unknown() {
+        let err = Color::parse("purple").unwrap_err();
+        assert!(err.to_string().contains("\"purple\""));
+    }
+
+    #[test]
+    fn test_downgrade_to_256() {
+        let depth = ColorDepth::Ansi256;
+        assert_eq!(
+            Color::Rgb(255, 0, 0).downgrade(depth),
+            Some(Color::Indexed(196))
+        );
+        assert_eq!(
+            Color::Rgb(0, 255, 0).downgrade(depth),
+            Some(Color::Indexed(46))
+        );
+        assert_eq!(
+            Color::Rgb(128, 128, 128).downgrade(depth),
+            Some(Color::Indexed(244))
+        );
+        assert_eq!(
+            Color::Indexed(109).downgrade(depth),
+            Some(Color::Indexed(109))
+        );
+    }
+
+    #[test]
+    fn test_downgrade_to_16() {
+        let depth = ColorDepth::Ansi16;
+        assert_eq!(Color::Indexed(196).downgrade(depth), Some(Color::Named(91)));
+        assert_eq!(Color::Indexed(46).downgrade(depth), Some(Color::Named(92)));
+        assert_eq!(Color::Indexed(1).downgrade(depth), Some(Color::Named(31)));
+        assert_eq!(Color::Rgb(0, 0, 0).downgrade(depth), Some(Color::Named(30)));
+        assert_eq!(Color::Named(33).downgrade(depth), Some(Color::Named(33)));
+    }
+
+    #[test]
+    fn test_downgrade_none_and_truecolor() {
+        assert_eq!(Color::Named(31).downgrade(ColorDepth::None), None);
+        assert_eq!(
+            Color::Rgb(1, 2, 3).downgrade(ColorDepth::Truecolor),
+            Some(Color::Rgb(1, 2, 3))
+        );
+    }
+
+    #[test]
+    fn test_fg_sequences() {
+        assert_eq!(Color::Named(37).fg(), "\x1b[37m");
+        assert_eq!(Color::Indexed(46).fg(), "\x1b[38;5;46m");
+        assert_eq!(Color::Rgb(1, 2, 3).fg(), "\x1b[38;2;1;2;3m");
+        assert_eq!(Color::Named(91).bg(), "\x1b[101m");
+        assert_eq!(Color::Indexed(46).bg(), "\x1b[48;5;46m");
+        assert_eq!(Color::Rgb(1, 2, 3).bg(), "\x1b[48;2;1;2;3m");
+    }
+
+    #[test]
+    fn test_hex() {
+        assert_eq!(Color::Rgb(255, 136, 0).hex(), "#ff8800");
+        assert_eq!(Color::Indexed(196).hex(), "#ff0000");
+        assert_eq!(Color::Named(32).hex(), "#00cd00");
+    }
+}
@@ -0,0 +1,297 @@
+//! Segment format templates such as `"{icon} {percent}% {bar}"`.
+//!
+//! A template is parsed once, when the config is loaded, into literal text
+//! and placeholders, and the status line fills the placeholders from each
+//! segment's data. `{{` and `}}` produce literal braces.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A value that can be inserted into a segment format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Placeholder {
+    /// Segment icon for the current display mode.
+    Icon,
+    /// Usage percentage without the `%` sign.
+    Percent,
+    /// Usage bar drawn from the percentage.
+    Bar,
+    /// Used amount of the quota.
+    Used,
+    /// Quota limit.
+    Limit,
+    /// Reset time of the quota window.
+    Reset,
+    /// Consumption multiplier such as `3x`, when above 1x.
+    Multiplier,
+    /// The segment's default main text.
+    Primary,
+    /// The segment's default supplemental text.
+    Secondary,
+    /// Spike marker while usage is spiking.
+    Spike,
+}
+
+/// Placeholder names in the order they are listed in error messages.
+const PLACEHOLDERS: [(&str, Placeholder); 10] = [
+    ("icon", Placeholder::Icon),
+    ("percent", Placeholder::Percent),
+    ("bar", Placeholder::Bar),
+    ("used", Placeholder::Used),
+    ("limit", Placeholder::Limit),
+    ("reset", Placeholder::Reset),
+    ("multiplier", Placeholder::Multiplier),
+    ("primary", Placeholder::Primary),
+    ("secondary", Placeholder::Secondary),
+    ("spike", Placeholder::Spike),
+];
+
+impl Placeholder {
+    fn from_name(name: &str) -> Option<Self> {
+        PLACEHOLDERS
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, p)| *p)
+    }
+}
+
+/// A piece of a parsed format template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FormatPiece {
+    /// Text copied as is.
+    Literal(String),
+    /// A value filled in from segment data.
+    Placeholder(Placeholder),
+}
+
+/// A parsed segment format template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatTemplate {
+    pieces: Vec<FormatPiece>,
+}
+
+/// Error returned for an invalid format template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FormatError {
+    /// A `{name}` that is not a known placeholder.
+    UnknownPlaceholder { name: String, column: usize },
+    /// A `{` without a closing `}`.
+    Unclosed { column: usize },
+    /// A `}` without an opening `{`.
+    UnmatchedClose { column: usize },
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FormatError::UnknownPlaceholder { name, column } => {
+                write!(f, "unknown placeholder {{{name}}} at column {column}")?;
+                let suggestion = PLACEHOLDERS
+                    .iter()
+                    .map(|(n, _)| *n)
+                    .find(|n| !name.is_empty() && (n.starts_with(name) || name.starts_with(n)));
+                if let Some(suggestion) = suggestion {
+                    write!(f, " (did you mean {{{suggestion}}}?)")?;
+                }
+                let names: Vec<String> = PLACEHOLDERS
+                    .iter()
+                    .map(|(n, _)| format!("{{{n}}}"))
+                    .collect();
+                write!(f, "; available: {}", names.join(" "))
+            }
+            FormatError::Unclosed { column } => write!(
+                f,
+                "unclosed \"{{\" at column {column} (use \"{{{{\" for a literal brace)"
+            ),
+            FormatError::UnmatchedClose { column } => write!(
+                f,
+                "unmatched \"}}\" at column {column} (use \"}}}}\" for a literal brace)"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for FormatError {}
+
+impl FormatTemplate {
+    /// Parses a format template.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for unknown placeholders and unbalanced braces.
+    pub fn parse(source: &str) -> Result<Self, FormatError> {
+        let mut pieces = Vec::new();
+        let mut literal = String::new();
+        let mut chars = source.chars().zip(1..).peekable();
+
+        while let Some((c, column)) = chars.next() {
+            match c {
+                '{' if chars.peek().is_some_and(|(next, _)| *next == '{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek().is_some_and(|(next, _)| *next == '}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some(('}', _)) => break,
+                            Some(('{', _)) | None => return Err(FormatError::Unclosed { column }),
+                            Some((c, _)) => name.push(c),
+                        }
+                    }
+                    let placeholder = Placeholder::from_name(name.trim())
+                        .ok_or(FormatError::UnknownPlaceholder { name, column })?;
+                    if !literal.is_empty() {
+                        pieces.push(FormatPiece::Literal(std::mem::take(&mut literal)));
+                    }
+                    pieces.push(FormatPiece::Placeholder(placeholder));
+                }
+                '}' => return Err(FormatError::UnmatchedClose { column }),
+                c => literal.push(c),
+            }
+        }
+        if !literal.is_empty() {
+            pieces.push(FormatPiece::Literal(literal));
+        }
+        Ok(Self { pieces })
+    }
+
+    /// Returns the parsed pieces in order.
+    #[must_use]
+    pub fn pieces(&self) -> &[FormatPiece] {
+        &self.pieces
+    }
+}
+
+/// A segment's `format` or `compact_format` setting.
+///
+/// Keeps the configured text for serialization next to the template parsed
+/// from it. An invalid template is kept with its error, which `check`
+/// reports; the status line then uses the segment's default layout.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub struct SegmentFormat {
+    source: String,
+    template: Result<FormatTemplate, FormatError>,
+}
+
+impl SegmentFormat {
+    /// Parses `source` into a segment format.
+    #[must_use]
+    pub fn new(source: impl Into<String>) -> Self {
+        let source = source.into();
+        let template = FormatTemplate::parse(&source);
+        Self { source, template }
+    }
+
+    /// Returns the template text as configured.
+    #[must_use]
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Returns the parsed template.
+    ///
+    /// # Errors
+    ///
+    /// Returns the parse error of an invalid template.
+    pub fn template(&self) -> Result<&FormatTemplate, &FormatError> {
+        self.template.as_ref()
+    }
+}
+
+impl From<String> for SegmentFormat {
+    fn from(source: String) -> Self {
+        Self::new(source)
+    }
+}
+
+impl From<SegmentFormat> for String {
+    fn from(format: SegmentFormat) -> Self {
+        format.source
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_segment_format_parses_once_and_keeps_source() {
+        let format: SegmentFormat = serde_json::from_str(r#""{icon} {percent}%""#).unwrap();
+        assert_eq!(format.source(), "{icon} {percent}%");
+        assert_eq!(format.template().unwrap().pieces().len(), 4);
+        assert_eq!(
+            serde_json::to_string(&format).unwrap(),
+            r#""{icon} {percent}%""#
+        );
+
+        let invalid = SegmentFormat::new("{pct}");
+        assert_eq!(invalid.source(), "{pct}");
+        assert!(matches!(
+            invalid.template(),
+            Err(FormatError::UnknownPlaceholder { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_placeholders_and_literals() {
+        let template = FormatTemplate::parse("{icon} {percent}% {used}/{limit}").unwrap();
+        assert_eq!(
+            template.pieces(),
+            [
+                FormatPiece::Placeholder(Placeholder::Icon),
+                FormatPiece::Literal(" ".to_string()),
+                FormatPiece::Placeholder(Placeholder::Percent),
+                FormatPiece::Literal("% ".to_string()),
+                FormatPiece::Placeholder(Placeholder::Used),
+                FormatPiece::Literal("/".to_string()),
+                FormatPiece::Placeholder(Placeholder::Limit),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_escaped_braces() {
+        let template = FormatTemplate::parse("{{{percent}}}").unwrap();
+        assert_eq!(
+            template.pieces(),
+            [
+                FormatPiece::Literal("{".to_string()),
+                FormatPiece::Placeholder(Placeholder::Percent),
+                FormatPiece::Literal("}".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_unknown_placeholder() {
+        let err = FormatTemplate::parse("{icon} {percentage}").unwrap_err();
+        assert_eq!(
+            err,
+            FormatError::UnknownPlaceholder {
+                name: "percentage".to_string(),
+                column: 8
+            }
+        );
+        let message = err.to_string();
+        assert!(message.contains("did you mean {percent}?"), "{message}");
+        assert!(message.contains("{multiplier}"), "{message}");
+    }
+
+    #[test]
+    fn test_parse_unbalanced_braces() {
+        assert_eq!(
+            FormatTemplate::parse("{percent").unwrap_err(),
+            FormatError::Unclosed { column: 1 }
+        );
+        assert_eq!(
+            FormatTemplate::parse("50%}").unwrap_err(),
+            FormatError::UnmatchedClose { column: 4 }
+        );
+    }
+}
@@ -5,14 +5,24 @@
 
 use super::migration::{self, MigrationResult};
 use super::template;
-use super::types::Config;
+use super::theme::Theme;
+use super::types::{Config, Locale};
 use anyhow::{Context, Result};
 use std::collections::HashSet;
 use std::fs;
 use std::path::{Path, PathBuf};
 
 /// Valid segment identifiers.
-const VALID_SEGMENT_IDS: &[&str] = &["token_usage", "weekly_usage", "mcp_usage"];
+const VALID_SEGMENT_IDS: &[&str] = &[
+    "token_usage",
+    "weekly_usage",
+    "mcp_usage",
+    "session",
+    "context",
+    "cost",
+    "value",
+    "budget",
+];
 
 /// Configuration loading and management trait.
 pub trait ConfigLoader {
@@ -42,7 +52,8 @@ pub trait ConfigLoader {
     ///
     /// # Errors
     ///
-    /// Returns an error if segments are empty, contain duplicate IDs, or invalid segment IDs.
+    /// Returns an error if segments are empty, contain duplicate IDs, or invalid segment IDs,
+    /// or if the theme cannot be loaded.
     fn check(&self) -> Result<()>;
 }
 
@@ -69,8 +80,17 @@ impl ConfigLoader for Config {
             let _ = write_migrated_config(&path, &value);
         }
 
-        let config = deserialize_migrated_value(&value);
-        config.merge_default_segments()
+        let mut config = deserialize_migrated_value(&value).merge_default_segments();
+        // An unknown or broken theme is reported by `check`
+        if let Some(theme) = config
+            .style
+            .theme
+            .as_deref()
+            .and_then(|n| Theme::load(n).ok())
+        {
+            theme.apply(&mut config);
+        }
+        config
     }
 
     fn load_for_update() -> Result<(Config, Option<MigrationResult>)> {
@@ -111,9 +131,7 @@ impl ConfigLoader for Config {
             )
         })?;
 
-        let config_template = include_str!("../config_template.toml");
-
-        fs::write(&config_path, config_template)
+        fs::write(&config_path, template::template(Locale::Auto))
             .with_context(|| format!("Failed to write config file: {}", config_path.display()))?;
 
         Ok(config_path)
@@ -156,10 +174,174 @@ impl ConfigLoader for Config {
             }
         }
 
+        if let Some(name) = &self.style.theme {
+            if let Err(e) = Theme::load(name) {
+                anyhow::bail!("Invalid style.theme: {e:#}");
+            }
+        }
+
+        check_segment_styles(self)?;
+
+        for alert in &self.alerts {
+            if alert.threshold > 100 {
+                anyhow::bail!(
+                    "Invalid alert threshold for {}: {} (must be 0-100)",
+                    alert.quota.as_str(),
+                    alert.threshold
+                );
+            }
+            let has_webhook = self
+                .webhook
+                .wants(crate::config::WebhookEventKind::Threshold);
+            if alert.command.is_none() && !alert.notify && !has_webhook {
+                anyhow::bail!(
+                    "Alert {} has no action: set `command`, `notify = true` or a [webhook] url",
+                    alert.key()
+                );
+            }
+        }
+
+        let budgets = [
+            ("daily", self.budget.daily),
+            ("five_hour", self.budget.five_hour),
+            ("weekly", self.budget.weekly),
+        ];
+        for (window, budget) in budgets {
+            if let Some(pct) = budget.filter(|pct| !(1..=100).contains(pct)) {
+                anyhow::bail!("Invalid [budget] {window}: {pct} (must be 1-100)");
+            }
+        }
+
+        if self.spike.enabled {
+            if self.spike.window_minutes == 0
+                || self.spike.baseline_minutes <= self.spike.window_minutes
+            {
+                anyhow::bail!(
+                    "Invalid [spike] windows: baseline_minutes ({}) must be greater than window_minutes ({}) and window_minutes must be at least 1",
+                    self.spike.baseline_minutes,
+                    self.spike.window_minutes
+                );
+            }
+            if self.spike.factor < 1.0 || self.spike.min_rate <= 0.0 {
+                anyhow::bail!(
+                    "Invalid [spike] thresholds: factor must be at least 1 and min_rate positive"
+                );
+            }
+        }
+
+        if let Some(url) = self.webhook.enabled_url() {
+            if !url.starts_with("http://") && !url.starts_with("https://") {
+                anyhow::bail!("Invalid webhook url: {url} (must start with http:// or https://)");
+            }
+        }
+
         Ok(())
     }
 }
 
+/// Validates segment format templates, bar widths, and
+/// `[style.colors]`/`[segments.colors]`.
+fn check_segment_styles(config: &Config) -> Result<()> {
+    for segment in &config.segments {
+        let formats = [
+            ("format", &segment.format),
+            ("compact_format", &segment.compact_format),
+        ];
+        for (field, format) in formats {
+            if let Some(format) = format {
+                if let Err(e) = format.template() {
+                    anyhow::bail!(
+                        "Invalid {field} {:?} of segment {}: {e}",
+                        format.source(),
+                        segment.id
+                    );
+                }
+            }
+        }
+        if segment.line == 0 {
+            anyhow::bail!(
+                "Invalid line of segment {}: 0 (lines are numbered from 1)",
+                segment.id
+            );
+        }
+        if let Some(min) = segment.show_when.min_percentage.filter(|m| *m > 100) {
+            anyhow::bail!(
+                "Invalid show_when.min_percentage of segment {}: {min} (must be 0-100)",
+                segment.id
+            );
+        }
+        if let Some(above) = segment
+            .show_when
+            .multiplier_above
+            .filter(|a| !a.is_finite() || *a < 0.0)
+        {
+            anyhow::bail!(
+                "Invalid show_when.multiplier_above of segment {}: {above} (must be 0 or more)",
+                segment.id
+            );
+        }
+        if let Some(level) = segment.icon.levels.iter().find(|l| l.from > 100) {
+            anyhow::bail!(
+                "Invalid icon level of segment {}: from = {} (must be 0-100)",
+                segment.id,
+                level.from
+            );
+        }
+        if let Some(Err(_)) = crate::config::QuotaDisplay::from_options(&segment.options) {
+            anyhow::bail!(
+                "Invalid display of segment {}: {} (must be percent, used_limit, remaining or remaining_percent)",
+                segment.id,
+                segment.options["display"]
+            );
+        }
+        if let Some(width) = segment.options.get("bar_width") {
+            let max = crate::core::bar::MAX_BAR_WIDTH;
+            if !width
+                .as_u64()
+                .is_some_and(|w| (1..=u64::from(max)).contains(&w))
+            {
+                anyhow::bail!(
+                    "Invalid bar_width of segment {}: {width} (must be 1-{max})",
+                    segment.id
+                );
+            }
+        }
+    }
+
+    let color_sections = std::iter::once(("[style.colors]".to_string(), &config.style.colors))
+        .chain(
+            config
+                .segments
+                .iter()
+                .map(|s| (format!("[segments.colors] of {}", s.id), &s.colors)),
+        );
+    for (section, colors) in color_sections {
+        for (field, color) in colors.entries() {
+            if let Err(e) = crate::config::Color::parse(color) {
+                anyhow::bail!("Invalid {section} {field}: {e}");
+            }
+        }
+        if let Some(t) = colors.thresholds.iter().find(|t| t.from > 100) {
+            anyhow::bail!(
+                "Invalid {section} thresholds: from = {} (must be 0-100)",
+                t.from
+            );
+        }
+        if let Some(m) = colors
+            .multiplier
+            .iter()
+            .find(|m| m.from.is_nan() || m.from < 0.0)
+        {
+            anyhow::bail!(
+                "Invalid {section} multiplier: from = {} (must be 0 or more)",
+                m.from
+            );
+        }
+    }
+
+    Ok(())
+}
+
 /// Deserializes a migrated `toml::Value` into a `Config`.
 ///
 /// Uses `toml::to_string` instead of `Value::to_string` because the latter
@@ -185,7 +367,7 @@ fn write_migrated_config(path: &Path, value: &toml::Value) -> Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::SegmentConfig;
+    use crate::config::{SegmentConfig, SegmentFormat};
 
     #[test]
     fn test_check_valid() {
@@ -457,6 +639,206 @@ timeout_ms = 3000
         assert!(config.check().is_ok());
     }
 
+    #[test]
+    fn test_check_session_segment_valid() {
+        let config = Config {
+            segments: vec![SegmentConfig::token_usage(), SegmentConfig::session()],
+            ..Config::default()
+        };
+        config.check().unwrap();
+    }
+
+    fn alert(threshold: u8, notify: bool) -> crate::config::AlertRule {
+        crate::config::AlertRule {
+            quota: crate::config::AlertQuota::TokenUsage,
+            threshold,
+            direction: crate::config::AlertDirection::Above,
+            command: None,
+            notify,
+        }
+    }
+
+    #[test]
+    fn test_check_alert_valid() {
+        let config = Config {
+            alerts: vec![alert(80, true)],
+            ..Config::default()
+        };
+        config.check().unwrap();
+    }
+
+    #[test]
+    fn test_check_alert_threshold_out_of_range() {
+        let config = Config {
+            alerts: vec![alert(120, true)],
+            ..Config::default()
+        };
+        let err = config.check().unwrap_err();
+        assert!(err.to_string().contains("Invalid alert threshold"));
+    }
+
+    #[test]
+    fn test_check_alert_without_action() {
+        let config = Config {
+            alerts: vec![alert(80, false)],
+            ..Config::default()
+        };
+        let err = config.check().unwrap_err();
+        assert!(err.to_string().contains("no action"));
+    }
+
+    #[test]
+    fn test_check_budget_out_of_range() {
+        let mut config = Config::default();
+        config.budget.weekly = Some(70);
+        config.check().unwrap();
+
+        config.budget.daily = Some(0);
+        let err = config.check().unwrap_err();
+        assert!(err.to_string().contains("[budget] daily"));
+    }
+
+    #[test]
+    fn test_check_colors() {
+        let mut config = Config::default();
+        config.style.colors.thresholds = vec![crate::config::ColorThreshold {
+            from: 0,
+            color: "#00ff00".to_string(),
+        }];
+        config.segments[0].colors.secondary = Some("245".to_string());
+        config.check().unwrap();
+
+        config.segments[0].colors.secondary = Some("purple".to_string());
+        let err = config.check().unwrap_err().to_string();
+        assert!(
+            err.contains("[segments.colors] of token_usage secondary"),
+            "{err}"
+        );
+
+        config.segments[0].colors.secondary = None;
+        config.style.colors.thresholds[0].from = 120;
+        let err = config.check().unwrap_err().to_string();
+        assert!(err.contains("[style.colors] thresholds"), "{err}");
+    }
+
+    #[test]
+    fn test_check_format() {
+        let mut config = Config::default();
+        config.segments[0].format = Some(SegmentFormat::new("{icon} {percent}% {bar}"));
+        config.check().unwrap();
+
+        config.segments[1].format = Some(SegmentFormat::new("{icon} {pct}%"));
+        let err = config.check().unwrap_err().to_string();
+        assert!(err.contains("of segment weekly_usage"), "{err}");
+        assert!(
+            err.contains("unknown placeholder {pct} at column 8"),
+            "{err}"
+        );
+
+        config.segments[1].format = None;
+        config.segments[2].compact_format = Some(SegmentFormat::new("{used"));
+        let err = config.check().unwrap_err().to_string();
+        assert!(
+            err.contains("Invalid compact_format \"{used\" of segment mcp_usage"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_check_bar_width() {
+        let mut config = Config::default();
+        config.segments[0]
+            .options
+            .insert("bar_width".to_string(), serde_json::json!(12));
+        config.check().unwrap();
+
+        config.segments[0]
+            .options
+            .insert("bar_width".to_string(), serde_json::json!(0));
+        let err = config.check().unwrap_err().to_string();
+        assert!(err.contains("bar_width of segment token_usage: 0"), "{err}");
+    }
+
+    #[test]
+    fn test_check_line() {
+        let mut config = Config::default();
+        config.segments[2].line = 2;
+        config.check().unwrap();
+
+        config.segments[2].line = 0;
+        let err = config.check().unwrap_err().to_string();
+        assert!(err.contains("line of segment mcp_usage: 0"), "{err}");
+    }
+
+    #[test]
+    fn test_check_icon_levels() {
+        let mut config = Config::default();
+        config.segments[0]
+            .icon
+            .levels
+            .push(crate::config::IconLevel {
+                from: 101,
+                icon: crate::config::IconVariants::default(),
+            });
+        let err = config.check().unwrap_err().to_string();
+        assert!(
+            err.contains("icon level of segment token_usage: from = 101"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_check_theme() {
+        let mut config = Config::default();
+        config.style.theme = Some("minimal".to_string());
+        config.check().unwrap();
+
+        config.style.theme = Some("no-such-theme-here".to_string());
+        let err = config.check().unwrap_err().to_string();
+        assert!(
+            err.contains("Invalid style.theme: Unknown theme: no-such-theme-here"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_check_show_when() {
+        let mut config = Config::default();
+        config.segments[1].show_when.min_percentage = Some(50);
+        config.segments[1].show_when.multiplier_above = Some(1.0);
+        config.check().unwrap();
+
+        config.segments[1].show_when.min_percentage = Some(150);
+        let err = config.check().unwrap_err().to_string();
+        assert!(
+            err.contains("show_when.min_percentage of segment weekly_usage: 150"),
+            "{err}"
+        );
+
+        config.segments[1].show_when.min_percentage = None;
+        config.segments[1].show_when.multiplier_above = Some(-1.0);
+        let err = config.check().unwrap_err().to_string();
+        assert!(err.contains("show_when.multiplier_above"), "{err}");
+    }
+
+    #[test]
+    fn test_check_quota_display() {
+        let mut config = Config::default();
+        config.segments[1]
+            .options
+            .insert("display".to_string(), serde_json::json!("used_limit"));
+        config.check().unwrap();
+
+        config.segments[1]
+            .options
+            .insert("display".to_string(), serde_json::json!("tokens"));
+        let err = config.check().unwrap_err().to_string();
+        assert!(
+            err.contains("Invalid display of segment weekly_usage: \"tokens\""),
+            "{err}"
+        );
+    }
+
     #[test]
     fn test_print_outputs_toml() {
         let config = Config::default();
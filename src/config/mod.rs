@@ -1,10 +1,19 @@
 //! Configuration loading and types for the GLM plan usage plugin.
 
+mod color;
+mod format;
 mod loader;
 mod migration;
 mod template;
+mod theme;
 mod types;
 
+#[doc(inline)]
+#[allow(unused_imports, reason = "re-exported for public API")]
+pub use color::{Color, ColorParseError};
+#[doc(inline)]
+#[allow(unused_imports, reason = "re-exported for public API")]
+pub use format::{FormatError, FormatPiece, FormatTemplate, Placeholder, SegmentFormat};
 #[doc(inline)]
 pub use loader::ConfigLoader;
 #[doc(inline)]
@@ -12,7 +21,13 @@ pub use loader::ConfigLoader;
 pub use migration::MigrationResult;
 #[doc(inline)]
 #[allow(unused_imports, reason = "re-exported for public API")]
+pub use theme::{list as list_themes, themes_dir, Theme, ThemeInfo, ThemeSegment};
+#[doc(inline)]
+#[allow(unused_imports, reason = "re-exported for public API")]
 pub use types::{
-    ApiConfig, CacheConfig, Config, DisplayMode, IconConfig, InputData, MultiplierConfig,
-    PromoConfig, SegmentConfig, StyleConfig, DEFAULT_SEPARATOR,
+    AlertDirection, AlertQuota, AlertRule, ApiConfig, BudgetConfig, CacheConfig, ColorConfig,
+    ColorDepth, ColorThreshold, Config, CostInfo, DisplayMode, IconConfig, IconLevel, IconVariants,
+    InputData, Locale, ModelPrice, MultiplierColor, MultiplierConfig, PricingConfig, PromoConfig,
+    QuotaDisplay, SegmentConfig, ShowWhen, SpikeConfig, StyleConfig, TrackingConfig, WebhookConfig,
+    WebhookEventKind, WebhookFormat, DEFAULT_SEPARATOR,
 };
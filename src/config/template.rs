@@ -3,16 +3,31 @@
 //! This module handles generating configuration files by overlaying
 //! user values onto the commented template.
 
+use super::types::Locale;
 use std::fmt::Write;
 use toml::Value;
 
+/// Returns the commented config template in `locale`.
+pub fn template(locale: Locale) -> &'static str {
+    match locale.resolve() {
+        Locale::Zh => include_str!("../config_template.zh.toml"),
+        _ => include_str!("../config_template.toml"),
+    }
+}
+
 /// Generate a new config file by overlaying user values onto the commented template.
 ///
 /// The template has all fields commented out. This function produces a valid TOML
 /// file where user-customized fields are uncommented, and all others stay commented.
-/// Segment blocks are reordered to match user segment order.
+/// Segment blocks are reordered to match user segment order. The template
+/// language follows the user's `style.locale`.
 pub fn generate_overlay(user_values: &Value) -> String {
-    let template = include_str!("../config_template.toml");
+    let locale = user_values
+        .get("style")
+        .and_then(|style| style.get("locale"))
+        .and_then(|locale| locale.clone().try_into::<Locale>().ok())
+        .unwrap_or_default();
+    let template = template(locale);
     let lines: Vec<&str> = template.lines().collect();
 
     // Phase 1: Parse template into sections and segment blocks
@@ -57,6 +72,10 @@ pub fn generate_overlay(user_values: &Value) -> String {
         }
     }
 
+    // Output other arrays of tables (e.g. [[alerts]]) verbatim; the template
+    // only documents them in comments
+    output.push_str(&render_extra_arrays(user_values));
+
     // Strip leading/trailing whitespace and ensure trailing newline
     let trimmed = output.trim_start();
     if trimmed.is_empty() {
@@ -219,7 +238,9 @@ fn render_section(section: &Section, user_values: &Value) -> String {
 /// Renders a segment block by overlaying user values onto commented template lines.
 ///
 /// When `as_comment` is true, renders as fully commented (unused segment).
-/// When false, uncomments keys that have user-provided values.
+/// When false, uncomments keys that have user-provided values. User keys and
+/// subtables the template does not document (e.g. `[segments.colors]` on
+/// most segments) are appended so they survive the rewrite.
 fn render_segment_block(
     block: &SegmentBlock,
     seg_value: Option<&Value>,
@@ -227,10 +248,18 @@ fn render_segment_block(
 ) -> String {
     let mut output = String::new();
     let mut current_path: Vec<String> = Vec::new();
+    let mut documented: Vec<String> = Vec::new();
+    let mut subsections: Vec<String> = Vec::new();
+    let user_value = seg_value.filter(|_| !as_comment);
 
     for line in &block.lines {
         let trimmed = line.trim();
 
+        if parse_commented_section_header(trimmed).is_some() {
+            render_undocumented_keys(&mut output, user_value, &current_path, &documented);
+            documented.clear();
+        }
+
         if is_commented_segments_header(trimmed) {
             if as_comment {
                 output.push_str(line);
@@ -246,6 +275,7 @@ fn render_segment_block(
 
         if let Some(header) = parse_commented_section_header(trimmed) {
             if is_segment_subsection(trimmed) {
+                subsections.extend(header.last().cloned());
                 current_path = header;
                 if as_comment {
                     output.push_str(line);
@@ -264,6 +294,7 @@ fn render_segment_block(
         }
 
         if let Some(kv) = parse_commented_kv(trimmed) {
+            documented.push(kv.key.clone());
             if as_comment {
                 output.push_str(line);
                 output.push('\n');
@@ -284,7 +315,85 @@ fn render_segment_block(
         output.push_str(line);
         output.push('\n');
     }
+    render_undocumented_keys(&mut output, user_value, &current_path, &documented);
+
+    // Subtables with no commented counterpart in this block
+    if let Some(table) = user_value.and_then(Value::as_table) {
+        for (name, value) in table {
+            let Some(sub_table) = value.as_table() else {
+                continue;
+            };
+            if subsections.contains(name) || sub_table.is_empty() {
+                continue;
+            }
+            write!(output, "\n[segments.{}]\n", format_toml_key(name)).unwrap();
+            for (key, val) in sub_table {
+                writeln!(
+                    output,
+                    "{} = {}",
+                    format_toml_key(key),
+                    format_toml_value(val)
+                )
+                .unwrap();
+            }
+        }
+    }
+
+    output
+}
+
+/// Appends user keys of a segment or segment subtable that the template does
+/// not document. Subtables of the segment itself are handled separately by
+/// the caller; tables nested in a subtable (e.g. `icon.stale`) are inlined.
+fn render_undocumented_keys(
+    output: &mut String,
+    seg_value: Option<&Value>,
+    path: &[String],
+    documented: &[String],
+) {
+    let Some(table) = seg_value
+        .and_then(|v| get_segment_table(v, path))
+        .and_then(Value::as_table)
+    else {
+        return;
+    };
+    for (key, val) in table {
+        let handled_by_caller = path.is_empty() && val.is_table();
+        if !documented.contains(key) && !handled_by_caller {
+            writeln!(
+                output,
+                "{} = {}",
+                format_toml_key(key),
+                format_toml_value(val)
+            )
+            .unwrap();
+        }
+    }
+}
 
+/// Renders user arrays of tables other than `segments`.
+///
+/// These have no commented counterpart in the template, so they are
+/// serialized as-is and appended after the segment blocks.
+fn render_extra_arrays(user_values: &Value) -> String {
+    let Some(table) = user_values.as_table() else {
+        return String::new();
+    };
+    let mut output = String::new();
+    for (key, value) in table {
+        let is_table_array = value
+            .as_array()
+            .is_some_and(|arr| !arr.is_empty() && arr.iter().all(Value::is_table));
+        if key == "segments" || !is_table_array {
+            continue;
+        }
+        let mut wrapper = toml::map::Map::new();
+        wrapper.insert(key.clone(), value.clone());
+        if let Ok(rendered) = toml::to_string(&Value::Table(wrapper)) {
+            output.push('\n');
+            output.push_str(&rendered);
+        }
+    }
     output
 }
 
@@ -351,16 +460,19 @@ fn get_user_value<'a>(values: &'a Value, path: &[String], key: &str) -> Option<&
 }
 
 fn get_segment_field<'a>(seg_value: &'a Value, path: &[String], key: &str) -> Option<&'a Value> {
-    if path.is_empty() {
-        seg_value.get(key)
-    } else {
-        let mut current = seg_value;
-        for p in path {
-            let cleaned = p.strip_prefix("segments.").unwrap_or(p);
-            current = current.get(cleaned)?;
+    get_segment_table(seg_value, path)?.get(key)
+}
+
+fn get_segment_table<'a>(seg_value: &'a Value, path: &[String]) -> Option<&'a Value> {
+    let mut current = seg_value;
+    for p in path {
+        let cleaned = p.strip_prefix("segments.").unwrap_or(p);
+        if cleaned == "segments" {
+            continue;
         }
-        current.get(key)
+        current = current.get(cleaned)?;
     }
+    Some(current)
 }
 
 fn find_segment_value<'a>(values: &'a Value, id: &str) -> Option<&'a Value> {
@@ -386,11 +498,24 @@ fn section_has_data_in_path(lines: &[String], user_values: &Value, path: &[Strin
     false
 }
 
+/// Formats a table key, quoting it unless it is a valid bare key.
+fn format_toml_key(key: &str) -> String {
+    let bare = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if bare {
+        key.to_string()
+    } else {
+        format!("\"{key}\"")
+    }
+}
+
 fn format_toml_value(value: &Value) -> String {
     match value {
         Value::String(s) => format!("\"{s}\""),
         Value::Integer(i) => i.to_string(),
-        Value::Float(f) => format!("{f}"),
+        Value::Float(_) => value.to_string(),
         Value::Boolean(b) => b.to_string(),
         Value::Array(arr) => {
             let items: Vec<String> = arr.iter().map(format_toml_value).collect();
@@ -399,7 +524,7 @@ fn format_toml_value(value: &Value) -> String {
         Value::Table(t) => {
             let items: Vec<String> = t
                 .iter()
-                .map(|(k, v)| format!("{} = {}", k, format_toml_value(v)))
+                .map(|(k, v)| format!("{} = {}", format_toml_key(k), format_toml_value(v)))
                 .collect();
             format!("{{ {} }}", items.join(", "))
         }
@@ -426,6 +551,38 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_templates_document_the_same_keys() {
+        let structure = |locale| -> Vec<&str> {
+            template(locale)
+                .lines()
+                .map(str::trim)
+                .filter(|line| {
+                    parse_commented_section_header(line).is_some()
+                        || is_commented_segments_header(line)
+                        || parse_commented_kv(line).is_some_and(|kv| {
+                            kv.key.chars().all(|c| c.is_ascii_lowercase() || c == '_')
+                        })
+                })
+                .collect()
+        };
+        assert_eq!(structure(Locale::Zh), structure(Locale::En));
+    }
+
+    #[test]
+    fn test_generate_overlay_follows_locale() {
+        let mut user = toml::map::Map::new();
+        let mut style = toml::map::Map::new();
+        style.insert("locale".into(), Value::String("zh".into()));
+        user.insert("style".into(), Value::Table(style));
+
+        let result = generate_overlay(&Value::Table(user));
+        assert!(result.contains("# 样式配置"));
+        assert!(result.contains("locale = \"zh\""));
+        assert!(result.contains("# mode = \"auto\""));
+        toml::from_str::<Value>(&result).unwrap();
+    }
+
     #[test]
     fn test_single_value_overlay() {
         let mut user = toml::map::Map::new();
@@ -635,6 +792,147 @@ enabled = false
         assert!(result.contains("enabled = false"));
     }
 
+    #[test]
+    fn test_generate_overlay_roundtrips_pricing_models() {
+        let raw: Value = toml::from_str(
+            r#"
+[pricing]
+plan_price = 20.0
+[pricing.models."glm-5.1"]
+input = 1.5
+output = 4.0
+"#,
+        )
+        .unwrap();
+
+        let result = generate_overlay(&raw);
+        let reparsed: Value = toml::from_str(&result).unwrap();
+        assert_eq!(
+            reparsed["pricing"]["plan_price"],
+            raw["pricing"]["plan_price"]
+        );
+        assert_eq!(reparsed["pricing"]["models"], raw["pricing"]["models"]);
+    }
+
+    #[test]
+    fn test_generate_overlay_keeps_alert_rules() {
+        let raw: Value = toml::from_str(
+            r#"
+[style]
+mode = "ascii"
+
+[[alerts]]
+quota = "token_usage"
+threshold = 90
+command = "echo hi"
+
+[[alerts]]
+quota = "weekly_usage"
+threshold = 80
+"#,
+        )
+        .unwrap();
+
+        let result = generate_overlay(&raw);
+        let reparsed: Value = toml::from_str(&result).unwrap();
+        assert_eq!(reparsed["alerts"], raw["alerts"]);
+        assert_eq!(reparsed["style"]["mode"], raw["style"]["mode"]);
+    }
+
+    #[test]
+    fn test_generate_overlay_keeps_webhook_settings() {
+        let raw: Value = toml::from_str(
+            r#"
+[webhook]
+url = "https://example.com/hook"
+format = "slack"
+events = ["reset"]
+"#,
+        )
+        .unwrap();
+
+        let result = generate_overlay(&raw);
+        let reparsed: Value = toml::from_str(&result).unwrap();
+        assert_eq!(reparsed["webhook"], raw["webhook"]);
+    }
+
+    #[test]
+    fn test_generate_overlay_keeps_color_settings() {
+        let raw: Value = toml::from_str(
+            r##"
+[style.colors]
+neutral = "#ff8800"
+thresholds = [{ from = 0, color = "green" }, { from = 50, color = "red" }]
+[[segments]]
+id = "token_usage"
+[segments.colors]
+thresholds = [{ from = 0, color = "blue" }]
+multiplier = [{ from = 2.0, color = "yellow" }]
+[[segments]]
+id = "mcp_usage"
+[segments.colors]
+neutral = "gray"
+"##,
+        )
+        .unwrap();
+
+        let result = generate_overlay(&raw);
+        let reparsed: Value = toml::from_str(&result).unwrap();
+        assert_eq!(reparsed["style"]["colors"], raw["style"]["colors"]);
+        let segments = reparsed["segments"].as_array().unwrap();
+        assert_eq!(segments[0]["colors"], raw["segments"][0]["colors"]);
+        assert_eq!(segments[1]["colors"], raw["segments"][1]["colors"]);
+    }
+
+    #[test]
+    fn test_generate_overlay_keeps_segment_formats() {
+        let raw: Value = toml::from_str(
+            r#"
+[[segments]]
+id = "token_usage"
+format = "{icon} {percent}% {bar}"
+[[segments]]
+id = "budget"
+format = "{icon} {primary}"
+"#,
+        )
+        .unwrap();
+
+        let result = generate_overlay(&raw);
+        let reparsed: Value = toml::from_str(&result).unwrap();
+        let segments = reparsed["segments"].as_array().unwrap();
+        assert_eq!(segments[0]["format"], raw["segments"][0]["format"]);
+        assert_eq!(segments[1]["format"], raw["segments"][1]["format"]);
+    }
+
+    #[test]
+    fn test_generate_overlay_keeps_icon_levels_and_states() {
+        let raw: Value = toml::from_str(
+            r##"
+[[segments]]
+id = "token_usage"
+[segments.icon]
+emoji = "🔋"
+ascii = "_"
+levels = [{ from = 80, emoji = "🪫", ascii = "#" }]
+error = { ascii = "!" }
+[[segments]]
+id = "budget"
+[segments.icon]
+emoji = "🎯"
+ascii = "b"
+stale = { ascii = "~" }
+"##,
+        )
+        .unwrap();
+
+        let result = generate_overlay(&raw);
+        let reparsed: Value = toml::from_str(&result).unwrap();
+        let segments = reparsed["segments"].as_array().unwrap();
+        assert_eq!(segments[0]["icon"], raw["segments"][0]["icon"]);
+        assert_eq!(segments[1]["icon"], raw["segments"][1]["icon"]);
+    }
+
     #[test]
     fn test_generate_overlay_preserves_user_segment_order() {
         let raw: Value = toml::from_str(
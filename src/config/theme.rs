@@ -0,0 +1,360 @@
+//! Named themes selected with `style.theme`.
+//!
+//! A theme bundles icons, colors, separators, and format templates. Themes
+//! are read from `~/.claude/glm-plan-usage/themes/<name>.toml`, where a file
+//! may also replace one of the built-in themes. A theme only fills in what
+//! the config leaves at its default, so settings in `config.toml` win.
+
+use super::format::SegmentFormat;
+use super::types::{ColorConfig, Config, IconConfig, SegmentConfig, DEFAULT_SEPARATOR};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// Built-in themes and their TOML sources.
+const BUILTIN_THEMES: [(&str, &str); 4] = [
+    ("default", include_str!("themes/default.toml")),
+    ("minimal", include_str!("themes/minimal.toml")),
+    ("powerline", include_str!("themes/powerline.toml")),
+    ("monochrome", include_str!("themes/monochrome.toml")),
+];
+
+/// A theme file.
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Theme {
+    /// One-line description shown by `theme list`.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    /// Separator string between segments.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub separator: Option<String>,
+    /// Separators of each status line row.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub line_separators: Vec<String>,
+    /// Draw segments on colored backgrounds joined by powerline arrows.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub powerline: Option<bool>,
+    /// Global colors, as in `[style.colors]`.
+    #[serde(default, skip_serializing_if = "ColorConfig::is_empty")]
+    pub colors: ColorConfig,
+    /// Segment styles by segment ID.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub segments: BTreeMap<String, ThemeSegment>,
+}
+
+/// Style of one segment in a theme (`[segments.<id>]`).
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ThemeSegment {
+    /// Icons replacing the built-in ones.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<IconConfig>,
+    /// Segment colors, as in `[segments.colors]`.
+    #[serde(default, skip_serializing_if = "ColorConfig::is_empty")]
+    pub colors: ColorConfig,
+    /// Layout template of the segment.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<SegmentFormat>,
+    /// Layout template used when the status line is too wide.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compact_format: Option<SegmentFormat>,
+}
+
+/// A theme available to `style.theme`, as listed by `theme list`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThemeInfo {
+    /// Theme name (the file name without `.toml`).
+    pub name: String,
+    /// Description from the theme file.
+    pub description: String,
+    /// Theme file, or `None` for a built-in theme.
+    pub path: Option<PathBuf>,
+}
+
+/// Returns the directory of user theme files.
+#[must_use]
+pub fn themes_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".claude").join("glm-plan-usage").join("themes"))
+}
+
+/// Returns `true` if `name` can be used as a theme file name.
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Returns `true` if every icon variant the segment sets is the built-in one
+/// and it has no icon levels or state icons.
+///
+/// Configs written by older versions set only the emoji and ASCII icons.
+fn has_builtin_icon(segment: &SegmentConfig) -> bool {
+    let Some(builtin) = SegmentConfig::builtin(&segment.id).map(|s| s.icon) else {
+        return false;
+    };
+    let icon = &segment.icon;
+    if !icon.levels.is_empty() || icon.stale.is_some() || icon.error.is_some() {
+        return false;
+    }
+    let variants = [
+        (&icon.emoji, &builtin.emoji),
+        (&icon.ascii, &builtin.ascii),
+        (&icon.nerd_font, &builtin.nerd_font),
+    ];
+    variants
+        .iter()
+        .all(|(set, default)| set.is_empty() || set == default)
+}
+
+impl Theme {
+    /// Parses a theme from TOML.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the TOML is invalid or has unknown keys.
+    pub fn parse(contents: &str) -> Result<Self> {
+        Ok(toml::from_str(contents)?)
+    }
+
+    /// Loads a theme by name, preferring a user file over a built-in theme.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the name is invalid, no such theme exists, or the
+    /// theme file cannot be read or parsed.
+    pub fn load(name: &str) -> Result<Self> {
+        if !is_valid_name(name) {
+            anyhow::bail!("Invalid theme name {name:?} (use letters, digits, '-' and '_')");
+        }
+        if let Some(path) = themes_dir()
+            .map(|dir| dir.join(format!("{name}.toml")))
+            .filter(|path| path.exists())
+        {
+            let contents = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read theme file: {}", path.display()))?;
+            return Self::parse(&contents)
+                .with_context(|| format!("Failed to parse theme file: {}", path.display()));
+        }
+        let Some((_, source)) = BUILTIN_THEMES.iter().find(|(n, _)| *n == name) else {
+            let names = list().into_iter().map(|t| t.name).collect::<Vec<_>>();
+            anyhow::bail!("Unknown theme: {name}. Available themes: {names:?}");
+        };
+        Self::parse(source).with_context(|| format!("Failed to parse built-in theme {name}"))
+    }
+
+    /// Applies the theme to everything `config` leaves at its default.
+    ///
+    /// Separators, formats, and colors are filled in where unset; icons
+    /// replace the built-in icons of a segment but not customized ones, and
+    /// `powerline = true` cannot be turned off by a theme.
+    pub fn apply(&self, config: &mut Config) {
+        let style = &mut config.style;
+        if let Some(separator) = &self.separator {
+            if style.separator == DEFAULT_SEPARATOR {
+                style.separator.clone_from(separator);
+            }
+        }
+        if style.line_separators.is_empty() {
+            style.line_separators.clone_from(&self.line_separators);
+        }
+        style.powerline |= self.powerline.unwrap_or(false);
+        style.colors.fill_from(&self.colors);
+
+        for segment in &mut config.segments {
+            let Some(theme) = self.segments.get(&segment.id) else {
+                continue;
+            };
+            if let Some(icon) = theme.icon.as_ref().filter(|_| has_builtin_icon(segment)) {
+                segment.icon = icon.clone();
+            }
+            if segment.format.is_none() {
+                segment.format.clone_from(&theme.format);
+            }
+            if segment.compact_format.is_none() {
+                segment.compact_format.clone_from(&theme.compact_format);
+            }
+            segment.colors.fill_from(&theme.colors);
+        }
+    }
+}
+
+/// Returns the built-in themes and the themes in [`themes_dir`], by name.
+///
+/// A theme file with the name of a built-in theme replaces it; unreadable
+/// theme files are listed without a description.
+#[must_use]
+pub fn list() -> Vec<ThemeInfo> {
+    let mut themes: BTreeMap<String, ThemeInfo> = BUILTIN_THEMES
+        .iter()
+        .map(|(name, source)| {
+            let info = ThemeInfo {
+                name: (*name).to_string(),
+                description: Theme::parse(source)
+                    .map(|t| t.description)
+                    .unwrap_or_default(),
+                path: None,
+            };
+            ((*name).to_string(), info)
+        })
+        .collect();
+
+    let entries = themes_dir().and_then(|dir| fs::read_dir(dir).ok());
+    for path in entries.into_iter().flatten().flatten().map(|e| e.path()) {
+        let Some(name) = path
+            .extension()
+            .filter(|ext| *ext == "toml")
+            .and_then(|_| path.file_stem())
+            .and_then(|stem| stem.to_str())
+            .filter(|stem| is_valid_name(stem))
+            .map(str::to_string)
+        else {
+            continue;
+        };
+        let description = fs::read_to_string(&path)
+            .ok()
+            .and_then(|contents| Theme::parse(&contents).ok())
+            .map(|t| t.description)
+            .unwrap_or_default();
+        let info = ThemeInfo {
+            name: name.clone(),
+            description,
+            path: Some(path),
+        };
+        themes.insert(name, info);
+    }
+
+    themes.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_themes_parse() {
+        for (name, source) in BUILTIN_THEMES {
+            let theme = Theme::parse(source).unwrap_or_else(|e| panic!("{name}: {e}"));
+            assert!(!theme.description.is_empty(), "{name} has no description");
+
+            let mut config = Config::default();
+            theme.apply(&mut config);
+            config.style.theme = Some(name.to_string());
+            assert!(
+                crate::config::ConfigLoader::check(&config).is_ok(),
+                "{name} does not pass check"
+            );
+        }
+    }
+
+    #[test]
+    fn test_default_theme_changes_nothing() {
+        let mut config = Config::default();
+        Theme::load("default").unwrap().apply(&mut config);
+        let default = Config::default();
+        assert_eq!(config.style.separator, default.style.separator);
+        assert!(config.style.colors.is_empty());
+        assert_eq!(config.segments[0].icon, default.segments[0].icon);
+    }
+
+    #[test]
+    fn test_apply_fills_defaults_only() {
+        let theme = Theme::parse(
+            r#"
+separator = " / "
+powerline = true
+
+[colors]
+neutral = "cyan"
+secondary = "gray"
+
+[segments.token_usage]
+format = "{icon} {percent}%"
+[segments.token_usage.icon]
+emoji = "T"
+ascii = "t"
+
+[segments.weekly_usage.icon]
+emoji = "W"
+ascii = "w"
+"#,
+        )
+        .unwrap();
+
+        let mut config = Config::default();
+        config.style.colors.secondary = Some("white".to_string());
+        config.segments[1].icon = IconConfig::new("📅", "W");
+        theme.apply(&mut config);
+
+        assert_eq!(config.style.separator, " / ");
+        assert!(config.style.powerline);
+        assert_eq!(config.style.colors.neutral.as_deref(), Some("cyan"));
+        assert_eq!(config.style.colors.secondary.as_deref(), Some("white"));
+        assert_eq!(config.segments[0].icon, IconConfig::new("T", "t"));
+        assert_eq!(
+            config.segments[0]
+                .format
+                .as_ref()
+                .map(SegmentFormat::source),
+            Some("{icon} {percent}%")
+        );
+        assert_eq!(config.segments[1].icon, IconConfig::new("📅", "W"));
+    }
+
+    #[test]
+    fn test_has_builtin_icon() {
+        let mut segment = SegmentConfig::token_usage();
+        assert!(has_builtin_icon(&segment));
+        segment.icon = IconConfig::new("🪙", "$");
+        assert!(has_builtin_icon(&segment));
+        segment.icon = IconConfig::new("🪙", "T");
+        assert!(!has_builtin_icon(&segment));
+        segment.icon = IconConfig::new("🪙", "$");
+        segment.icon.levels.push(crate::config::IconLevel {
+            from: 80,
+            icon: crate::config::IconVariants::default(),
+        });
+        assert!(!has_builtin_icon(&segment));
+    }
+
+    #[test]
+    fn test_apply_keeps_custom_separator() {
+        let theme = Theme {
+            separator: Some(" / ".to_string()),
+            ..Theme::default()
+        };
+        let mut config = Config::default();
+        config.style.separator = " :: ".to_string();
+        theme.apply(&mut config);
+        assert_eq!(config.style.separator, " :: ");
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_keys() {
+        let err = Theme::parse("seperator = \" / \"").unwrap_err().to_string();
+        assert!(err.contains("seperator"), "{err}");
+        let err = Theme::parse("[segments.token_usage]\nicons = \"x\"")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("icons"), "{err}");
+    }
+
+    #[test]
+    fn test_load_rejects_invalid_and_unknown_names() {
+        let err = Theme::load("../config").unwrap_err().to_string();
+        assert!(err.contains("Invalid theme name"), "{err}");
+        let err = Theme::load("no-such-theme-here").unwrap_err().to_string();
+        assert!(err.contains("Unknown theme: no-such-theme-here"), "{err}");
+    }
+
+    #[test]
+    fn test_is_valid_name() {
+        assert!(is_valid_name("solarized-dark_2"));
+        assert!(!is_valid_name(""));
+        assert!(!is_valid_name("a/b"));
+        assert!(!is_valid_name("a.toml"));
+    }
+}
@@ -3,15 +3,19 @@
 //! This module defines all configuration structures used by the plugin,
 //! including input data from Claude Code, display settings, and segment configuration.
 
+use super::format::SegmentFormat;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::OnceLock;
 
 pub const DEFAULT_SEPARATOR: &str = " | ";
 
 /// Input data received from Claude Code via stdin.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
 pub struct InputData {
+    /// Claude Code session identifier.
+    #[serde(default)]
+    pub session_id: Option<String>,
     #[serde(default)]
     #[allow(unused, reason = "deserialized from stdin, accessed by segments")]
     pub model: Option<ModelInfo>,
@@ -22,7 +26,6 @@ pub struct InputData {
     #[allow(unused, reason = "deserialized from stdin, accessed by segments")]
     pub transcript_path: Option<String>,
     #[serde(rename = "cost", default)]
-    #[allow(unused, reason = "deserialized from stdin, accessed by segments")]
     pub cost_info: Option<CostInfo>,
 }
 
@@ -45,15 +48,28 @@ pub struct WorkspaceInfo {
     pub current_dir: Option<String>,
 }
 
-/// Cost and token usage information.
-#[derive(Debug, Deserialize)]
+/// Session cost information reported by Claude Code.
+#[derive(Debug, Default, Deserialize)]
+#[allow(
+    clippy::struct_field_names,
+    reason = "field names mirror the statusline JSON schema"
+)]
 pub struct CostInfo {
+    /// Estimated session cost in USD.
     #[serde(default)]
-    #[allow(unused, reason = "deserialized from stdin, accessed by segments")]
-    pub tokens: Option<f64>,
+    pub total_cost_usd: Option<f64>,
+    /// Wall-clock session duration in milliseconds.
     #[serde(default)]
-    #[allow(unused, reason = "deserialized from stdin, accessed by segments")]
-    pub cost: Option<f64>,
+    pub total_duration_ms: Option<u64>,
+    /// Time spent waiting for API responses in milliseconds.
+    #[serde(default)]
+    pub total_api_duration_ms: Option<u64>,
+    /// Lines of code added during the session.
+    #[serde(default)]
+    pub total_lines_added: Option<u64>,
+    /// Lines of code removed during the session.
+    #[serde(default)]
+    pub total_lines_removed: Option<u64>,
 }
 
 /// Display mode for icons and styling.
@@ -64,6 +80,8 @@ pub enum DisplayMode {
     Auto,
     Emoji,
     Ascii,
+    /// Nerd Font glyphs; never chosen by Auto detection.
+    NerdFont,
 }
 
 /// Cached detection result for Auto mode
@@ -95,6 +113,150 @@ fn detect_display_mode() -> DisplayMode {
     DisplayMode::Emoji
 }
 
+/// Color depth used when emitting ANSI color escapes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ColorDepth {
+    #[default]
+    Auto,
+    /// No color escapes at all.
+    None,
+    /// The 16 basic ANSI colors.
+    #[serde(rename = "16")]
+    Ansi16,
+    /// The 256-color palette.
+    #[serde(rename = "256")]
+    Ansi256,
+    /// 24-bit colors.
+    Truecolor,
+}
+
+/// Cached detection result for Auto color depth
+static DETECTED_COLOR_DEPTH: OnceLock<ColorDepth> = OnceLock::new();
+
+/// Detects the color depth from the environment.
+fn detect_color_depth() -> ColorDepth {
+    let no_color = std::env::var("NO_COLOR").ok();
+    let colorterm = std::env::var("COLORTERM").ok();
+    let term = std::env::var("TERM").ok();
+    color_depth_from_env(no_color.as_deref(), colorterm.as_deref(), term.as_deref())
+}
+
+/// Picks a color depth from `NO_COLOR`, `COLORTERM`, and `TERM`.
+///
+/// A non-empty `NO_COLOR` disables colors (<https://no-color.org>). Unknown
+/// terminals get the 256-color palette, which the status line always used.
+fn color_depth_from_env(
+    no_color: Option<&str>,
+    colorterm: Option<&str>,
+    term: Option<&str>,
+) -> ColorDepth {
+    if no_color.is_some_and(|v| !v.is_empty()) {
+        return ColorDepth::None;
+    }
+    if colorterm.is_some_and(|v| v == "truecolor" || v == "24bit") {
+        return ColorDepth::Truecolor;
+    }
+    match term {
+        Some("dumb") => ColorDepth::None,
+        Some("linux" | "vt100" | "vt220" | "ansi") => ColorDepth::Ansi16,
+        _ => ColorDepth::Ansi256,
+    }
+}
+
+/// Language of segment labels, timers, and CLI messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Locale {
+    #[default]
+    Auto,
+    En,
+    /// Simplified Chinese.
+    Zh,
+}
+
+/// Cached detection result for the Auto locale
+static DETECTED_LOCALE: OnceLock<Locale> = OnceLock::new();
+
+/// Detects the locale from `LC_ALL`, `LC_MESSAGES`, and `LANG`.
+fn detect_locale() -> Locale {
+    let lc_all = std::env::var("LC_ALL").ok();
+    let lc_messages = std::env::var("LC_MESSAGES").ok();
+    let lang = std::env::var("LANG").ok();
+    locale_from_env(lc_all.as_deref(), lc_messages.as_deref(), lang.as_deref())
+}
+
+/// Picks a locale from the first non-empty locale variable, in POSIX order.
+///
+/// Any `zh` locale (`zh_CN.UTF-8`, `zh_TW`, ...) selects Chinese; everything
+/// else, including an unset environment, selects English.
+fn locale_from_env(lc_all: Option<&str>, lc_messages: Option<&str>, lang: Option<&str>) -> Locale {
+    let value = [lc_all, lc_messages, lang]
+        .into_iter()
+        .flatten()
+        .find(|v| !v.is_empty())
+        .unwrap_or_default();
+    if value.starts_with("zh") {
+        Locale::Zh
+    } else {
+        Locale::En
+    }
+}
+
+impl Locale {
+    /// Resolve the locale, detecting it from the environment if set to Auto.
+    #[must_use]
+    pub fn resolve(self) -> Locale {
+        match self {
+            Locale::Auto => *DETECTED_LOCALE.get_or_init(detect_locale),
+            locale => locale,
+        }
+    }
+
+    /// Returns `en` or `zh` depending on the resolved locale.
+    #[must_use]
+    pub fn pick<'a>(self, en: &'a str, zh: &'a str) -> &'a str {
+        match self.resolve() {
+            Locale::Zh => zh,
+            _ => en,
+        }
+    }
+}
+
+/// Cached terminal width used when `max_width` is unset
+static DETECTED_WIDTH: OnceLock<Option<usize>> = OnceLock::new();
+
+/// Detects the terminal width from `COLUMNS`, then the controlling terminal.
+fn detect_terminal_width() -> Option<usize> {
+    let columns = std::env::var("COLUMNS").ok();
+    columns_from_env(columns.as_deref()).or_else(tty_width)
+}
+
+/// Parses `COLUMNS`, ignoring empty, zero, and non-numeric values.
+fn columns_from_env(columns: Option<&str>) -> Option<usize> {
+    columns
+        .and_then(|c| c.trim().parse::<usize>().ok())
+        .filter(|c| *c > 0)
+}
+
+/// Asks `stty` for the size of the controlling terminal.
+///
+/// Claude Code pipes the status line's stdout, so the size is read from
+/// `/dev/tty` instead. Returns `None` without a terminal (e.g. on Windows).
+fn tty_width() -> Option<usize> {
+    let tty = std::fs::File::open("/dev/tty").ok()?;
+    let output = std::process::Command::new("stty")
+        .arg("size")
+        .stdin(tty)
+        .stderr(std::process::Stdio::null())
+        .output()
+        .ok()
+        .filter(|o| o.status.success())?;
+    // Output is "<rows> <columns>"
+    let size = String::from_utf8_lossy(&output.stdout);
+    columns_from_env(size.split_whitespace().nth(1))
+}
+
 /// Global configuration for the GLM plan usage plugin.
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Config {
@@ -113,6 +275,24 @@ pub struct Config {
     /// Multiplier calculation settings.
     #[serde(default)]
     pub multiplier: MultiplierConfig,
+    /// Local usage tracking settings.
+    #[serde(default)]
+    pub tracking: TrackingConfig,
+    /// Metered pricing and plan price settings.
+    #[serde(default)]
+    pub pricing: PricingConfig,
+    /// Threshold alert rules.
+    #[serde(default)]
+    pub alerts: Vec<AlertRule>,
+    /// Webhook delivery of quota events.
+    #[serde(default)]
+    pub webhook: WebhookConfig,
+    /// Usage spike detection for the 5-hour quota.
+    #[serde(default)]
+    pub spike: SpikeConfig,
+    /// Personal usage budgets below the plan limits.
+    #[serde(default)]
+    pub budget: BudgetConfig,
 }
 
 impl Default for Config {
@@ -127,6 +307,12 @@ impl Default for Config {
             api: ApiConfig::default(),
             cache: CacheConfig::default(),
             multiplier: MultiplierConfig::default(),
+            tracking: TrackingConfig::default(),
+            pricing: PricingConfig::default(),
+            alerts: Vec::new(),
+            webhook: WebhookConfig::default(),
+            spike: SpikeConfig::default(),
+            budget: BudgetConfig::default(),
         }
     }
 }
@@ -152,9 +338,30 @@ pub struct StyleConfig {
     /// Display mode (Auto, Emoji, or Ascii).
     #[serde(default)]
     pub mode: DisplayMode,
+    /// Name of the theme filling in icons, colors, separators, and formats.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theme: Option<String>,
     /// Separator string between segments.
     #[serde(default = "default_separator")]
     pub separator: String,
+    /// Separators of each status line row (rows without one use `separator`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub line_separators: Vec<String>,
+    /// Color depth (Auto, None, 16, 256, or Truecolor).
+    #[serde(default)]
+    pub color_depth: ColorDepth,
+    /// Draw segments on colored backgrounds joined by powerline arrows.
+    #[serde(default)]
+    pub powerline: bool,
+    /// Language of labels, timers, and CLI messages (Auto, En, or Zh).
+    #[serde(default)]
+    pub locale: Locale,
+    /// Maximum status line width in columns (unset detects it, 0 disables).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_width: Option<u16>,
+    /// Global colors (unset fields use the built-in palette).
+    #[serde(default, skip_serializing_if = "ColorConfig::is_empty")]
+    pub colors: ColorConfig,
 }
 
 impl StyleConfig {
@@ -164,6 +371,43 @@ impl StyleConfig {
             DisplayMode::Auto => *DETECTED_MODE.get_or_init(detect_display_mode),
             DisplayMode::Emoji => DisplayMode::Emoji,
             DisplayMode::Ascii => DisplayMode::Ascii,
+            DisplayMode::NerdFont => DisplayMode::NerdFont,
+        }
+    }
+
+    /// Resolve the color depth, detecting it from the environment if set to Auto.
+    ///
+    /// An explicit depth in the config takes precedence over `NO_COLOR`.
+    pub fn resolved_color_depth(&self) -> ColorDepth {
+        match self.color_depth {
+            ColorDepth::Auto => *DETECTED_COLOR_DEPTH.get_or_init(detect_color_depth),
+            depth => depth,
+        }
+    }
+
+    /// Resolve the locale, detecting it from the environment if set to Auto.
+    #[must_use]
+    pub fn resolved_locale(&self) -> Locale {
+        self.locale.resolve()
+    }
+
+    /// Returns the separator of status line row `line` (1-based).
+    #[must_use]
+    pub fn line_separator(&self, line: u8) -> &str {
+        usize::from(line)
+            .checked_sub(1)
+            .and_then(|i| self.line_separators.get(i))
+            .unwrap_or(&self.separator)
+    }
+
+    /// Resolve the maximum width, detecting the terminal width if unset.
+    ///
+    /// Returns `None` when the width is unlimited (`max_width = 0`) or unknown.
+    pub fn resolved_max_width(&self) -> Option<usize> {
+        match self.max_width {
+            Some(0) => None,
+            Some(width) => Some(usize::from(width)),
+            None => *DETECTED_WIDTH.get_or_init(detect_terminal_width),
         }
     }
 }
@@ -172,8 +416,110 @@ impl Default for StyleConfig {
     fn default() -> Self {
         Self {
             mode: DisplayMode::default(),
+            theme: None,
             separator: default_separator(),
+            line_separators: Vec::new(),
+            color_depth: ColorDepth::default(),
+            powerline: false,
+            locale: Locale::default(),
+            max_width: None,
+            colors: ColorConfig::default(),
+        }
+    }
+}
+
+/// Color of the primary text from a usage percentage upward.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct ColorThreshold {
+    /// Lowest usage percentage (0-100) that uses this color.
+    pub from: u8,
+    /// Color name, 256-color index, or `#rrggbb`.
+    pub color: String,
+}
+
+/// Color of the multiplier from a multiplier value upward.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct MultiplierColor {
+    /// Lowest multiplier value (e.g. `2.0`) that uses this color.
+    pub from: f64,
+    /// Color name, 256-color index, or `#rrggbb`.
+    pub color: String,
+}
+
+/// Status line colors.
+///
+/// Used globally as `[style.colors]` and per segment as
+/// `[segments.colors]`; empty fields fall back to the next level.
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+pub struct ColorConfig {
+    /// Primary text colors by usage percentage.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub thresholds: Vec<ColorThreshold>,
+    /// Multiplier colors by multiplier value.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub multiplier: Vec<MultiplierColor>,
+    /// Primary text color for segments without a usage percentage.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub neutral: Option<String>,
+    /// Secondary text color.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secondary: Option<String>,
+    /// Color of the separators between and within segments.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub separator: Option<String>,
+}
+
+impl ColorConfig {
+    /// Returns `true` if no color is set.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        *self == ColorConfig::default()
+    }
+
+    /// Sets every unset field to the value from `fallback`.
+    pub fn fill_from(&mut self, fallback: &ColorConfig) {
+        if self.thresholds.is_empty() {
+            self.thresholds.clone_from(&fallback.thresholds);
+        }
+        if self.multiplier.is_empty() {
+            self.multiplier.clone_from(&fallback.multiplier);
+        }
+        let singles = [
+            (&mut self.neutral, &fallback.neutral),
+            (&mut self.secondary, &fallback.secondary),
+            (&mut self.separator, &fallback.separator),
+        ];
+        for (field, value) in singles {
+            if field.is_none() {
+                field.clone_from(value);
+            }
+        }
+    }
+
+    /// Returns every configured color string with the field it belongs to.
+    #[must_use]
+    pub fn entries(&self) -> Vec<(&'static str, &str)> {
+        let mut entries: Vec<(&'static str, &str)> = self
+            .thresholds
+            .iter()
+            .map(|t| ("thresholds", t.color.as_str()))
+            .chain(
+                self.multiplier
+                    .iter()
+                    .map(|m| ("multiplier", m.color.as_str())),
+            )
+            .collect();
+        let singles = [
+            ("neutral", &self.neutral),
+            ("secondary", &self.secondary),
+            ("separator", &self.separator),
+        ];
+        for (field, color) in singles {
+            if let Some(color) = color {
+                entries.push((field, color.as_str()));
+            }
         }
+        entries
     }
 }
 
@@ -181,6 +527,62 @@ fn default_separator() -> String {
     DEFAULT_SEPARATOR.to_string()
 }
 
+/// How a quota segment shows its usage (the `display` segment option).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuotaDisplay {
+    /// Usage percentage, e.g. `24%`.
+    Percent,
+    /// Used amount and limit, e.g. `1.2M/5M`.
+    UsedLimit,
+    /// Amount left until the limit, e.g. `3.8M left`.
+    Remaining,
+    /// Percentage left until the limit, e.g. `76% left`.
+    RemainingPercent,
+}
+
+impl QuotaDisplay {
+    /// Reads the `display` option of a segment.
+    ///
+    /// Returns `None` if the option is unset, `Some(Err)` if it is invalid.
+    #[must_use]
+    pub fn from_options(
+        options: &HashMap<String, serde_json::Value>,
+    ) -> Option<Result<Self, serde_json::Error>> {
+        options
+            .get("display")
+            .map(|value| serde_json::from_value(value.clone()))
+    }
+}
+
+/// Conditions under which a segment is shown (`[segments.show_when]`).
+///
+/// Every condition that is set must hold; a segment without conditions is
+/// always shown.
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+pub struct ShowWhen {
+    /// Lowest usage percentage (0-100) at which the segment is shown.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_percentage: Option<u8>,
+    /// Show only during (`true`) or outside (`false`) the multiplier's peak hours.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub peak_hours: Option<bool>,
+    /// Show only with (`true`) or without (`false`) a premium model.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub premium_model: Option<bool>,
+    /// Show only while the consumption multiplier is above this value.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub multiplier_above: Option<f64>,
+}
+
+impl ShowWhen {
+    /// Returns `true` if no condition is set.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        *self == ShowWhen::default()
+    }
+}
+
 /// Configuration for a single status line segment.
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct SegmentConfig {
@@ -196,17 +598,60 @@ pub struct SegmentConfig {
     /// Segment-specific options.
     #[serde(default)]
     pub options: HashMap<String, serde_json::Value>,
+    /// Color overrides for this segment (unset fields use `[style.colors]`).
+    #[serde(default, skip_serializing_if = "ColorConfig::is_empty")]
+    pub colors: ColorConfig,
+    /// Layout template such as `"{icon} {percent}% {bar}"` (default layout when unset).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<SegmentFormat>,
+    /// Layout template used when the status line is too wide (icon and
+    /// primary text when unset).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compact_format: Option<SegmentFormat>,
+    /// Status line row (1-based) the segment is shown on.
+    #[serde(default = "default_line")]
+    pub line: u8,
+    /// Layout priority; when the status line is too wide, lower priorities
+    /// are compacted and then dropped first.
+    #[serde(default = "default_priority")]
+    pub priority: u8,
+    /// Conditions under which the segment is shown (always when empty).
+    #[serde(default, skip_serializing_if = "ShowWhen::is_empty")]
+    pub show_when: ShowWhen,
 }
 
 impl SegmentConfig {
+    /// Returns the default configuration of a built-in segment by ID.
+    #[must_use]
+    pub fn builtin(id: &str) -> Option<Self> {
+        let segment = match id {
+            "token_usage" => Self::token_usage(),
+            "weekly_usage" => Self::weekly_usage(),
+            "mcp_usage" => Self::mcp_usage(),
+            "session" => Self::session(),
+            "context" => Self::context(),
+            "cost" => Self::cost(),
+            "value" => Self::value(),
+            "budget" => Self::budget(),
+            _ => return None,
+        };
+        Some(segment)
+    }
+
     /// Create the default token usage segment configuration.
     #[must_use]
     pub fn token_usage() -> Self {
         Self {
             id: "token_usage".to_string(),
             enabled: true,
-            icon: IconConfig::new("🪙", "$"),
+            icon: IconConfig::new("🪙", "$").with_nerd_font("\u{f0d6}"),
             options: HashMap::new(),
+            colors: ColorConfig::default(),
+            format: None,
+            compact_format: None,
+            line: default_line(),
+            priority: default_priority(),
+            show_when: ShowWhen::default(),
         }
     }
 
@@ -216,8 +661,104 @@ impl SegmentConfig {
         Self {
             id: "mcp_usage".to_string(),
             enabled: true,
-            icon: IconConfig::new("🌐", "#"),
+            icon: IconConfig::new("🌐", "#").with_nerd_font("\u{f0ac}"),
+            options: HashMap::new(),
+            colors: ColorConfig::default(),
+            format: None,
+            compact_format: None,
+            line: default_line(),
+            priority: default_priority(),
+            show_when: ShowWhen::default(),
+        }
+    }
+
+    /// Create the default session consumption segment configuration.
+    #[must_use]
+    #[allow(dead_code, reason = "opt-in segment, not part of the defaults")]
+    pub fn session() -> Self {
+        Self {
+            id: "session".to_string(),
+            enabled: true,
+            icon: IconConfig::new("📈", "~").with_nerd_font("\u{f201}"),
+            options: HashMap::new(),
+            colors: ColorConfig::default(),
+            format: None,
+            compact_format: None,
+            line: default_line(),
+            priority: default_priority(),
+            show_when: ShowWhen::default(),
+        }
+    }
+
+    /// Create the default value segment configuration.
+    #[must_use]
+    #[allow(dead_code, reason = "opt-in segment, not part of the defaults")]
+    pub fn value() -> Self {
+        Self {
+            id: "value".to_string(),
+            enabled: true,
+            icon: IconConfig::new("💎", "v").with_nerd_font("\u{f219}"),
+            options: HashMap::new(),
+            colors: ColorConfig::default(),
+            format: None,
+            compact_format: None,
+            line: default_line(),
+            priority: default_priority(),
+            show_when: ShowWhen::default(),
+        }
+    }
+
+    /// Create the default budget segment configuration.
+    #[must_use]
+    #[allow(dead_code, reason = "opt-in segment, not part of the defaults")]
+    pub fn budget() -> Self {
+        Self {
+            id: "budget".to_string(),
+            enabled: true,
+            icon: IconConfig::new("🎯", "b").with_nerd_font("\u{f140}"),
+            options: HashMap::new(),
+            colors: ColorConfig::default(),
+            format: None,
+            compact_format: None,
+            line: default_line(),
+            priority: default_priority(),
+            show_when: ShowWhen::default(),
+        }
+    }
+
+    /// Create the default cost segment configuration.
+    #[must_use]
+    #[allow(dead_code, reason = "opt-in segment, not part of the defaults")]
+    pub fn cost() -> Self {
+        Self {
+            id: "cost".to_string(),
+            enabled: true,
+            icon: IconConfig::new("💰", "=").with_nerd_font("\u{f155}"),
+            options: HashMap::new(),
+            colors: ColorConfig::default(),
+            format: None,
+            compact_format: None,
+            line: default_line(),
+            priority: default_priority(),
+            show_when: ShowWhen::default(),
+        }
+    }
+
+    /// Create the default context segment configuration.
+    #[must_use]
+    #[allow(dead_code, reason = "opt-in segment, not part of the defaults")]
+    pub fn context() -> Self {
+        Self {
+            id: "context".to_string(),
+            enabled: true,
+            icon: IconConfig::new("🧠", "c").with_nerd_font("\u{f2db}"),
             options: HashMap::new(),
+            colors: ColorConfig::default(),
+            format: None,
+            compact_format: None,
+            line: default_line(),
+            priority: default_priority(),
+            show_when: ShowWhen::default(),
         }
     }
 
@@ -227,8 +768,14 @@ impl SegmentConfig {
         Self {
             id: "weekly_usage".to_string(),
             enabled: true,
-            icon: IconConfig::new("🗓️", "*"),
+            icon: IconConfig::new("🗓️", "*").with_nerd_font("\u{f073}"),
             options: HashMap::new(),
+            colors: ColorConfig::default(),
+            format: None,
+            compact_format: None,
+            line: default_line(),
+            priority: default_priority(),
+            show_when: ShowWhen::default(),
         }
     }
 }
@@ -237,8 +784,16 @@ fn default_enabled() -> bool {
     true
 }
 
-/// Icon configuration with both emoji and ASCII variants.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+fn default_line() -> u8 {
+    1
+}
+
+fn default_priority() -> u8 {
+    50
+}
+
+/// Icon configuration with emoji, ASCII, and Nerd Font variants.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct IconConfig {
     /// Emoji icon for modern terminals (e.g., "🪙").
     #[serde(default)]
@@ -246,6 +801,42 @@ pub struct IconConfig {
     /// ASCII icon for legacy terminals (e.g., "$").
     #[serde(default)]
     pub ascii: String,
+    /// Nerd Font glyph (e.g., "\u{f0d6}"); empty uses the built-in glyph.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub nerd_font: String,
+    /// Icons by usage percentage (`[[segments.icon.levels]]`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub levels: Vec<IconLevel>,
+    /// Icon while the usage shown is cached after a failed fetch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stale: Option<IconVariants>,
+    /// Icon while the usage cannot be fetched (the segment is hidden when unset).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<IconVariants>,
+}
+
+/// An icon for each display mode; empty variants use the segment's icon.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IconVariants {
+    /// Emoji icon.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub emoji: String,
+    /// ASCII icon.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub ascii: String,
+    /// Nerd Font glyph.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub nerd_font: String,
+}
+
+/// Icon of a segment from a usage percentage upward.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IconLevel {
+    /// Lowest usage percentage (0-100) that uses this icon.
+    pub from: u8,
+    /// Icons of this level.
+    #[serde(flatten)]
+    pub icon: IconVariants,
 }
 
 impl IconConfig {
@@ -255,8 +846,29 @@ impl IconConfig {
         Self {
             emoji: emoji.to_string(),
             ascii: ascii.to_string(),
+            nerd_font: String::new(),
+            levels: Vec::new(),
+            stale: None,
+            error: None,
         }
     }
+
+    /// Returns the icons of the highest level at or below `percentage`.
+    #[must_use]
+    pub fn level(&self, percentage: u8) -> Option<&IconVariants> {
+        self.levels
+            .iter()
+            .filter(|level| level.from <= percentage)
+            .max_by_key(|level| level.from)
+            .map(|level| &level.icon)
+    }
+
+    /// Sets the Nerd Font glyph.
+    #[must_use]
+    pub fn with_nerd_font(mut self, glyph: &str) -> Self {
+        self.nerd_font = glyph.to_string();
+        self
+    }
 }
 
 /// API connection settings.
@@ -404,6 +1016,453 @@ fn default_promo_expires() -> String {
     "2026-09-30".to_string()
 }
 
+/// Local usage tracking settings.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct TrackingConfig {
+    /// Attribute quota consumption to the active workspace and model.
+    #[serde(default = "default_attribution")]
+    pub attribution: bool,
+    /// Hours of inactivity after which per-session quota state is discarded.
+    ///
+    /// Transcript accounting is kept for 35 days regardless, so resuming an
+    /// old session does not count its tokens again.
+    #[serde(default = "default_session_ttl_hours")]
+    pub session_ttl_hours: u64,
+    /// Count tokens from Claude Code transcripts on every render; when off,
+    /// segments use the counts last recorded.
+    #[serde(default = "default_transcripts")]
+    pub transcripts: bool,
+}
+
+impl TrackingConfig {
+    /// Returns the session inactivity timeout in seconds.
+    #[must_use]
+    pub fn session_ttl_secs(&self) -> i64 {
+        i64::try_from(self.session_ttl_hours.saturating_mul(3600)).unwrap_or(i64::MAX)
+    }
+}
+
+impl Default for TrackingConfig {
+    fn default() -> Self {
+        Self {
+            attribution: default_attribution(),
+            session_ttl_hours: default_session_ttl_hours(),
+            transcripts: default_transcripts(),
+        }
+    }
+}
+
+fn default_attribution() -> bool {
+    true
+}
+
+fn default_session_ttl_hours() -> u64 {
+    24
+}
+
+fn default_transcripts() -> bool {
+    true
+}
+
+/// Metered price of a model, per million tokens.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+pub struct ModelPrice {
+    /// Price of uncached input tokens.
+    #[serde(default)]
+    pub input: f64,
+    /// Price of output tokens.
+    #[serde(default)]
+    pub output: f64,
+    /// Price of cache reads; defaults to the input price.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_read: Option<f64>,
+    /// Price of cache writes; defaults to the input price.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_creation: Option<f64>,
+}
+
+impl ModelPrice {
+    const fn new(input: f64, output: f64, cache_read: f64) -> Self {
+        Self {
+            input,
+            output,
+            cache_read: Some(cache_read),
+            cache_creation: None,
+        }
+    }
+}
+
+/// Built-in metered prices (USD per million tokens, Z.AI list prices).
+///
+/// Keys are matched as case-insensitive substrings of the model ID.
+const BUILTIN_MODEL_PRICES: &[(&str, ModelPrice)] = &[
+    ("glm-5", ModelPrice::new(1.0, 3.2, 0.2)),
+    ("glm-4.7", ModelPrice::new(0.6, 2.2, 0.11)),
+    ("glm-4.6", ModelPrice::new(0.6, 2.2, 0.11)),
+    ("glm-4.5", ModelPrice::new(0.6, 2.2, 0.11)),
+    ("glm-4.5-air", ModelPrice::new(0.2, 1.1, 0.03)),
+];
+
+/// Metered pricing and plan price settings.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct PricingConfig {
+    /// Price paid for the coding plan per period (0 = not configured).
+    #[serde(default)]
+    pub plan_price: f64,
+    /// Number of days covered by `plan_price`.
+    #[serde(default = "default_plan_period_days")]
+    pub plan_period_days: u32,
+    /// Currency symbol used for prices.
+    #[serde(default = "default_currency_symbol")]
+    pub currency_symbol: String,
+    /// Per-model prices, added to (and overriding) the built-in table.
+    #[serde(default)]
+    pub models: BTreeMap<String, ModelPrice>,
+}
+
+impl PricingConfig {
+    /// Returns the price for a model ID.
+    ///
+    /// Configured and built-in keys are matched as case-insensitive
+    /// substrings; the longest matching key wins, and configured prices
+    /// take precedence over built-in ones for the same key.
+    #[must_use]
+    pub fn price_for(&self, model: &str) -> Option<ModelPrice> {
+        let model = model.to_lowercase();
+        let configured = self.models.iter().map(|(k, p)| (k.to_lowercase(), *p, 1));
+        let builtin = BUILTIN_MODEL_PRICES
+            .iter()
+            .map(|(k, p)| ((*k).to_string(), *p, 0));
+        configured
+            .chain(builtin)
+            .filter(|(key, _, _)| !key.is_empty() && model.contains(key.as_str()))
+            .max_by_key(|(key, _, priority)| (key.len(), *priority))
+            .map(|(_, price, _)| price)
+    }
+
+    /// Returns the plan price prorated over `days`, if a plan price is set.
+    #[must_use]
+    pub fn prorated_plan_price(&self, days: u32) -> Option<f64> {
+        (self.plan_price > 0.0 && self.plan_period_days > 0)
+            .then(|| self.plan_price * f64::from(days) / f64::from(self.plan_period_days))
+    }
+}
+
+impl Default for PricingConfig {
+    fn default() -> Self {
+        Self {
+            plan_price: 0.0,
+            plan_period_days: default_plan_period_days(),
+            currency_symbol: default_currency_symbol(),
+            models: BTreeMap::new(),
+        }
+    }
+}
+
+fn default_plan_period_days() -> u32 {
+    30
+}
+
+fn default_currency_symbol() -> String {
+    "$".to_string()
+}
+
+/// Quota watched by an alert rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[allow(
+    clippy::enum_variant_names,
+    reason = "variants mirror the usage segment IDs"
+)]
+pub enum AlertQuota {
+    /// The 5-hour token quota.
+    TokenUsage,
+    /// The weekly token quota.
+    WeeklyUsage,
+    /// The MCP call quota.
+    McpUsage,
+}
+
+impl AlertQuota {
+    /// Returns the configuration name of the quota.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AlertQuota::TokenUsage => "token_usage",
+            AlertQuota::WeeklyUsage => "weekly_usage",
+            AlertQuota::McpUsage => "mcp_usage",
+        }
+    }
+
+    /// Returns a human-readable name of the quota.
+    #[must_use]
+    pub fn label(self) -> &'static str {
+        match self {
+            AlertQuota::TokenUsage => "5-hour token quota",
+            AlertQuota::WeeklyUsage => "weekly token quota",
+            AlertQuota::McpUsage => "MCP quota",
+        }
+    }
+
+    /// All quotas, in display order.
+    pub const ALL: [AlertQuota; 3] = [
+        AlertQuota::TokenUsage,
+        AlertQuota::WeeklyUsage,
+        AlertQuota::McpUsage,
+    ];
+}
+
+/// Direction in which an alert threshold is crossed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertDirection {
+    /// Fire when usage rises to or above the threshold.
+    #[default]
+    Above,
+    /// Fire when usage falls to or below the threshold.
+    Below,
+}
+
+/// A threshold alert rule (`[[alerts]]`).
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct AlertRule {
+    /// Quota to watch.
+    pub quota: AlertQuota,
+    /// Usage percentage that triggers the alert.
+    pub threshold: u8,
+    /// Whether the alert fires above or below the threshold.
+    #[serde(default)]
+    pub direction: AlertDirection,
+    /// Shell command run when the alert fires.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    /// Send a desktop notification when the alert fires.
+    #[serde(default = "default_alert_notify")]
+    pub notify: bool,
+}
+
+impl AlertRule {
+    /// Returns a stable key identifying this rule in persisted state.
+    #[must_use]
+    pub fn key(&self) -> String {
+        let direction = match self.direction {
+            AlertDirection::Above => "above",
+            AlertDirection::Below => "below",
+        };
+        format!("{}:{direction}:{}", self.quota.as_str(), self.threshold)
+    }
+}
+
+fn default_alert_notify() -> bool {
+    true
+}
+
+/// Payload format of webhook messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookFormat {
+    /// Generic JSON with all event fields.
+    #[default]
+    Json,
+    /// Feishu/Lark custom bot text message.
+    Feishu,
+    /// Dingtalk custom robot text message.
+    Dingtalk,
+    /// Slack incoming webhook message.
+    Slack,
+}
+
+/// Quota event kinds delivered to the webhook.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEventKind {
+    /// An `[[alerts]]` threshold was crossed.
+    Threshold,
+    /// A quota window was reset.
+    Reset,
+    /// The monitor API rejected the auth token.
+    AuthFailure,
+    /// 5-hour quota consumption spiked above its baseline.
+    Spike,
+}
+
+/// Webhook delivery settings.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct WebhookConfig {
+    /// Webhook URL (unset = disabled).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Payload format.
+    #[serde(default)]
+    pub format: WebhookFormat,
+    /// Event kinds to deliver.
+    #[serde(default = "default_webhook_events")]
+    pub events: Vec<WebhookEventKind>,
+    /// Time budget in milliseconds for delivering events on each run.
+    #[serde(default = "default_webhook_timeout")]
+    pub timeout_ms: u64,
+    /// Seconds to wait before retrying after a failed delivery.
+    #[serde(default = "default_webhook_retry_interval")]
+    pub retry_interval_secs: u64,
+    /// Maximum number of undelivered events kept in the outbox.
+    #[serde(default = "default_webhook_max_outbox")]
+    pub max_outbox: usize,
+}
+
+impl WebhookConfig {
+    /// Returns the webhook URL if delivery is enabled.
+    #[must_use]
+    pub fn enabled_url(&self) -> Option<&str> {
+        self.url.as_deref().filter(|url| !url.is_empty())
+    }
+
+    /// Returns `true` if events of `kind` should be delivered.
+    #[must_use]
+    pub fn wants(&self, kind: WebhookEventKind) -> bool {
+        self.enabled_url().is_some() && self.events.contains(&kind)
+    }
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            url: None,
+            format: WebhookFormat::default(),
+            events: default_webhook_events(),
+            timeout_ms: default_webhook_timeout(),
+            retry_interval_secs: default_webhook_retry_interval(),
+            max_outbox: default_webhook_max_outbox(),
+        }
+    }
+}
+
+fn default_webhook_events() -> Vec<WebhookEventKind> {
+    vec![
+        WebhookEventKind::Threshold,
+        WebhookEventKind::Reset,
+        WebhookEventKind::AuthFailure,
+        WebhookEventKind::Spike,
+    ]
+}
+
+fn default_webhook_timeout() -> u64 {
+    1000
+}
+
+fn default_webhook_retry_interval() -> u64 {
+    60
+}
+
+fn default_webhook_max_outbox() -> usize {
+    100
+}
+
+/// Usage spike detection settings.
+///
+/// A spike is flagged when the 5-hour quota is consumed at least
+/// `min_rate` percentage points per minute over the last `window_minutes`,
+/// and at least `factor` times faster than the rolling baseline.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct SpikeConfig {
+    /// Enable spike detection.
+    #[serde(default = "default_spike_enabled")]
+    pub enabled: bool,
+    /// Minutes of recent usage compared against the baseline.
+    #[serde(default = "default_spike_window_minutes")]
+    pub window_minutes: u32,
+    /// Minutes of history forming the rolling baseline.
+    #[serde(default = "default_spike_baseline_minutes")]
+    pub baseline_minutes: u32,
+    /// How many times faster than the baseline counts as a spike.
+    #[serde(default = "default_spike_factor")]
+    pub factor: f64,
+    /// Minimum consumption rate (percentage points per minute) for a spike.
+    #[serde(default = "default_spike_min_rate")]
+    pub min_rate: f64,
+    /// Shell command to run when a spike starts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    /// Send a desktop notification when a spike starts.
+    #[serde(default = "default_spike_notify")]
+    pub notify: bool,
+}
+
+impl SpikeConfig {
+    /// Returns the recent usage window in seconds.
+    #[must_use]
+    pub fn window_secs(&self) -> i64 {
+        i64::from(self.window_minutes) * 60
+    }
+
+    /// Returns the baseline history length in seconds.
+    #[must_use]
+    pub fn baseline_secs(&self) -> i64 {
+        i64::from(self.baseline_minutes) * 60
+    }
+}
+
+impl Default for SpikeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_spike_enabled(),
+            window_minutes: default_spike_window_minutes(),
+            baseline_minutes: default_spike_baseline_minutes(),
+            factor: default_spike_factor(),
+            min_rate: default_spike_min_rate(),
+            command: None,
+            notify: default_spike_notify(),
+        }
+    }
+}
+
+fn default_spike_enabled() -> bool {
+    true
+}
+
+fn default_spike_window_minutes() -> u32 {
+    10
+}
+
+fn default_spike_baseline_minutes() -> u32 {
+    60
+}
+
+fn default_spike_factor() -> f64 {
+    3.0
+}
+
+fn default_spike_min_rate() -> f64 {
+    2.0
+}
+
+fn default_spike_notify() -> bool {
+    true
+}
+
+/// Personal usage budgets, as percentages of the plan quotas.
+///
+/// Unset windows have no budget and use the plan limits.
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct BudgetConfig {
+    /// Share of the weekly quota that may be used per local calendar day.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub daily: Option<u8>,
+    /// Share of the 5-hour quota that may be used per window.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub five_hour: Option<u8>,
+    /// Share of the weekly quota that may be used per window.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weekly: Option<u8>,
+}
+
+impl BudgetConfig {
+    /// Returns `true` if any window has a budget.
+    #[must_use]
+    pub fn is_set(&self) -> bool {
+        self.daily.is_some() || self.five_hour.is_some() || self.weekly.is_some()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -423,6 +1482,39 @@ mod tests {
         assert_eq!(default_promo_expires(), "2026-09-30");
     }
 
+    #[test]
+    fn test_price_for_longest_match() {
+        let pricing = PricingConfig::default();
+        let air = pricing.price_for("GLM-4.5-Air").unwrap();
+        assert!((air.input - 0.2).abs() < f64::EPSILON);
+        let glm5 = pricing.price_for("glm-5.1").unwrap();
+        assert!((glm5.output - 3.2).abs() < f64::EPSILON);
+        assert!(pricing.price_for("claude-sonnet").is_none());
+    }
+
+    #[test]
+    fn test_price_for_configured_overrides_builtin() {
+        let mut pricing = PricingConfig::default();
+        pricing
+            .models
+            .insert("glm-5".to_string(), ModelPrice::new(2.0, 8.0, 0.5));
+        pricing
+            .models
+            .insert("my-model".to_string(), ModelPrice::new(1.0, 1.0, 1.0));
+        let glm5 = pricing.price_for("glm-5.1").unwrap();
+        assert!((glm5.input - 2.0).abs() < f64::EPSILON);
+        assert!(pricing.price_for("my-model-v2").is_some());
+    }
+
+    #[test]
+    fn test_prorated_plan_price() {
+        let mut pricing = PricingConfig::default();
+        assert!(pricing.prorated_plan_price(7).is_none());
+        pricing.plan_price = 30.0;
+        let weekly = pricing.prorated_plan_price(7).unwrap();
+        assert!((weekly - 7.0).abs() < 1e-9);
+    }
+
     #[test]
     fn test_merge_segments_empty() {
         let config = Config {
@@ -488,7 +1580,14 @@ mod tests {
     fn test_resolved_mode_emoji() {
         let style = StyleConfig {
             mode: DisplayMode::Emoji,
+            theme: None,
             separator: DEFAULT_SEPARATOR.to_string(),
+            line_separators: Vec::new(),
+            color_depth: ColorDepth::default(),
+            powerline: false,
+            locale: Locale::default(),
+            max_width: None,
+            colors: ColorConfig::default(),
         };
         assert_eq!(style.resolved_mode(), DisplayMode::Emoji);
     }
@@ -497,7 +1596,14 @@ mod tests {
     fn test_resolved_mode_ascii() {
         let style = StyleConfig {
             mode: DisplayMode::Ascii,
+            theme: None,
             separator: DEFAULT_SEPARATOR.to_string(),
+            line_separators: Vec::new(),
+            color_depth: ColorDepth::default(),
+            powerline: false,
+            locale: Locale::default(),
+            max_width: None,
+            colors: ColorConfig::default(),
         };
         assert_eq!(style.resolved_mode(), DisplayMode::Ascii);
     }
@@ -506,13 +1612,117 @@ mod tests {
     fn test_resolved_mode_auto_never_auto() {
         let style = StyleConfig {
             mode: DisplayMode::Auto,
+            theme: None,
             separator: DEFAULT_SEPARATOR.to_string(),
+            line_separators: Vec::new(),
+            color_depth: ColorDepth::default(),
+            powerline: false,
+            locale: Locale::default(),
+            max_width: None,
+            colors: ColorConfig::default(),
         };
         let resolved = style.resolved_mode();
         assert_ne!(resolved, DisplayMode::Auto);
         assert!(resolved == DisplayMode::Emoji || resolved == DisplayMode::Ascii);
     }
 
+    #[test]
+    fn test_resolved_color_depth_explicit() {
+        let style = StyleConfig {
+            color_depth: ColorDepth::Ansi16,
+            ..StyleConfig::default()
+        };
+        assert_eq!(style.resolved_color_depth(), ColorDepth::Ansi16);
+        let auto = StyleConfig::default().resolved_color_depth();
+        assert_ne!(auto, ColorDepth::Auto);
+    }
+
+    #[test]
+    fn test_color_depth_from_env() {
+        let s = Some;
+        assert_eq!(
+            color_depth_from_env(s("1"), s("truecolor"), s("xterm-256color")),
+            ColorDepth::None
+        );
+        assert_eq!(
+            color_depth_from_env(s(""), s("truecolor"), None),
+            ColorDepth::Truecolor
+        );
+        assert_eq!(
+            color_depth_from_env(None, None, s("dumb")),
+            ColorDepth::None
+        );
+        assert_eq!(
+            color_depth_from_env(None, None, s("linux")),
+            ColorDepth::Ansi16
+        );
+        assert_eq!(
+            color_depth_from_env(None, None, s("xterm-256color")),
+            ColorDepth::Ansi256
+        );
+        assert_eq!(color_depth_from_env(None, None, None), ColorDepth::Ansi256);
+    }
+
+    #[test]
+    fn test_columns_from_env() {
+        assert_eq!(columns_from_env(Some("120")), Some(120));
+        assert_eq!(columns_from_env(Some(" 80\n")), Some(80));
+        assert_eq!(columns_from_env(Some("0")), None);
+        assert_eq!(columns_from_env(Some("wide")), None);
+        assert_eq!(columns_from_env(None), None);
+    }
+
+    #[test]
+    fn test_locale_from_env() {
+        assert_eq!(locale_from_env(None, None, Some("zh_CN.UTF-8")), Locale::Zh);
+        assert_eq!(locale_from_env(None, None, Some("en_US.UTF-8")), Locale::En);
+        assert_eq!(
+            locale_from_env(Some("C"), None, Some("zh_CN.UTF-8")),
+            Locale::En
+        );
+        assert_eq!(
+            locale_from_env(Some(""), Some("zh_TW"), Some("C")),
+            Locale::Zh
+        );
+        assert_eq!(locale_from_env(None, None, None), Locale::En);
+    }
+
+    #[test]
+    fn test_locale_pick() {
+        assert_eq!(Locale::Zh.pick("week", "周"), "周");
+        assert_eq!(Locale::En.pick("week", "周"), "week");
+    }
+
+    #[test]
+    fn test_line_separator() {
+        let style = StyleConfig {
+            line_separators: vec![" · ".to_string()],
+            ..StyleConfig::default()
+        };
+        assert_eq!(style.line_separator(1), " · ");
+        assert_eq!(style.line_separator(2), DEFAULT_SEPARATOR);
+        assert_eq!(style.line_separator(0), DEFAULT_SEPARATOR);
+    }
+
+    #[test]
+    fn test_resolved_max_width() {
+        let mut style = StyleConfig {
+            max_width: Some(60),
+            ..StyleConfig::default()
+        };
+        assert_eq!(style.resolved_max_width(), Some(60));
+        style.max_width = Some(0);
+        assert_eq!(style.resolved_max_width(), None);
+    }
+
+    #[test]
+    fn test_color_depth_deserialize() {
+        let style: StyleConfig = toml::from_str("color_depth = \"16\"").unwrap();
+        assert_eq!(style.color_depth, ColorDepth::Ansi16);
+        let style: StyleConfig = toml::from_str("color_depth = \"truecolor\"").unwrap();
+        assert_eq!(style.color_depth, ColorDepth::Truecolor);
+    }
+
     #[test]
     fn test_merge_default_segments_fills_all() {
         let config = Config::default();
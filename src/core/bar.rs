@@ -0,0 +1,69 @@
+//! Progress bar rendering for usage percentages.
+//!
+//! Emoji mode draws a framed bar with eighth-block partial cells, e.g.
+//! `▕███▌░░░▏` at 50% over 7 cells; ASCII mode draws `[####----]`.
+
+use crate::config::DisplayMode;
+
+/// Default number of cells when a segment sets no `bar_width`.
+pub const DEFAULT_BAR_WIDTH: u8 = 10;
+
+/// Largest accepted `bar_width`.
+pub const MAX_BAR_WIDTH: u8 = 50;
+
+/// Partial blocks from one to seven eighths of a cell.
+const PARTIAL_BLOCKS: [&str; 7] = ["▏", "▎", "▍", "▌", "▋", "▊", "▉"];
+
+/// Renders a bar `width` cells wide filled to `percentage`.
+#[must_use]
+pub fn render(percentage: u8, width: u8, mode: DisplayMode) -> String {
+    let width = width.clamp(1, MAX_BAR_WIDTH);
+    let percentage = u16::from(percentage.min(100));
+    match mode {
+        DisplayMode::Ascii => {
+            let filled = usize::from((percentage * u16::from(width) + 50) / 100);
+            let empty = usize::from(width) - filled;
+            format!("[{}{}]", "#".repeat(filled), "-".repeat(empty))
+        }
+        DisplayMode::Emoji | DisplayMode::NerdFont | DisplayMode::Auto => {
+            let eighths = (percentage * u16::from(width) * 8 + 50) / 100;
+            let full = usize::from(eighths / 8);
+            let partial = usize::from(eighths % 8);
+            let mut bar = String::from("▕");
+            bar.push_str(&"█".repeat(full));
+            if partial > 0 {
+                bar.push_str(PARTIAL_BLOCKS[partial - 1]);
+            }
+            let empty = usize::from(width) - full - usize::from(partial > 0);
+            bar.push_str(&"░".repeat(empty));
+            bar.push('▏');
+            bar
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_emoji_partial_blocks() {
+        assert_eq!(render(50, 7, DisplayMode::Emoji), "▕███▌░░░▏");
+        assert_eq!(render(0, 4, DisplayMode::Emoji), "▕░░░░▏");
+        assert_eq!(render(100, 4, DisplayMode::Emoji), "▕████▏");
+        assert_eq!(render(3, 10, DisplayMode::Emoji), "▕▎░░░░░░░░░▏");
+    }
+
+    #[test]
+    fn test_render_ascii() {
+        assert_eq!(render(50, 8, DisplayMode::Ascii), "[####----]");
+        assert_eq!(render(85, 10, DisplayMode::Ascii), "[#########-]");
+        assert_eq!(render(100, 3, DisplayMode::Ascii), "[###]");
+    }
+
+    #[test]
+    fn test_render_clamps_width() {
+        assert_eq!(render(100, 0, DisplayMode::Ascii), "[#]");
+        assert_eq!(render(0, 200, DisplayMode::Ascii).len(), 52);
+    }
+}
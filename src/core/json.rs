@@ -0,0 +1,107 @@
+//! JSON output of the status line data (`--format json`).
+//!
+//! Lists every rendered segment's [`SegmentData`] with its ID and row, plus
+//! the underlying [`UsageStats`], so other tools can read the values
+//! without parsing ANSI escapes.
+
+use crate::api::UsageStats;
+use crate::config::SegmentConfig;
+use crate::core::segments::SegmentData;
+use serde::Serialize;
+
+/// A segment in the JSON output.
+#[derive(Debug, Serialize)]
+struct JsonSegment<'a> {
+    id: &'a str,
+    line: u8,
+    #[serde(flatten)]
+    data: &'a SegmentData,
+}
+
+/// The JSON output document.
+#[derive(Debug, Serialize)]
+struct JsonOutput<'a> {
+    segments: Vec<JsonSegment<'a>>,
+    /// `null` when no usage statistics were fetched or cached.
+    usage: Option<&'a UsageStats>,
+}
+
+/// Serializes the segments and usage statistics as a single JSON line.
+#[must_use]
+pub fn generate_json(
+    segments: &[(SegmentConfig, SegmentData)],
+    usage: Option<&UsageStats>,
+) -> String {
+    let output = JsonOutput {
+        segments: segments
+            .iter()
+            .map(|(config, data)| JsonSegment {
+                id: &config.id,
+                line: config.line,
+                data,
+            })
+            .collect(),
+        usage,
+    };
+    serde_json::to_string(&output).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::QuotaUsage;
+    use serde_json::{json, Value};
+
+    #[test]
+    fn test_generate_json() {
+        let segments = [(
+            SegmentConfig::token_usage(),
+            SegmentData::new("85%")
+                .with_secondary("@ 14:30")
+                .with_multiplier("3x")
+                .with_metadata("percentage", 85),
+        )];
+        let usage = UsageStats {
+            token_usage: Some(QuotaUsage {
+                used: 850,
+                limit: 1000,
+                percentage: 85,
+                time_window: "5h".to_string(),
+                reset_at: Some(1_700_000_000),
+            }),
+            weekly_usage: None,
+            mcp_usage: None,
+        };
+        let output: Value = serde_json::from_str(&generate_json(&segments, Some(&usage))).unwrap();
+        assert_eq!(
+            output,
+            json!({
+                "segments": [{
+                    "id": "token_usage",
+                    "line": 1,
+                    "primary": "85%",
+                    "secondary": "@ 14:30",
+                    "multiplier": "3x",
+                    "metadata": { "percentage": "85" }
+                }],
+                "usage": {
+                    "token_usage": {
+                        "used": 850,
+                        "limit": 1000,
+                        "percentage": 85,
+                        "time_window": "5h",
+                        "reset_at": 1_700_000_000
+                    },
+                    "weekly_usage": null,
+                    "mcp_usage": null
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_generate_json_without_usage() {
+        let output: Value = serde_json::from_str(&generate_json(&[], None)).unwrap();
+        assert_eq!(output, json!({ "segments": [], "usage": null }));
+    }
+}
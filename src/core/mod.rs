@@ -3,10 +3,27 @@
 //! This module provides the core functionality for generating status line output,
 //! including segment implementations and the status line generator.
 
+pub mod bar;
+mod json;
+mod prompt;
 mod segments;
 mod statusline;
+mod visibility;
+mod widget;
+mod width;
 
 #[doc(inline)]
-pub use segments::{McpUsageSegment, Segment, SegmentData, TokenUsageSegment, WeeklyUsageSegment};
+pub use json::generate_json;
+#[doc(inline)]
+pub use prompt::prompt_escape;
+#[doc(inline)]
+pub use segments::{
+    BudgetSegment, ContextSegment, CostSegment, McpUsageSegment, Segment, SegmentData,
+    SessionSegment, TokenUsageSegment, ValueSegment, WeeklyUsageSegment,
+};
 #[doc(inline)]
 pub use statusline::StatusLineGenerator;
+#[doc(inline)]
+pub use visibility::should_show;
+#[doc(inline)]
+pub use widget::{generate_i3bar, generate_waybar, I3BAR_HEADER};
@@ -0,0 +1,144 @@
+//! Status line output for tmux and shell prompts (`prompt` command).
+//!
+//! The ANSI status line is converted to each target's syntax: tmux gets
+//! `#[fg=…]` style codes, zsh and bash get the escapes wrapped in `%{ %}`
+//! and `\[ \]` so the prompt width is measured correctly. Characters the
+//! target would interpret (`#` in tmux, `%` in zsh, `\`, `$` and `` ` `` in
+//! bash) are escaped.
+
+use crate::cli::PromptTarget;
+
+/// Converts an ANSI status line to `target` syntax.
+#[must_use]
+pub fn prompt_escape(ansi: &str, target: PromptTarget) -> String {
+    let mut output = String::with_capacity(ansi.len());
+    let mut rest = ansi;
+    while let Some(start) = rest.find('\x1b') {
+        push_text(&mut output, &rest[..start], target);
+        let sequence = &rest[start..];
+        let end = sequence.find('m').map_or(sequence.len(), |i| i + 1);
+        push_escape(&mut output, &sequence[..end], target);
+        rest = &sequence[end..];
+    }
+    push_text(&mut output, rest, target);
+    output
+}
+
+/// Appends plain text, escaping characters special to `target`.
+///
+/// Bash decodes prompt escapes and then, with the default `promptvars`
+/// option, expands the result like a double-quoted string, so `\`, `$` and
+/// `` ` `` are escaped for both passes.
+fn push_text(output: &mut String, text: &str, target: PromptTarget) {
+    for c in text.chars() {
+        match (target, c) {
+            (PromptTarget::Tmux, '#') => output.push_str("##"),
+            (PromptTarget::Zsh, '%') => output.push_str("%%"),
+            (PromptTarget::Bash, '\\') => output.push_str("\\\\\\\\"),
+            (PromptTarget::Bash, '$' | '`') => {
+                output.push_str("\\\\");
+                output.push(c);
+            }
+            _ => output.push(c),
+        }
+    }
+}
+
+/// Appends an ANSI SGR sequence such as `\x1b[38;5;196m` in `target` syntax.
+fn push_escape(output: &mut String, sequence: &str, target: PromptTarget) {
+    match target {
+        PromptTarget::Tmux => {
+            let params = sequence
+                .strip_prefix("\x1b[")
+                .and_then(|s| s.strip_suffix('m'))
+                .unwrap_or_default();
+            if let Some(style) = tmux_style(params) {
+                output.push_str("#[");
+                output.push_str(&style);
+                output.push(']');
+            }
+        }
+        PromptTarget::Zsh => {
+            output.push_str("%{");
+            output.push_str(sequence);
+            output.push_str("%}");
+        }
+        PromptTarget::Bash => {
+            output.push_str("\\[");
+            output.push_str(sequence);
+            output.push_str("\\]");
+        }
+        PromptTarget::Plain => {}
+    }
+}
+
+/// Translates SGR parameters (e.g. `38;5;196`) to a tmux style.
+fn tmux_style(params: &str) -> Option<String> {
+    let codes: Vec<u8> = params
+        .split(';')
+        .map(|p| {
+            if p.is_empty() {
+                Some(0)
+            } else {
+                p.parse().ok()
+            }
+        })
+        .collect::<Option<_>>()?;
+    let style = match codes.as_slice() {
+        [0] => "default".to_string(),
+        [code @ 30..=37] => format!("fg=colour{}", code - 30),
+        [code @ 90..=97] => format!("fg=colour{}", code - 90 + 8),
+        [code @ 40..=47] => format!("bg=colour{}", code - 40),
+        [code @ 100..=107] => format!("bg=colour{}", code - 100 + 8),
+        [38, 5, index] => format!("fg=colour{index}"),
+        [48, 5, index] => format!("bg=colour{index}"),
+        [38, 2, r, g, b] => format!("fg=#{r:02x}{g:02x}{b:02x}"),
+        [48, 2, r, g, b] => format!("bg=#{r:02x}{g:02x}{b:02x}"),
+        _ => return None,
+    };
+    Some(style)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LINE: &str = "\x1b[38;5;46m# 30%\x1b[0m\x1b[37m | \x1b[0m\x1b[38;2;255;136;0m$1\x1b[0m";
+
+    #[test]
+    fn test_tmux_styles() {
+        assert_eq!(
+            prompt_escape(LINE, PromptTarget::Tmux),
+            "#[fg=colour46]## 30%#[default]#[fg=colour7] | #[default]#[fg=#ff8800]$1#[default]"
+        );
+        assert_eq!(
+            prompt_escape("\x1b[101m\x1b[30m x \x1b[0m", PromptTarget::Tmux),
+            "#[bg=colour9]#[fg=colour0] x #[default]"
+        );
+    }
+
+    #[test]
+    fn test_zsh_wraps_escapes() {
+        assert_eq!(
+            prompt_escape("\x1b[31m50%\x1b[0m", PromptTarget::Zsh),
+            "%{\x1b[31m%}50%%%{\x1b[0m%}"
+        );
+    }
+
+    #[test]
+    fn test_bash_wraps_escapes() {
+        assert_eq!(
+            prompt_escape("\x1b[31ma\\b\x1b[0m", PromptTarget::Bash),
+            "\\[\x1b[31m\\]a\\\\\\\\b\\[\x1b[0m\\]"
+        );
+        assert_eq!(
+            prompt_escape("$1.20 `id`", PromptTarget::Bash),
+            "\\\\$1.20 \\\\`id\\\\`"
+        );
+    }
+
+    #[test]
+    fn test_plain_strips_escapes() {
+        assert_eq!(prompt_escape(LINE, PromptTarget::Plain), "# 30% | $1");
+    }
+}
@@ -0,0 +1,152 @@
+//! Budget segment for displaying the remaining personal budget.
+//!
+//! This segment compares usage with the `[budget]` limits and shows the
+//! window closest to its budget, or by how much a budget was exceeded.
+
+use super::{Segment, SegmentData};
+use crate::api::SharedCache;
+use crate::config::{Config, InputData, Locale};
+
+/// Usage of a single budgeted window, in percentage points of its quota.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct WindowBudget {
+    /// Short window label shown as secondary text.
+    label: &'static str,
+    /// Points used in the window.
+    used: u8,
+    /// Points the budget allows.
+    budget: u8,
+}
+
+impl WindowBudget {
+    /// Returns how much of the budget is used, in percent (capped at 100).
+    fn budget_percentage(self) -> u8 {
+        let pct = u16::from(self.used) * 100 / u16::from(self.budget.max(1));
+        u8::try_from(pct.min(100)).unwrap_or(100)
+    }
+
+    /// Returns `true` if usage went past the budget.
+    fn exceeded(self) -> bool {
+        self.used > self.budget
+    }
+}
+
+/// Returns the window closest to (or furthest past) its budget.
+fn tightest(windows: &[WindowBudget]) -> Option<WindowBudget> {
+    windows
+        .iter()
+        .copied()
+        .max_by_key(|w| (u16::from(w.used) * 100) / u16::from(w.budget.max(1)))
+}
+
+/// Formats the remaining budget of a window as primary display text.
+fn format_remaining(window: WindowBudget, locale: Locale) -> String {
+    match (window.exceeded(), locale.resolve()) {
+        (true, Locale::Zh) => format!("超出 {}%", window.used - window.budget),
+        (true, _) => format!("+{}% over", window.used - window.budget),
+        (false, Locale::Zh) => format!("剩余 {}%", window.budget - window.used),
+        (false, _) => format!("{}% left", window.budget - window.used),
+    }
+}
+
+super::segment_with_cache!(BudgetSegment);
+
+/// Collects remaining budget data for display in the status line.
+impl Segment for BudgetSegment {
+    fn collect(&self, _input: &InputData, config: &Config) -> Option<SegmentData> {
+        let budget = &config.budget;
+        if !budget.is_set() {
+            return None;
+        }
+        let locale = config.style.resolved_locale();
+        let stats = super::fetch_usage(config, &self.cache)?;
+
+        let mut windows = Vec::new();
+        if let Some(limit) = budget.daily {
+            if let Some(used) = crate::state::daily_usage(&stats) {
+                windows.push(WindowBudget {
+                    label: locale.pick("today", "今日"),
+                    used,
+                    budget: limit,
+                });
+            }
+        }
+        if let (Some(limit), Some(token)) = (budget.five_hour, stats.token_usage.as_ref()) {
+            windows.push(WindowBudget {
+                label: locale.pick("5h", "5小时"),
+                used: token.percentage,
+                budget: limit,
+            });
+        }
+        if let (Some(limit), Some(weekly)) = (budget.weekly, stats.weekly_usage.as_ref()) {
+            windows.push(WindowBudget {
+                label: locale.pick("week", "本周"),
+                used: weekly.percentage,
+                budget: limit,
+            });
+        }
+        let window = tightest(&windows)?;
+
+        let show_window = config
+            .segments
+            .iter()
+            .find(|s| s.id == "budget")
+            .and_then(|s| s.options.get("show_window"))
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(true);
+        let secondary = if show_window { window.label } else { "" };
+
+        Some(
+            SegmentData::new(format_remaining(window, locale))
+                .with_secondary(secondary)
+                .with_metadata("percentage", window.budget_percentage())
+                .with_metadata("budget_exceeded", window.exceeded()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(label: &'static str, used: u8, budget: u8) -> WindowBudget {
+        WindowBudget {
+            label,
+            used,
+            budget,
+        }
+    }
+
+    #[test]
+    fn test_format_remaining() {
+        assert_eq!(
+            format_remaining(window("week", 50, 70), Locale::En),
+            "20% left"
+        );
+        assert_eq!(
+            format_remaining(window("week", 75, 70), Locale::En),
+            "+5% over"
+        );
+        assert_eq!(
+            format_remaining(window("week", 50, 70), Locale::Zh),
+            "剩余 20%"
+        );
+        assert_eq!(
+            format_remaining(window("week", 75, 70), Locale::Zh),
+            "超出 5%"
+        );
+    }
+
+    #[test]
+    fn test_budget_percentage() {
+        assert_eq!(window("5h", 35, 70).budget_percentage(), 50);
+        assert_eq!(window("5h", 90, 70).budget_percentage(), 100);
+    }
+
+    #[test]
+    fn test_tightest_picks_highest_budget_usage() {
+        let windows = [window("5h", 10, 50), window("week", 60, 70)];
+        assert_eq!(tightest(&windows).map(|w| w.label), Some("week"));
+        assert!(tightest(&[]).is_none());
+    }
+}
@@ -0,0 +1,138 @@
+//! Context segment for displaying locally counted session tokens.
+//!
+//! This segment reads the Claude Code transcript to show the context
+//! window fill and the tokens used by the session, without calling the
+//! GLM monitor API.
+
+use super::{Segment, SegmentData};
+use crate::config::{Config, InputData, SegmentConfig};
+use crate::transcript::TranscriptState;
+
+/// Default context window size in tokens.
+///
+/// Matches the 200K context of the GLM coding models.
+const DEFAULT_CONTEXT_WINDOW: u64 = 200_000;
+
+/// Returns the context window fill as a percentage, capped at 100.
+fn context_percentage(context_tokens: u64, window: u64) -> u8 {
+    if window == 0 {
+        return 0;
+    }
+    u8::try_from((context_tokens.saturating_mul(100) / window).min(100)).unwrap_or(100)
+}
+
+/// Reads the configured context window size.
+fn context_window(segment_config: Option<&SegmentConfig>) -> u64 {
+    segment_config
+        .and_then(|s| s.options.get("context_window"))
+        .and_then(serde_json::Value::as_u64)
+        .filter(|&w| w > 0)
+        .unwrap_or(DEFAULT_CONTEXT_WINDOW)
+}
+
+/// Builds segment data from transcript accounting.
+fn build_data(state: &TranscriptState, window: u64, show_tokens: bool) -> SegmentData {
+    let percentage = context_percentage(state.context_tokens, window);
+    let secondary = if show_tokens {
+        format!("{} tok", crate::util::format_compact(state.totals.total()))
+    } else {
+        String::new()
+    };
+    SegmentData::new(format!("{percentage}%"))
+        .with_secondary(secondary)
+        .with_metadata("percentage", percentage)
+        .with_metadata("context_tokens", state.context_tokens)
+        .with_metadata("input_tokens", state.totals.input)
+        .with_metadata("output_tokens", state.totals.output)
+        .with_metadata("cache_creation_tokens", state.totals.cache_creation)
+        .with_metadata("cache_read_tokens", state.totals.cache_read)
+        .with_metadata("total_tokens", state.totals.total())
+}
+
+/// Segment showing context window fill and session tokens.
+#[derive(Debug, Default)]
+pub struct ContextSegment;
+
+impl ContextSegment {
+    /// Create a new context segment.
+    #[must_use]
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Collects transcript token accounting for display in the status line.
+impl Segment for ContextSegment {
+    fn collect(&self, input: &InputData, config: &Config) -> Option<SegmentData> {
+        let state = crate::transcript::observe(config, input)?;
+
+        let segment_config = config.segments.iter().find(|s| s.id == "context");
+        let show_tokens = segment_config
+            .and_then(|s| s.options.get("show_tokens"))
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(true);
+
+        Some(build_data(
+            &state,
+            context_window(segment_config),
+            show_tokens,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transcript::TokenTotals;
+
+    #[test]
+    fn test_context_percentage() {
+        assert_eq!(context_percentage(0, 200_000), 0);
+        assert_eq!(context_percentage(50_000, 200_000), 25);
+        assert_eq!(context_percentage(400_000, 200_000), 100);
+        assert_eq!(context_percentage(10, 0), 0);
+    }
+
+    #[test]
+    fn test_context_window_default_and_override() {
+        assert_eq!(context_window(None), DEFAULT_CONTEXT_WINDOW);
+        let mut config = SegmentConfig::context();
+        config
+            .options
+            .insert("context_window".to_string(), serde_json::json!(128_000));
+        assert_eq!(context_window(Some(&config)), 128_000);
+    }
+
+    #[test]
+    fn test_build_data() {
+        let state = TranscriptState {
+            context_tokens: 100_000,
+            totals: TokenTotals {
+                input: 1_000,
+                output: 200,
+                cache_creation: 0,
+                cache_read: 1_200_000,
+            },
+            ..TranscriptState::default()
+        };
+        let data = build_data(&state, 200_000, true);
+        assert_eq!(data.primary, "50%");
+        assert_eq!(data.secondary, "1.2M tok");
+        assert_eq!(data.metadata.get("percentage"), Some(&"50".to_string()));
+        assert_eq!(data.metadata.get("output_tokens"), Some(&"200".to_string()));
+    }
+
+    #[test]
+    fn test_build_data_hides_tokens() {
+        let data = build_data(&TranscriptState::default(), 200_000, false);
+        assert!(data.secondary.is_empty());
+    }
+
+    #[test]
+    fn test_collect_without_transcript() {
+        let input = InputData::default();
+        assert!(ContextSegment::new()
+            .collect(&input, &Config::default())
+            .is_none());
+    }
+}
@@ -0,0 +1,190 @@
+//! Cost segment for displaying the session cost reported by Claude Code.
+//!
+//! This segment reads the `cost` payload Claude Code passes on stdin,
+//! so it works without the GLM monitor API.
+
+use super::{Segment, SegmentData};
+use crate::config::{Config, CostInfo, InputData, SegmentConfig};
+
+/// Display settings read from the segment options.
+#[derive(Debug, Clone, PartialEq)]
+struct CostOptions {
+    /// Symbol placed before the amount.
+    currency_symbol: String,
+    /// Number of decimal places.
+    precision: usize,
+    /// Multiplier applied to the USD amount before display.
+    exchange_rate: f64,
+    /// Show lines added/removed as secondary text.
+    show_lines: bool,
+}
+
+impl Default for CostOptions {
+    fn default() -> Self {
+        Self {
+            currency_symbol: "$".to_string(),
+            precision: 2,
+            exchange_rate: 1.0,
+            show_lines: true,
+        }
+    }
+}
+
+impl CostOptions {
+    fn from_config(segment_config: Option<&SegmentConfig>) -> Self {
+        let defaults = Self::default();
+        let Some(options) = segment_config.map(|s| &s.options) else {
+            return defaults;
+        };
+        Self {
+            currency_symbol: options
+                .get("currency_symbol")
+                .and_then(serde_json::Value::as_str)
+                .map_or(defaults.currency_symbol, str::to_string),
+            precision: options
+                .get("precision")
+                .and_then(serde_json::Value::as_u64)
+                .and_then(|p| usize::try_from(p).ok())
+                .map_or(defaults.precision, |p| p.min(6)),
+            exchange_rate: options
+                .get("exchange_rate")
+                .and_then(serde_json::Value::as_f64)
+                .filter(|rate| rate.is_finite() && *rate > 0.0)
+                .unwrap_or(defaults.exchange_rate),
+            show_lines: options
+                .get("show_lines")
+                .and_then(serde_json::Value::as_bool)
+                .unwrap_or(defaults.show_lines),
+        }
+    }
+}
+
+/// Formats the session cost as primary and secondary display text.
+///
+/// Returns `None` if Claude Code did not report a cost.
+fn format_cost(cost: &CostInfo, options: &CostOptions) -> Option<(String, String)> {
+    let usd = cost.total_cost_usd.filter(|c| c.is_finite())?;
+    let primary = format!(
+        "{}{:.*}",
+        options.currency_symbol,
+        options.precision,
+        usd * options.exchange_rate
+    );
+    let secondary = match (
+        options.show_lines,
+        cost.total_lines_added,
+        cost.total_lines_removed,
+    ) {
+        (true, Some(added), Some(removed)) => format!("+{added}/-{removed}"),
+        (true, Some(added), None) => format!("+{added}"),
+        (true, None, Some(removed)) => format!("-{removed}"),
+        _ => String::new(),
+    };
+    Some((primary, secondary))
+}
+
+/// Segment showing the session cost reported by Claude Code.
+#[derive(Debug, Default)]
+pub struct CostSegment;
+
+impl CostSegment {
+    /// Create a new cost segment.
+    #[must_use]
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Collects session cost data for display in the status line.
+impl Segment for CostSegment {
+    fn collect(&self, input: &InputData, config: &Config) -> Option<SegmentData> {
+        let cost = input.cost_info.as_ref()?;
+        let options = CostOptions::from_config(config.segments.iter().find(|s| s.id == "cost"));
+        let (primary, secondary) = format_cost(cost, &options)?;
+
+        let mut out = SegmentData::new(primary).with_secondary(secondary);
+        if let Some(usd) = cost.total_cost_usd {
+            out = out.with_metadata("cost_usd", usd);
+        }
+        if let Some(ms) = cost.total_duration_ms {
+            out = out.with_metadata("duration_ms", ms);
+        }
+        if let Some(ms) = cost.total_api_duration_ms {
+            out = out.with_metadata("api_duration_ms", ms);
+        }
+        if let Some(lines) = cost.total_lines_added {
+            out = out.with_metadata("lines_added", lines);
+        }
+        if let Some(lines) = cost.total_lines_removed {
+            out = out.with_metadata("lines_removed", lines);
+        }
+        Some(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cost(usd: f64) -> CostInfo {
+        CostInfo {
+            total_cost_usd: Some(usd),
+            total_lines_added: Some(156),
+            total_lines_removed: Some(23),
+            ..CostInfo::default()
+        }
+    }
+
+    #[test]
+    fn test_format_cost_defaults() {
+        let (primary, secondary) = format_cost(&cost(0.012_34), &CostOptions::default()).unwrap();
+        assert_eq!(primary, "$0.01");
+        assert_eq!(secondary, "+156/-23");
+    }
+
+    #[test]
+    fn test_format_cost_custom_symbol_precision_and_rate() {
+        let options = CostOptions {
+            currency_symbol: "¥".to_string(),
+            precision: 3,
+            exchange_rate: 7.0,
+            show_lines: false,
+        };
+        let (primary, secondary) = format_cost(&cost(0.5), &options).unwrap();
+        assert_eq!(primary, "¥3.500");
+        assert!(secondary.is_empty());
+    }
+
+    #[test]
+    fn test_format_cost_missing_amount() {
+        assert!(format_cost(&CostInfo::default(), &CostOptions::default()).is_none());
+    }
+
+    #[test]
+    fn test_options_from_config() {
+        let mut config = SegmentConfig::cost();
+        config
+            .options
+            .insert("currency_symbol".to_string(), serde_json::json!("€"));
+        config
+            .options
+            .insert("precision".to_string(), serde_json::json!(4));
+        config
+            .options
+            .insert("exchange_rate".to_string(), serde_json::json!(-1.0));
+
+        let options = CostOptions::from_config(Some(&config));
+        assert_eq!(options.currency_symbol, "€");
+        assert_eq!(options.precision, 4);
+        assert!((options.exchange_rate - 1.0).abs() < f64::EPSILON);
+        assert!(options.show_lines);
+    }
+
+    #[test]
+    fn test_collect_without_cost_payload() {
+        let input = InputData::default();
+        assert!(CostSegment::new()
+            .collect(&input, &Config::default())
+            .is_none());
+    }
+}
@@ -1,24 +1,114 @@
 //! MCP usage segment for displaying MCP tool time consumption.
 //!
 //! This segment shows the used vs limit time for MCP tool usage
-//! within the 30-day billing period.
+//! within the 30-day billing period, followed by the MCP calls made
+//! in the current session when a transcript is available.
 
 use super::{Segment, SegmentData};
 use crate::api::SharedCache;
-use crate::config::{Config, InputData};
+use crate::config::{Config, InputData, QuotaDisplay, SegmentConfig};
+
+/// Appends the session calls, if any, to the account usage text.
+fn format_mcp(usage: String, session_calls: Option<u64>) -> String {
+    match session_calls {
+        Some(calls) => format!("{usage} (+{calls})"),
+        None => usage,
+    }
+}
+
+/// GLM's own MCP servers (search, web reader, zread and vision), the only
+/// ones metered by the account's MCP quota.
+const GLM_MCP_SERVERS: [&str; 4] = ["web-search-prime", "web-reader", "zread", "zai-mcp-server"];
+
+/// Reads the MCP server names counted as session calls.
+///
+/// Defaults to [`GLM_MCP_SERVERS`]; an explicit empty list counts calls to
+/// every MCP server.
+fn session_servers(segment_config: Option<&SegmentConfig>) -> Vec<String> {
+    segment_config
+        .and_then(|s| s.options.get("session_servers"))
+        .and_then(serde_json::Value::as_array)
+        .map_or_else(
+            || GLM_MCP_SERVERS.map(str::to_string).to_vec(),
+            |servers| {
+                servers
+                    .iter()
+                    .filter_map(serde_json::Value::as_str)
+                    .map(str::to_string)
+                    .collect()
+            },
+        )
+}
 
 super::segment_with_cache!(McpUsageSegment);
 
 /// Collects MCP usage data for display in the status line.
 impl Segment for McpUsageSegment {
-    fn collect(&self, _input: &InputData, config: &Config) -> Option<SegmentData> {
-        let stats = super::fetch_usage(config, &self.cache)?;
+    fn collect(&self, input: &InputData, config: &Config) -> Option<SegmentData> {
+        let Some(stats) = super::fetch_usage(config, &self.cache) else {
+            return super::unavailable(config, "mcp_usage");
+        };
 
         let mcp = stats.mcp_usage.as_ref()?;
-        let primary = format!("{}/{}", mcp.used, mcp.limit);
 
-        let out = SegmentData::new(primary).with_metadata("percentage", mcp.percentage);
+        let segment_config = config.segments.iter().find(|s| s.id == "mcp_usage");
+        let show_session = segment_config
+            .and_then(|s| s.options.get("show_session"))
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(true);
+        let session_calls = if show_session {
+            crate::transcript::observe(config, input)
+                .map(|state| state.mcp_call_count(&session_servers(segment_config)))
+        } else {
+            None
+        };
+
+        let usage = super::format_quota(
+            mcp,
+            segment_config,
+            QuotaDisplay::UsedLimit,
+            config.style.resolved_locale(),
+        );
+        let primary = format_mcp(usage, session_calls);
+
+        let mut out = SegmentData::new(primary)
+            .with_metadata("percentage", mcp.percentage)
+            .with_quota_amounts(mcp, segment_config, config.style.resolved_locale());
+        if let Some(calls) = session_calls {
+            out = out.with_metadata("session_calls", calls);
+        }
+
+        Some(out.with_cache_state(&self.cache))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_mcp() {
+        assert_eq!(format_mcp("30/100".to_string(), None), "30/100");
+        assert_eq!(format_mcp("30/100".to_string(), Some(7)), "30/100 (+7)");
+    }
+
+    #[test]
+    fn test_session_servers_option() {
+        assert_eq!(session_servers(None), GLM_MCP_SERVERS.map(str::to_string));
+
+        let mut config = SegmentConfig::mcp_usage();
+        config
+            .options
+            .insert("session_servers".to_string(), serde_json::json!([]));
+        assert!(session_servers(Some(&config)).is_empty());
 
-        Some(out)
+        config.options.insert(
+            "session_servers".to_string(),
+            serde_json::json!(["web-reader", "zread"]),
+        );
+        assert_eq!(
+            session_servers(Some(&config)),
+            vec!["web-reader".to_string(), "zread".to_string()]
+        );
     }
 }
@@ -3,12 +3,18 @@
 //! This module provides segment types that collect usage data
 //! and format it for display in the status bar.
 
+pub mod budget;
+pub mod context;
+pub mod cost;
 pub mod mcp_usage;
+pub mod session;
 pub mod token_usage;
+pub mod value;
 pub mod weekly_usage;
 
-use crate::api::{GlmApiClient, SharedCache, UsageStats};
-use crate::config::{Config, InputData};
+use crate::api::{GlmApiClient, QuotaUsage, SharedCache, UsageStats};
+use crate::config::{Config, InputData, Locale, QuotaDisplay, SegmentConfig};
+use serde::Serialize;
 use std::collections::HashMap;
 use std::time::Duration;
 
@@ -51,16 +57,81 @@ pub(super) fn fetch_usage(config: &Config, cache: &SharedCache) -> Option<UsageS
         GlmApiClient::from_env(timeout, retries)
             .ok()?
             .fetch_usage_stats()
+            .inspect_err(|e| {
+                if crate::api::is_unauthorized(e) {
+                    cache.mark_auth_failed();
+                }
+            })
             .ok()
     };
     if config.cache.enabled {
         cache.get_or_fetch(config.cache.ttl_seconds, fetch)
     } else {
-        fetch()
+        // Still remember the result so post-render bookkeeping can see it
+        let stats = fetch()?;
+        cache.put(stats.clone());
+        Some(stats)
+    }
+}
+
+/// Formats a quota's usage in the segment's `display` mode (or `default`).
+///
+/// Amounts are compacted (`1.2M`, or `120万` in Chinese) unless the segment
+/// sets `compact_numbers = false`, which shows them with digit separators.
+pub(super) fn format_quota(
+    quota: &QuotaUsage,
+    segment: Option<&SegmentConfig>,
+    default: QuotaDisplay,
+    locale: Locale,
+) -> String {
+    let display = segment
+        .and_then(|s| QuotaDisplay::from_options(&s.options))
+        .and_then(Result::ok)
+        .unwrap_or(default);
+    let locale = locale.resolve();
+    let amount = |value: i64| format_amount(value, segment, locale);
+    let left = |text: String| match locale {
+        Locale::Zh => format!("剩余 {text}"),
+        _ => format!("{text} left"),
+    };
+    match display {
+        QuotaDisplay::Percent => format!("{}%", quota.percentage),
+        QuotaDisplay::UsedLimit => format!("{}/{}", amount(quota.used), amount(quota.limit)),
+        QuotaDisplay::Remaining => left(amount(quota.limit.saturating_sub(quota.used))),
+        QuotaDisplay::RemainingPercent => {
+            left(format!("{}%", 100u8.saturating_sub(quota.percentage)))
+        }
     }
 }
 
-#[derive(Debug, Clone, Default)]
+/// Formats a quota amount, compacted unless the segment sets
+/// `compact_numbers = false`.
+fn format_amount(value: i64, segment: Option<&SegmentConfig>, locale: Locale) -> String {
+    let compact = segment
+        .and_then(|s| s.options.get("compact_numbers"))
+        .and_then(serde_json::Value::as_bool)
+        .unwrap_or(true);
+    let value = u64::try_from(value).unwrap_or(0);
+    match (compact, locale.resolve()) {
+        (false, _) => crate::util::format_grouped(value),
+        (true, Locale::Zh) => crate::util::format_compact_zh(value),
+        (true, _) => crate::util::format_compact(value),
+    }
+}
+
+/// Returns the data shown for a quota segment whose usage cannot be fetched.
+///
+/// The segment shows `--` with its error icon, or is hidden if it has none.
+pub(super) fn unavailable(config: &Config, id: &str) -> Option<SegmentData> {
+    config
+        .segments
+        .iter()
+        .find(|s| s.id == id)
+        .and_then(|s| s.icon.error.as_ref())?;
+    Some(SegmentData::new("--").with_metadata("state", "error"))
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct SegmentData {
     /// Main display text (e.g., "50%").
     pub primary: String,
@@ -99,6 +170,32 @@ impl SegmentData {
         self.metadata.insert(key.into(), value.to_string());
         self
     }
+
+    /// Adds the quota's raw `used`/`limit` amounts, and the same amounts
+    /// formatted like [`format_quota`] as `used_display`/`limit_display`
+    /// for format templates.
+    #[must_use]
+    pub(super) fn with_quota_amounts(
+        self,
+        quota: &QuotaUsage,
+        segment: Option<&SegmentConfig>,
+        locale: Locale,
+    ) -> Self {
+        self.with_metadata("used", quota.used)
+            .with_metadata("limit", quota.limit)
+            .with_metadata("used_display", format_amount(quota.used, segment, locale))
+            .with_metadata("limit_display", format_amount(quota.limit, segment, locale))
+    }
+
+    /// Marks the data as stale if `cache` served it after a failed fetch.
+    #[must_use]
+    pub fn with_cache_state(self, cache: &SharedCache) -> Self {
+        if cache.is_stale() {
+            self.with_metadata("state", "stale")
+        } else {
+            self
+        }
+    }
 }
 
 /// Trait for segments that collect and format usage data for display.
@@ -110,17 +207,103 @@ pub trait Segment: Send + Sync {
     fn collect(&self, input: &InputData, config: &Config) -> Option<SegmentData>;
 }
 
+#[doc(inline)]
+pub use budget::BudgetSegment;
+#[doc(inline)]
+pub use context::ContextSegment;
+#[doc(inline)]
+pub use cost::CostSegment;
 #[doc(inline)]
 pub use mcp_usage::McpUsageSegment;
 #[doc(inline)]
+pub use session::SessionSegment;
+#[doc(inline)]
 pub use token_usage::TokenUsageSegment;
 #[doc(inline)]
+pub use value::ValueSegment;
+#[doc(inline)]
 pub use weekly_usage::WeeklyUsageSegment;
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn quota(used: i64, limit: i64, percentage: u8) -> QuotaUsage {
+        QuotaUsage {
+            used,
+            limit,
+            percentage,
+            time_window: "weekly".to_string(),
+            reset_at: None,
+        }
+    }
+
+    fn display_segment(display: &str) -> SegmentConfig {
+        let mut segment = SegmentConfig::weekly_usage();
+        segment
+            .options
+            .insert("display".to_string(), serde_json::json!(display));
+        segment
+    }
+
+    #[test]
+    fn test_format_quota_modes() {
+        let weekly = quota(1_200_000, 5_000_000, 24);
+        let format = |segment: Option<&SegmentConfig>, locale| {
+            format_quota(&weekly, segment, QuotaDisplay::Percent, locale)
+        };
+
+        assert_eq!(format(None, Locale::En), "24%");
+        let segment = display_segment("used_limit");
+        assert_eq!(format(Some(&segment), Locale::En), "1.2M/5M");
+        assert_eq!(format(Some(&segment), Locale::Zh), "120万/500万");
+        let segment = display_segment("remaining");
+        assert_eq!(format(Some(&segment), Locale::En), "3.8M left");
+        assert_eq!(format(Some(&segment), Locale::Zh), "剩余 380万");
+        let segment = display_segment("remaining_percent");
+        assert_eq!(format(Some(&segment), Locale::En), "76% left");
+        assert_eq!(format(Some(&segment), Locale::Zh), "剩余 76%");
+    }
+
+    #[test]
+    fn test_format_quota_without_compact_numbers() {
+        let weekly = quota(1_200_000, 5_000_000, 24);
+        let mut segment = display_segment("used_limit");
+        segment
+            .options
+            .insert("compact_numbers".to_string(), serde_json::json!(false));
+        assert_eq!(
+            format_quota(&weekly, Some(&segment), QuotaDisplay::Percent, Locale::En),
+            "1,200,000/5,000,000"
+        );
+    }
+
+    #[test]
+    fn test_with_quota_amounts() {
+        let weekly = quota(1_200_000, 5_000_000, 24);
+        let data = SegmentData::new("24%").with_quota_amounts(&weekly, None, Locale::En);
+        assert_eq!(data.metadata["used"], "1200000");
+        assert_eq!(data.metadata["used_display"], "1.2M");
+        assert_eq!(data.metadata["limit_display"], "5M");
+
+        let mut segment = display_segment("percent");
+        segment
+            .options
+            .insert("compact_numbers".to_string(), serde_json::json!(false));
+        let data = SegmentData::new("24%").with_quota_amounts(&weekly, Some(&segment), Locale::Zh);
+        assert_eq!(data.metadata["limit_display"], "5,000,000");
+    }
+
+    #[test]
+    fn test_format_quota_falls_back_on_invalid_display() {
+        let mcp = quota(30, 100, 30);
+        let segment = display_segment("bogus");
+        assert_eq!(
+            format_quota(&mcp, Some(&segment), QuotaDisplay::UsedLimit, Locale::En),
+            "30/100"
+        );
+    }
+
     #[test]
     fn test_segmentdata_new() {
         let data = SegmentData::new("50%");
@@ -201,4 +384,36 @@ mod tests {
         let result = fetch_usage(&config, &cache);
         assert!(result.is_none());
     }
+
+    #[test]
+    fn test_unavailable_needs_error_icon() {
+        let mut config = Config::default();
+        assert!(unavailable(&config, "token_usage").is_none());
+
+        config.segments[0].icon.error = Some(crate::config::IconVariants {
+            emoji: "⚠️".to_string(),
+            ..Default::default()
+        });
+        let data = unavailable(&config, "token_usage").unwrap();
+        assert_eq!(data.primary, "--");
+        assert_eq!(data.metadata.get("state"), Some(&"error".to_string()));
+        assert!(unavailable(&config, "weekly_usage").is_none());
+    }
+
+    #[test]
+    fn test_segmentdata_with_cache_state() {
+        let cache = SharedCache::new();
+        let data = SegmentData::new("50%").with_cache_state(&cache);
+        assert!(!data.metadata.contains_key("state"));
+
+        cache.put(UsageStats {
+            token_usage: None,
+            weekly_usage: None,
+            mcp_usage: None,
+        });
+        std::thread::sleep(Duration::from_millis(10));
+        cache.get_or_fetch(0, || None);
+        let data = SegmentData::new("50%").with_cache_state(&cache);
+        assert_eq!(data.metadata.get("state"), Some(&"stale".to_string()));
+    }
 }
@@ -0,0 +1,108 @@
+//! Session segment for displaying quota consumed by the current session.
+//!
+//! This segment shows how many percentage points of the 5-hour and
+//! weekly quotas were consumed since the Claude Code session started.
+
+use super::{Segment, SegmentData};
+use crate::api::SharedCache;
+use crate::config::{Config, InputData, Locale};
+use crate::state::SessionEntry;
+
+/// Formats session consumption as primary and secondary display text.
+fn format_session(entry: &SessionEntry, show_weekly: bool, locale: Locale) -> (String, String) {
+    let primary = match locale.resolve() {
+        Locale::Zh => format!("本会话 +{}%", entry.five_hour_pct),
+        _ => format!("+{}% this session", entry.five_hour_pct),
+    };
+    let secondary = if show_weekly && entry.last_weekly.is_some() {
+        format!("{} +{}%", locale.pick("wk", "周"), entry.weekly_pct)
+    } else {
+        String::new()
+    };
+    (primary, secondary)
+}
+
+super::segment_with_cache!(SessionSegment);
+
+/// Collects session consumption data for display in the status line.
+impl Segment for SessionSegment {
+    fn collect(&self, input: &InputData, config: &Config) -> Option<SegmentData> {
+        let session_id = input.session_id.as_deref().filter(|id| !id.is_empty())?;
+        let stats = super::fetch_usage(config, &self.cache)?;
+        let entry = crate::state::observe_session(config, session_id, &stats)?;
+
+        let show_weekly = config
+            .segments
+            .iter()
+            .find(|s| s.id == "session")
+            .and_then(|s| s.options.get("show_weekly"))
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(true);
+
+        let (primary, secondary) =
+            format_session(&entry, show_weekly, config.style.resolved_locale());
+
+        Some(
+            SegmentData::new(primary)
+                .with_secondary(secondary)
+                .with_metadata("session_five_hour", entry.five_hour_pct)
+                .with_metadata("session_weekly", entry.weekly_pct),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::SessionStore;
+
+    #[test]
+    fn test_format_session_with_weekly() {
+        let entry = SessionEntry {
+            five_hour_pct: 12,
+            weekly_pct: 3,
+            last_weekly: Some(crate::state::QuotaSnapshot::default()),
+            ..SessionEntry::default()
+        };
+        let (primary, secondary) = format_session(&entry, true, Locale::En);
+        assert_eq!(primary, "+12% this session");
+        assert_eq!(secondary, "wk +3%");
+
+        let (primary, secondary) = format_session(&entry, true, Locale::Zh);
+        assert_eq!(primary, "本会话 +12%");
+        assert_eq!(secondary, "周 +3%");
+    }
+
+    #[test]
+    fn test_format_session_hides_weekly() {
+        let entry = SessionEntry {
+            five_hour_pct: 5,
+            weekly_pct: 1,
+            last_weekly: Some(crate::state::QuotaSnapshot::default()),
+            ..SessionEntry::default()
+        };
+        let (_, secondary) = format_session(&entry, false, Locale::En);
+        assert!(secondary.is_empty());
+    }
+
+    #[test]
+    fn test_format_session_without_weekly_quota() {
+        let mut store = SessionStore::default();
+        let stats = crate::api::UsageStats {
+            token_usage: None,
+            weekly_usage: None,
+            mcp_usage: None,
+        };
+        let entry = store.observe("s1", &stats, 0, 60);
+        let (primary, secondary) = format_session(&entry, true, Locale::En);
+        assert_eq!(primary, "+0% this session");
+        assert!(secondary.is_empty());
+    }
+
+    #[test]
+    fn test_collect_without_session_id() {
+        let segment = SessionSegment::new();
+        let input = InputData::default();
+        assert!(segment.collect(&input, &Config::default()).is_none());
+    }
+}
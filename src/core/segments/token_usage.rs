@@ -5,7 +5,7 @@
 
 use super::{Segment, SegmentData};
 use crate::api::SharedCache;
-use crate::config::{Config, DisplayMode, InputData, SegmentConfig};
+use crate::config::{Config, DisplayMode, InputData, Locale, QuotaDisplay, SegmentConfig};
 use std::sync::LazyLock;
 use std::time::{SystemTime, UNIX_EPOCH};
 use time::{format_description, Month, OffsetDateTime, UtcOffset};
@@ -24,7 +24,7 @@ static CLOCK_FORMAT: LazyLock<Vec<time::format_description::FormatItem<'static>>
 
 /// Timer display mode determining how reset time is shown.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum TimerMode {
+pub(super) enum TimerMode {
     /// Shows reset time as a clock (e.g., "14:00").
     Clock,
     /// Shows time remaining until reset as countdown (e.g., "2h 30m").
@@ -61,8 +61,8 @@ fn parse_ymd(s: &str) -> Option<(i32, Month, u8)> {
     Some((y, month, d))
 }
 
-/// Formats remaining time as "H:MM" countdown.
-fn format_countdown(reset_at: i64) -> String {
+/// Formats remaining time as an "H:MM" countdown, or "2小时30分" in Chinese.
+fn format_countdown(reset_at: i64, locale: Locale) -> String {
     #[expect(
         clippy::cast_possible_wrap,
         reason = "current epoch seconds fit in i64"
@@ -72,16 +72,15 @@ fn format_countdown(reset_at: i64) -> String {
         .unwrap_or_default()
         .as_secs() as i64;
 
-    let remaining = reset_at.saturating_sub(now);
-
-    if remaining <= 0 {
-        return "0:00".to_string();
-    }
-
+    let remaining = reset_at.saturating_sub(now).max(0);
     let hours = remaining / 3600;
     let minutes = (remaining % 3600) / 60;
 
-    format!("{hours}:{minutes:02}")
+    match locale.resolve() {
+        Locale::Zh if hours > 0 => format!("{hours}小时{minutes}分"),
+        Locale::Zh => format!("{minutes}分"),
+        _ => format!("{hours}:{minutes:02}"),
+    }
 }
 
 /// Formats the reset timestamp as a local clock time "HH:MM".
@@ -100,13 +99,17 @@ fn format_clock_time(reset_at: i64) -> String {
 }
 
 /// Formats the reset time according to the timer mode.
-fn format_reset_time(reset_at: Option<i64>, timer_mode: TimerMode) -> String {
+pub(super) fn format_reset_time(
+    reset_at: Option<i64>,
+    timer_mode: TimerMode,
+    locale: Locale,
+) -> String {
     let Some(at) = reset_at else {
         return "--:--".to_string();
     };
 
     match timer_mode {
-        TimerMode::Countdown => format_countdown(at),
+        TimerMode::Countdown => format_countdown(at, locale),
         TimerMode::Clock => format_clock_time(at),
     }
 }
@@ -115,12 +118,24 @@ fn format_reset_time(reset_at: Option<i64>, timer_mode: TimerMode) -> String {
 fn get_timer_icon(timer_mode: TimerMode, display_mode: DisplayMode) -> &'static str {
     match (timer_mode, display_mode) {
         (TimerMode::Countdown, DisplayMode::Ascii) => "!",
+        (TimerMode::Countdown, DisplayMode::NerdFont) => "\u{f252}",
         (TimerMode::Countdown, _) => "⌛️",
         (TimerMode::Clock, DisplayMode::Ascii) => "@",
+        (TimerMode::Clock, DisplayMode::NerdFont) => "\u{f017}",
         (TimerMode::Clock, _) => "⏱\u{FE0F}",
     }
 }
 
+/// Returns the spike marker shown next to the usage percentage.
+fn format_spike_marker(rate: f64, display_mode: DisplayMode) -> String {
+    let icon = match display_mode {
+        DisplayMode::Ascii => "^",
+        DisplayMode::NerdFont => "\u{f06d}",
+        _ => "🔥",
+    };
+    format!("{icon} {rate:.1}%/m")
+}
+
 /// Returns the current minutes since midnight in UTC+8 timezone.
 fn current_minutes_since_midnight() -> u16 {
     let now_utc8 = OffsetDateTime::now_utc().to_offset(UTC_PLUS_8);
@@ -128,7 +143,7 @@ fn current_minutes_since_midnight() -> u16 {
 }
 
 /// Returns whether the current time falls within the peak time range.
-fn is_peak_time(peak_start: &str, peak_end: &str) -> Option<bool> {
+pub(crate) fn is_peak_time(peak_start: &str, peak_end: &str) -> Option<bool> {
     let current = current_minutes_since_midnight();
     let start = parse_hhmm(peak_start)?;
     let end = parse_hhmm(peak_end)?;
@@ -143,24 +158,27 @@ fn is_promo_active(expires: &str) -> Option<bool> {
     Some(now_utc8.date() <= expires_date)
 }
 
-/// Calculates the usage multiplier based on model, time, and promo status.
-fn calculate_multiplier(input: &InputData, config: &Config) -> f64 {
-    let model_id = match input.model.as_ref() {
-        Some(m) => &m.id,
-        None => return 1.0,
+/// Returns whether the current model is one of the premium models.
+pub(crate) fn is_premium_model(input: &InputData, config: &Config) -> bool {
+    let Some(model) = input.model.as_ref() else {
+        return false;
     };
-
-    let mc = &config.multiplier;
-    let model_lower = model_id.to_lowercase();
-    let is_premium = mc
+    let model_lower = model.id.to_lowercase();
+    config
+        .multiplier
         .premium_models
         .iter()
-        .any(|pm| model_lower.contains(&pm.to_lowercase()));
+        .any(|pm| model_lower.contains(&pm.to_lowercase()))
+}
 
-    if !is_premium {
+/// Calculates the usage multiplier based on model, time, and promo status.
+pub(crate) fn calculate_multiplier(input: &InputData, config: &Config) -> f64 {
+    if !is_premium_model(input, config) {
         return 1.0;
     }
 
+    let mc = &config.multiplier;
+
     let Some(is_peak) = is_peak_time(&mc.peak_start, &mc.peak_end) else {
         return 1.0;
     };
@@ -196,7 +214,7 @@ fn format_multiplier(value: f64) -> String {
 }
 
 /// Resolves whether to show timer and which mode from segment config.
-fn resolve_timer_mode(segment_config: Option<&SegmentConfig>) -> (bool, TimerMode) {
+pub(super) fn resolve_timer_mode(segment_config: Option<&SegmentConfig>) -> (bool, TimerMode) {
     let show_timer_opt = segment_config
         .and_then(|s| s.options.get("show_timer"))
         .and_then(serde_json::Value::as_bool);
@@ -224,21 +242,29 @@ super::segment_with_cache!(TokenUsageSegment);
 /// Collects token usage data for display in the status line.
 impl Segment for TokenUsageSegment {
     fn collect(&self, input: &InputData, config: &Config) -> Option<SegmentData> {
-        let stats = super::fetch_usage(config, &self.cache)?;
+        let Some(stats) = super::fetch_usage(config, &self.cache) else {
+            return super::unavailable(config, "token_usage");
+        };
 
         let token = stats.token_usage.as_ref()?;
 
-        let primary = format!("{}%", token.percentage);
-
         let resolved_mode = config.style.resolved_mode();
 
         let segment_config = config.segments.iter().find(|s| s.id == "token_usage");
 
+        let primary = super::format_quota(
+            token,
+            segment_config,
+            QuotaDisplay::Percent,
+            config.style.resolved_locale(),
+        );
+
         let (show_timer, timer_mode) = resolve_timer_mode(segment_config);
 
+        let formatted_time =
+            format_reset_time(token.reset_at, timer_mode, config.style.resolved_locale());
         let secondary = if show_timer {
             let timer_icon = get_timer_icon(timer_mode, resolved_mode);
-            let formatted_time = format_reset_time(token.reset_at, timer_mode);
             format!("{timer_icon} {formatted_time}")
         } else {
             String::new()
@@ -255,13 +281,29 @@ impl Segment for TokenUsageSegment {
 
         let mut out = SegmentData::new(primary)
             .with_secondary(secondary)
-            .with_metadata("percentage", token.percentage);
+            .with_metadata("percentage", token.percentage)
+            .with_quota_amounts(token, segment_config, config.style.resolved_locale())
+            .with_metadata("reset", formatted_time);
 
         if let Some(m) = multiplier_str {
             out = out.with_multiplier(m);
         }
 
-        Some(out)
+        if let Some(budget) = config.budget.five_hour {
+            out = out.with_metadata("budget", budget);
+        }
+
+        let show_spike = segment_config
+            .and_then(|s| s.options.get("show_spike"))
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(true);
+        if show_spike {
+            if let Some(spike) = crate::state::current_spike(config, &stats) {
+                out = out.with_metadata("spike", format_spike_marker(spike.rate, resolved_mode));
+            }
+        }
+
+        Some(out.with_cache_state(&self.cache))
     }
 }
 
@@ -304,7 +346,7 @@ mod tests {
     #[test]
     fn test_format_countdown_future() {
         let far_future = 2000000000i64;
-        let result = format_countdown(far_future);
+        let result = format_countdown(far_future, Locale::En);
         assert!(result.contains(':'));
         assert!(!result.is_empty());
     }
@@ -312,7 +354,7 @@ mod tests {
     #[test]
     fn test_format_countdown_past() {
         let past = 1000i64;
-        assert_eq!(format_countdown(past), "0:00");
+        assert_eq!(format_countdown(past, Locale::En), "0:00");
     }
 
     #[test]
@@ -361,22 +403,31 @@ mod tests {
 
     #[test]
     fn test_format_reset_time_none_countdown() {
-        assert_eq!(format_reset_time(None, TimerMode::Countdown), "--:--");
+        assert_eq!(
+            format_reset_time(None, TimerMode::Countdown, Locale::En),
+            "--:--"
+        );
     }
 
     #[test]
     fn test_format_reset_time_none_clock() {
-        assert_eq!(format_reset_time(None, TimerMode::Clock), "--:--");
+        assert_eq!(
+            format_reset_time(None, TimerMode::Clock, Locale::En),
+            "--:--"
+        );
     }
 
     #[test]
     fn test_format_reset_time_countdown_past() {
-        assert_eq!(format_reset_time(Some(1000), TimerMode::Countdown), "0:00");
+        assert_eq!(
+            format_reset_time(Some(1000), TimerMode::Countdown, Locale::En),
+            "0:00"
+        );
     }
 
     #[test]
     fn test_format_reset_time_clock_valid() {
-        let result = format_reset_time(Some(2000000000), TimerMode::Clock);
+        let result = format_reset_time(Some(2000000000), TimerMode::Clock, Locale::En);
         assert!(result.contains(':'));
     }
 
@@ -405,18 +456,30 @@ mod tests {
         assert!(!icon.is_ascii());
     }
 
+    #[test]
+    fn test_get_timer_icon_nerd_font() {
+        assert_eq!(
+            get_timer_icon(TimerMode::Clock, DisplayMode::NerdFont),
+            "\u{f017}"
+        );
+        assert_eq!(
+            get_timer_icon(TimerMode::Countdown, DisplayMode::NerdFont),
+            "\u{f252}"
+        );
+    }
+
     #[test]
     fn test_format_countdown_zero() {
         let now = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .map(|d| d.as_secs() as i64)
             .unwrap_or(0);
-        assert_eq!(format_countdown(now), "0:00");
+        assert_eq!(format_countdown(now, Locale::En), "0:00");
     }
 
     #[test]
     fn test_format_countdown_ancient_timestamp() {
-        assert_eq!(format_countdown(1000), "0:00");
+        assert_eq!(format_countdown(1000, Locale::En), "0:00");
     }
 
     #[test]
@@ -425,7 +488,7 @@ mod tests {
             .duration_since(UNIX_EPOCH)
             .map(|d| d.as_secs() as i64)
             .unwrap_or(0);
-        let result = format_countdown(now + 3600);
+        let result = format_countdown(now + 3600, Locale::En);
         assert_eq!(result, "1:00");
     }
 
@@ -435,10 +498,18 @@ mod tests {
             .duration_since(UNIX_EPOCH)
             .map(|d| d.as_secs() as i64)
             .unwrap_or(0);
-        let result = format_countdown(now + 86400);
+        let result = format_countdown(now + 86400, Locale::En);
         assert_eq!(result, "24:00");
     }
 
+    #[test]
+    fn test_format_countdown_chinese() {
+        let now = crate::util::unix_now();
+        assert_eq!(format_countdown(now + 9030, Locale::Zh), "2小时30分");
+        assert_eq!(format_countdown(now + 630, Locale::Zh), "10分");
+        assert_eq!(format_countdown(1000, Locale::Zh), "0分");
+    }
+
     #[test]
     fn test_timer_mode_from_str_countdown() {
         assert_eq!(TimerMode::from_str("countdown"), TimerMode::Countdown);
@@ -540,6 +611,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_format_spike_marker() {
+        assert_eq!(format_spike_marker(2.5, DisplayMode::Ascii), "^ 2.5%/m");
+        assert!(format_spike_marker(2.5, DisplayMode::Emoji).starts_with('🔥'));
+        assert_eq!(
+            format_spike_marker(2.5, DisplayMode::NerdFont),
+            "\u{f06d} 2.5%/m"
+        );
+    }
+
     #[test]
     fn test_current_minutes_since_midnight() {
         let mins = current_minutes_since_midnight();
@@ -0,0 +1,130 @@
+//! Value segment for displaying the metered equivalent of this week's usage.
+//!
+//! This segment prices the tokens counted from Claude Code transcripts
+//! with the `[pricing]` table, showing what the same usage would have
+//! cost on pay-as-you-go API pricing.
+
+use super::{Segment, SegmentData};
+use crate::api::SharedCache;
+use crate::config::{Config, InputData};
+use crate::transcript::{day_of, DailyLedger};
+
+/// Days in a weekly quota window.
+const WEEK_DAYS: i64 = 7;
+
+/// Returns the first day of the current weekly window.
+///
+/// Uses the weekly quota reset time when known, otherwise the last
+/// seven days including today.
+fn week_start_day(weekly_reset_at: Option<i64>, now: i64) -> i64 {
+    match weekly_reset_at {
+        Some(reset_at) if reset_at > now => day_of(reset_at) - WEEK_DAYS,
+        _ => day_of(now) - (WEEK_DAYS - 1),
+    }
+}
+
+/// Formats the weekly value as primary and secondary display text.
+fn format_value(config: &Config, value: f64, precision: usize) -> (String, String) {
+    let pricing = &config.pricing;
+    let primary = format!("{}{value:.precision$}", pricing.currency_symbol);
+    let secondary = pricing
+        .prorated_plan_price(u32::try_from(WEEK_DAYS).unwrap_or(7))
+        .map(|plan| format!("{:.1}x plan", value / plan))
+        .unwrap_or_default();
+    (primary, secondary)
+}
+
+/// Computes the metered value of the ledger from `first_day` onwards.
+fn weekly_value(config: &Config, ledger: &DailyLedger, first_day: i64) -> f64 {
+    crate::transcript::summarize(&config.pricing, &ledger.since(first_day)).total
+}
+
+super::segment_with_cache!(ValueSegment);
+
+/// Collects the metered value of this week's usage for display in the status line.
+impl Segment for ValueSegment {
+    fn collect(&self, input: &InputData, config: &Config) -> Option<SegmentData> {
+        // Count the current transcript before summing the ledger.
+        let _ = crate::transcript::observe(config, input);
+        let ledger = crate::transcript::load_daily();
+        if ledger.days.is_empty() {
+            return None;
+        }
+
+        let weekly_reset_at = super::fetch_usage(config, &self.cache)
+            .and_then(|stats| stats.weekly_usage)
+            .and_then(|weekly| weekly.reset_at);
+        let first_day = week_start_day(weekly_reset_at, crate::util::unix_now());
+        let value = weekly_value(config, &ledger, first_day);
+
+        let precision = config
+            .segments
+            .iter()
+            .find(|s| s.id == "value")
+            .and_then(|s| s.options.get("precision"))
+            .and_then(serde_json::Value::as_u64)
+            .and_then(|p| usize::try_from(p).ok())
+            .map_or(2, |p| p.min(6));
+
+        let (primary, secondary) = format_value(config, value, precision);
+
+        Some(
+            SegmentData::new(primary)
+                .with_secondary(secondary)
+                .with_metadata("value", format!("{value:.4}")),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transcript::TokenTotals;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_week_start_day_from_reset() {
+        let now = 100 * 86_400 + 3600;
+        let reset_at = 103 * 86_400;
+        assert_eq!(week_start_day(Some(reset_at), now), 96);
+    }
+
+    #[test]
+    fn test_week_start_day_fallback() {
+        let now = 100 * 86_400 + 3600;
+        assert_eq!(week_start_day(None, now), 94);
+        assert_eq!(week_start_day(Some(now - 10), now), 94);
+    }
+
+    #[test]
+    fn test_format_value_without_plan_price() {
+        let config = Config::default();
+        let (primary, secondary) = format_value(&config, 12.345, 2);
+        assert_eq!(primary, "$12.35");
+        assert!(secondary.is_empty());
+    }
+
+    #[test]
+    fn test_format_value_with_plan_price() {
+        let mut config = Config::default();
+        config.pricing.plan_price = 30.0;
+        config.pricing.currency_symbol = "¥".to_string();
+        let (primary, secondary) = format_value(&config, 14.0, 0);
+        assert_eq!(primary, "¥14");
+        assert_eq!(secondary, "2.0x plan");
+    }
+
+    #[test]
+    fn test_weekly_value_only_counts_window() {
+        let mut ledger = DailyLedger::default();
+        let tokens = TokenTotals {
+            input: 1_000_000,
+            ..TokenTotals::default()
+        };
+        ledger.add(90, &BTreeMap::from([("glm-5.1".to_string(), tokens)]));
+        ledger.add(95, &BTreeMap::from([("glm-5.1".to_string(), tokens)]));
+
+        let value = weekly_value(&Config::default(), &ledger, 94);
+        assert!((value - 1.0).abs() < 1e-9);
+    }
+}
@@ -5,19 +5,40 @@
 
 use super::{Segment, SegmentData};
 use crate::api::SharedCache;
-use crate::config::{Config, InputData};
+use crate::config::{Config, InputData, QuotaDisplay};
 
 super::segment_with_cache!(WeeklyUsageSegment);
 
 /// Collects weekly usage data for display in the status line.
 impl Segment for WeeklyUsageSegment {
     fn collect(&self, _input: &InputData, config: &Config) -> Option<SegmentData> {
-        let stats = super::fetch_usage(config, &self.cache)?;
+        let Some(stats) = super::fetch_usage(config, &self.cache) else {
+            return super::unavailable(config, "weekly_usage");
+        };
 
         let weekly = stats.weekly_usage.as_ref()?;
 
-        let primary = format!("{}%", weekly.percentage);
+        let segment_config = config.segments.iter().find(|s| s.id == "weekly_usage");
+        let primary = super::format_quota(
+            weekly,
+            segment_config,
+            QuotaDisplay::Percent,
+            config.style.resolved_locale(),
+        );
 
-        Some(SegmentData::new(primary).with_metadata("percentage", weekly.percentage))
+        let locale = config.style.resolved_locale();
+        let (_, timer_mode) = super::token_usage::resolve_timer_mode(segment_config);
+        let formatted_time =
+            super::token_usage::format_reset_time(weekly.reset_at, timer_mode, locale);
+
+        let mut out = SegmentData::new(primary)
+            .with_metadata("percentage", weekly.percentage)
+            .with_quota_amounts(weekly, segment_config, locale)
+            .with_metadata("reset", formatted_time);
+        if let Some(budget) = config.budget.weekly {
+            out = out.with_metadata("budget", budget);
+        }
+
+        Some(out.with_cache_state(&self.cache))
     }
 }
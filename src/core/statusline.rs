@@ -1,10 +1,23 @@
 //! Status line string generation with ANSI colors.
 //!
 //! This module generates the final status line string by combining
-//! segment data with colors and separators.
+//! segment data with colors and separators. Colors are downgraded to the
+//! resolved `style.color_depth`, and omitted entirely when it is `none`.
+//! With `style.powerline`, segments are drawn on colored backgrounds and
+//! joined by arrows instead of `style.separator`. When the line is wider
+//! than `style.max_width`, the lowest-priority segments are compacted and
+//! then dropped until it fits. Segments can be split into several rows
+//! with their `line`, each row joined by its own separator.
 
-use crate::config::{Config, DisplayMode, SegmentConfig};
+use crate::config::{
+    Color, ColorConfig, ColorDepth, Config, DisplayMode, FormatPiece, FormatTemplate, IconVariants,
+    Placeholder, SegmentConfig,
+};
+use crate::core::bar;
 use crate::core::segments::SegmentData;
+use crate::core::width::display_width;
+use std::collections::BTreeMap;
+use std::fmt::Write;
 
 /// Generates the colored status line string from segment data.
 #[derive(Default, Debug)]
@@ -19,13 +32,24 @@ impl StatusLineGenerator {
     }
 }
 
-const WHITE: &str = "\x1b[37m";
-const GRAY: &str = "\x1b[38;5;109m";
-const RED: &str = "\x1b[38;5;196m";
-const GREEN: &str = "\x1b[38;5;46m";
-const YELLOW: &str = "\x1b[38;5;226m";
 const RESET: &str = "\x1b[0m";
-const DOT_SEP_WITH_COLORS: &str = "\x1b[37m · \x1b[0m";
+const DOT_SEP: &str = " · ";
+
+/// Powerline arrow drawn between segments (requires a powerline or Nerd Font).
+const POWERLINE_ARROW: &str = "\u{e0b0}";
+
+/// Text color on powerline segment backgrounds.
+const POWERLINE_TEXT: Color = Color::Named(30);
+
+/// Columns a powerline segment adds around its text: two spaces and an arrow.
+const POWERLINE_PADDING: usize = 3;
+
+/// Built-in colors, used when neither the segment nor `[style.colors]` sets one.
+const DEFAULT_GREEN: &str = "46";
+const DEFAULT_YELLOW: &str = "226";
+const DEFAULT_RED: &str = "196";
+const DEFAULT_GRAY: &str = "109";
+const DEFAULT_WHITE: &str = "white";
 
 /// Threshold for warning color (yellow) - green below this percentage.
 ///
@@ -39,15 +63,286 @@ const PERCENTAGE_WARNING_THRESHOLD: u8 = 80;
 /// Changing this affects when the status line turns red.
 const PERCENTAGE_CRITICAL_THRESHOLD: u8 = 90;
 
-/// Returns the ANSI color code for a percentage value.
+/// Built-in primary colors: green for 0-80%, yellow for 81-90%, red for 91%+.
+const DEFAULT_THRESHOLDS: [(u8, &str); 3] = [
+    (0, DEFAULT_GREEN),
+    (PERCENTAGE_WARNING_THRESHOLD + 1, DEFAULT_YELLOW),
+    (PERCENTAGE_CRITICAL_THRESHOLD + 1, DEFAULT_RED),
+];
+
+/// Severity of a usage percentage, split at the built-in color thresholds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UsageLevel {
+    /// Up to 80%, green by default.
+    Ok,
+    /// 81-90%, yellow by default.
+    Warning,
+    /// Above 90%, red by default.
+    Critical,
+}
+
+impl UsageLevel {
+    /// Returns the level of a usage percentage.
+    #[must_use]
+    pub fn from_percentage(percentage: u8) -> Self {
+        if percentage > PERCENTAGE_CRITICAL_THRESHOLD {
+            UsageLevel::Critical
+        } else if percentage > PERCENTAGE_WARNING_THRESHOLD {
+            UsageLevel::Warning
+        } else {
+            UsageLevel::Ok
+        }
+    }
+
+    /// Returns the lowercase name, e.g. `"warning"`.
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            UsageLevel::Ok => "ok",
+            UsageLevel::Warning => "warning",
+            UsageLevel::Critical => "critical",
+        }
+    }
+}
+
+/// Resolves `color` at `depth`, falling back to `fallback` if it is invalid.
 ///
-/// Green for 0-80%, yellow for 81-90%, red for 91%+.
-/// Values exceeding 100% are treated as critical (red).
-fn get_color_for_percentage(percentage: u8) -> &'static str {
-    match percentage {
-        0..=PERCENTAGE_WARNING_THRESHOLD => GREEN,
-        81..=PERCENTAGE_CRITICAL_THRESHOLD => YELLOW,
-        _ => RED,
+/// Returns `None` when colors are disabled.
+fn resolve(color: Option<&str>, fallback: &str, depth: ColorDepth) -> Option<Color> {
+    color
+        .and_then(|c| Color::parse(c).ok())
+        .or_else(|| Color::parse(fallback).ok())
+        .and_then(|c| c.downgrade(depth))
+}
+
+/// Wraps `text` in `color`, leaving it bare when colors are disabled.
+fn paint(color: Option<Color>, text: &str) -> String {
+    match color {
+        Some(color) => format!("{}{text}{RESET}", color.fg()),
+        None => text.to_string(),
+    }
+}
+
+/// A run of segment text in one color.
+type Span = (Option<Color>, String);
+
+/// A segment ready to be joined into the status line.
+struct RenderedSegment {
+    /// Primary color, used as the background in powerline style.
+    primary: Option<Color>,
+    /// Colored text runs in display order.
+    spans: Vec<Span>,
+}
+
+impl RenderedSegment {
+    fn text(&self) -> String {
+        self.spans.iter().map(|(_, text)| text.as_str()).collect()
+    }
+
+    fn width(&self) -> usize {
+        self.spans.iter().map(|(_, text)| display_width(text)).sum()
+    }
+
+    fn painted(&self) -> String {
+        self.spans
+            .iter()
+            .map(|(color, text)| paint(*color, text))
+            .collect()
+    }
+}
+
+/// Returns the color of the highest stop whose `from` is at most `value`.
+fn pick_stop<T: PartialOrd + Copy>(stops: &[(T, &str)], value: T) -> Option<String> {
+    stops
+        .iter()
+        .filter(|(from, _)| *from <= value)
+        .max_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(_, color)| (*color).to_string())
+}
+
+/// Colors for one segment, resolved from the segment's `[segments.colors]`,
+/// then `[style.colors]`, then the built-in palette.
+struct Palette<'a> {
+    segment: &'a ColorConfig,
+    global: &'a ColorConfig,
+    depth: ColorDepth,
+}
+
+impl<'a> Palette<'a> {
+    fn new(segment: &'a ColorConfig, global: &'a ColorConfig, depth: ColorDepth) -> Self {
+        Self {
+            segment,
+            global,
+            depth,
+        }
+    }
+
+    /// Returns the first color set for a single-valued field.
+    fn single(&self, field: impl Fn(&ColorConfig) -> &Option<String>) -> Option<&str> {
+        field(self.segment)
+            .as_deref()
+            .or_else(|| field(self.global).as_deref())
+    }
+
+    /// Returns the primary color for a usage percentage, or the neutral
+    /// color when the segment has no percentage.
+    fn primary(&self, percentage: Option<u8>) -> Option<Color> {
+        let neutral = || resolve(self.single(|c| &c.neutral), DEFAULT_GRAY, self.depth);
+        let Some(percentage) = percentage else {
+            return neutral();
+        };
+        let configured = if self.segment.thresholds.is_empty() {
+            &self.global.thresholds
+        } else {
+            &self.segment.thresholds
+        };
+        let picked = if configured.is_empty() {
+            pick_stop(&DEFAULT_THRESHOLDS, percentage)
+        } else {
+            let stops: Vec<(u8, &str)> = configured
+                .iter()
+                .map(|t| (t.from, t.color.as_str()))
+                .collect();
+            pick_stop(&stops, percentage)
+        };
+        match picked {
+            Some(color) => resolve(Some(&color), DEFAULT_RED, self.depth),
+            None => neutral(),
+        }
+    }
+
+    /// Returns the color for a multiplier value such as `2.5`.
+    fn multiplier(&self, value: f64) -> Option<Color> {
+        let configured = if self.segment.multiplier.is_empty() {
+            &self.global.multiplier
+        } else {
+            &self.segment.multiplier
+        };
+        let stops: Vec<(f64, &str)> = configured
+            .iter()
+            .map(|m| (m.from, m.color.as_str()))
+            .collect();
+        resolve(pick_stop(&stops, value).as_deref(), DEFAULT_RED, self.depth)
+    }
+
+    fn secondary(&self) -> Option<Color> {
+        resolve(self.single(|c| &c.secondary), DEFAULT_GRAY, self.depth)
+    }
+
+    fn separator(&self) -> Option<Color> {
+        resolve(self.single(|c| &c.separator), DEFAULT_WHITE, self.depth)
+    }
+
+    /// Returns the color of the spike marker, which is always red.
+    fn spike(&self) -> Option<Color> {
+        resolve(None, DEFAULT_RED, self.depth)
+    }
+}
+
+/// A segment's full and compact renderings while fitting the line width.
+struct LayoutSlot {
+    priority: u8,
+    full: RenderedSegment,
+    compact: RenderedSegment,
+    /// How far the segment was shrunk: 0 full, 1 compact, 2 dropped.
+    level: u8,
+}
+
+impl LayoutSlot {
+    fn current(&self) -> Option<&RenderedSegment> {
+        match self.level {
+            0 => Some(&self.full),
+            // A compact form that renders empty keeps the full form
+            1 if self.compact.text().is_empty() => Some(&self.full),
+            1 => Some(&self.compact),
+            _ => None,
+        }
+    }
+}
+
+/// Shrinks segments until the joined line is at most `max_width` columns.
+///
+/// Segments are compacted from the lowest priority up, and from the right
+/// among equal priorities; if the line is still too wide they are dropped
+/// in the same order. The last visible segment is never dropped.
+fn fit_width(
+    slots: &mut [LayoutSlot],
+    max_width: usize,
+    line_width: impl Fn(&[&RenderedSegment]) -> usize,
+) {
+    let mut order: Vec<usize> = (0..slots.len()).collect();
+    order.sort_by_key(|i| (slots[*i].priority, std::cmp::Reverse(*i)));
+
+    let fits = |slots: &[LayoutSlot]| {
+        let visible: Vec<&RenderedSegment> = slots.iter().filter_map(LayoutSlot::current).collect();
+        line_width(&visible) <= max_width
+    };
+    for level in 1..=2 {
+        for &i in &order {
+            if fits(slots) {
+                return;
+            }
+            let visible = slots.iter().filter(|s| s.current().is_some()).count();
+            if level == 2 && visible <= 1 {
+                return;
+            }
+            slots[i].level = level;
+        }
+    }
+}
+
+/// Reads the segment's `bar_width` option.
+fn bar_width(seg_config: &SegmentConfig) -> u8 {
+    seg_config
+        .options
+        .get("bar_width")
+        .and_then(serde_json::Value::as_u64)
+        .and_then(|w| u8::try_from(w).ok())
+        .unwrap_or(bar::DEFAULT_BAR_WIDTH)
+}
+
+/// Renders the usage bar of a segment, if it has a percentage.
+fn segment_bar(config: &Config, seg_config: &SegmentConfig, data: &SegmentData) -> Option<String> {
+    let percentage = data.metadata.get("percentage")?.parse::<u8>().ok()?;
+    Some(bar::render(
+        percentage,
+        bar_width(seg_config),
+        config.style.resolved_mode(),
+    ))
+}
+
+/// Prefixes `text` with `icon` and a space, if there is an icon.
+fn with_icon(icon: &str, text: String) -> String {
+    if icon.is_empty() {
+        text
+    } else {
+        format!("{icon} {text}")
+    }
+}
+
+/// Returns the segment's usage percentage, relative to its budget if it has one.
+fn segment_percentage(data: &SegmentData) -> Option<u8> {
+    let budget = data
+        .metadata
+        .get("budget")
+        .and_then(|s| s.parse::<u8>().ok());
+    data.metadata
+        .get("percentage")
+        .and_then(|s| s.parse::<u8>().ok())
+        .map(|pct| budget_relative(pct, budget))
+}
+
+/// Scales a usage percentage to the share of a personal budget used.
+///
+/// With a budget of 70%, using 63% of the quota is 90% of the budget, so
+/// the usual color thresholds apply against the budget instead of the plan.
+fn budget_relative(percentage: u8, budget: Option<u8>) -> u8 {
+    match budget.filter(|b| *b > 0) {
+        Some(budget) => {
+            let scaled = u16::from(percentage) * 100 / u16::from(budget);
+            u8::try_from(scaled).unwrap_or(u8::MAX)
+        }
+        None => percentage,
     }
 }
 
@@ -55,91 +350,329 @@ impl StatusLineGenerator {
     /// Generate a status line string from the given segments.
     ///
     /// Segments are rendered with icons, colors, and separators according to the config.
+    /// Each segment's `line` picks the row it is shown on; rows are joined by
+    /// newlines, and empty rows are skipped. Every row is fitted to
+    /// `style.max_width` (or the detected terminal width) on its own by
+    /// compacting and dropping low-priority segments.
     #[must_use]
     pub fn generate(config: &Config, segments: &[(SegmentConfig, SegmentData)]) -> String {
-        let mut output = Vec::new();
-
-        for (seg_config, data) in segments {
-            let rendered = Self::render_segment(config, seg_config, data);
-            if !rendered.is_empty() {
-                output.push(rendered);
-            }
+        let mut rows: BTreeMap<u8, Vec<&(SegmentConfig, SegmentData)>> = BTreeMap::new();
+        for segment in segments {
+            rows.entry(segment.0.line).or_default().push(segment);
         }
+        rows.into_iter()
+            .map(|(line, row)| Self::generate_row(config, line, &row))
+            .filter(|row| !row.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
 
-        if output.is_empty() {
+    /// Generates status line row `line` from its segments.
+    fn generate_row(
+        config: &Config,
+        line: u8,
+        segments: &[&(SegmentConfig, SegmentData)],
+    ) -> String {
+        let mut slots: Vec<LayoutSlot> = segments
+            .iter()
+            .map(|(seg_config, data)| LayoutSlot {
+                priority: seg_config.priority,
+                full: Self::render_segment(config, seg_config, data, false),
+                compact: Self::render_segment(config, seg_config, data, true),
+                level: 0,
+            })
+            .filter(|slot| !slot.full.text().is_empty())
+            .collect();
+
+        if slots.is_empty() {
             return String::new();
         }
 
-        let separator = Self::format_separator(config);
-        output.join(&separator)
+        let colored = config.style.resolved_color_depth() != ColorDepth::None;
+        let powerline = config.style.powerline && colored;
+        let separator = config.style.line_separator(line);
+        if let Some(max_width) = config.style.resolved_max_width() {
+            let separator_width = display_width(separator);
+            fit_width(&mut slots, max_width, |visible| {
+                let text: usize = visible.iter().map(|s| s.width()).sum();
+                if powerline {
+                    text + POWERLINE_PADDING * visible.len()
+                } else {
+                    text + separator_width * visible.len().saturating_sub(1)
+                }
+            });
+        }
+        let rendered: Vec<&RenderedSegment> =
+            slots.iter().filter_map(LayoutSlot::current).collect();
+
+        if powerline {
+            return Self::join_powerline(&rendered);
+        }
+
+        let separator = Self::format_separator(config, separator);
+        rendered
+            .iter()
+            .map(|segment| segment.painted())
+            .collect::<Vec<_>>()
+            .join(&separator)
+    }
+
+    /// Returns the primary color of a segment at the configured color depth.
+    #[must_use]
+    pub fn segment_color(
+        config: &Config,
+        seg_config: &SegmentConfig,
+        data: &SegmentData,
+    ) -> Option<Color> {
+        let depth = config.style.resolved_color_depth();
+        Palette::new(&seg_config.colors, &config.style.colors, depth)
+            .primary(segment_percentage(data))
     }
 
     /// Renders a single segment with icon, colors, and text formatting.
-    fn render_segment(config: &Config, seg_config: &SegmentConfig, data: &SegmentData) -> String {
-        let icon = Self::get_icon(config, seg_config);
-        let pct_color = data
-            .metadata
-            .get("percentage")
-            .and_then(|s| s.parse::<u8>().ok())
-            .map_or(GRAY, get_color_for_percentage);
+    ///
+    /// The compact form uses `compact_format`, or only the icon and primary text.
+    fn render_segment(
+        config: &Config,
+        seg_config: &SegmentConfig,
+        data: &SegmentData,
+        compact: bool,
+    ) -> RenderedSegment {
+        let icon = Self::get_icon(config, seg_config, data);
+        let palette = Palette::new(
+            &seg_config.colors,
+            &config.style.colors,
+            config.style.resolved_color_depth(),
+        );
+        let pct_color = palette.primary(segment_percentage(data));
 
-        // Primary block: icon + primary text in percentage color
-        let primary_block = if icon.is_empty() {
-            format!("{}{}{}", pct_color, data.primary, RESET)
+        let format = if compact {
+            &seg_config.compact_format
         } else {
-            format!("{}{} {}{}", pct_color, icon, data.primary, RESET)
+            &seg_config.format
         };
+        // Invalid templates are reported by `check`
+        let template = format.as_ref().and_then(|f| f.template().ok());
+        let spans = match template {
+            Some(template) => Self::template_spans(
+                config, seg_config, template, &icon, data, &palette, pct_color,
+            ),
+            None if compact => vec![(pct_color, with_icon(&icon, data.primary.clone()))],
+            None => Self::default_spans(config, seg_config, &icon, data, &palette, pct_color),
+        };
+        RenderedSegment {
+            primary: pct_color,
+            spans,
+        }
+    }
 
-        // Multiplier block (red, only when present)
-        let multiplier_block = data
-            .multiplier
-            .as_deref()
-            .filter(|m| !m.is_empty())
-            .map(|m| format!("{RED}{m}{RESET}"));
+    /// Lays out a segment as `icon primary [· spike] [· multiplier] [· secondary]`.
+    fn default_spans(
+        config: &Config,
+        seg_config: &SegmentConfig,
+        icon: &str,
+        data: &SegmentData,
+        palette: &Palette,
+        pct_color: Option<Color>,
+    ) -> Vec<Span> {
+        // A bar replaces the primary text when the segment asks for one
+        let show_bar = seg_config
+            .options
+            .get("show_bar")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false);
+        let primary = show_bar
+            .then(|| segment_bar(config, seg_config, data))
+            .flatten()
+            .unwrap_or_else(|| data.primary.clone());
+
+        // Primary block: icon + primary text in percentage color
+        let mut parts = vec![(pct_color, with_icon(icon, primary))];
+
+        // Spike marker block (red, only while usage is spiking)
+        if let Some(marker) = data.metadata.get("spike") {
+            parts.push((palette.spike(), marker.clone()));
+        }
 
-        // Secondary block (gray)
-        let secondary_block =
-            (!data.secondary.is_empty()).then(|| format!("{}{}{}", GRAY, data.secondary, RESET));
+        // Multiplier block (colored by value, only when present)
+        if let Some(m) = data.multiplier.as_deref().filter(|m| !m.is_empty()) {
+            let value = m.trim_end_matches('x').parse::<f64>().unwrap_or(0.0);
+            parts.push((palette.multiplier(value), m.to_string()));
+        }
+
+        // Secondary block
+        if !data.secondary.is_empty() {
+            parts.push((palette.secondary(), data.secondary.clone()));
+        }
 
-        // Assemble: primary [· multiplier] [· secondary]
-        let dot_sep = DOT_SEP_WITH_COLORS;
-        let mut parts = vec![primary_block];
-        if let Some(m) = multiplier_block {
-            parts.push(m);
+        let dot_sep = (palette.separator(), DOT_SEP.to_string());
+        let mut spans = Vec::with_capacity(parts.len() * 2);
+        for (i, part) in parts.into_iter().enumerate() {
+            if i > 0 {
+                spans.push(dot_sep.clone());
+            }
+            spans.push(part);
         }
-        if let Some(s) = secondary_block {
-            parts.push(s);
+        spans
+    }
+
+    /// Lays out a segment from its `format` template.
+    ///
+    /// Placeholders without a value render empty. Multiplier, secondary, and
+    /// spike values keep their own colors; everything else uses the primary color.
+    fn template_spans(
+        config: &Config,
+        seg_config: &SegmentConfig,
+        template: &FormatTemplate,
+        icon: &str,
+        data: &SegmentData,
+        palette: &Palette,
+        pct_color: Option<Color>,
+    ) -> Vec<Span> {
+        let meta = |key: &str| data.metadata.get(key).cloned().unwrap_or_default();
+        // Prefer amounts formatted like the segment's primary text
+        let amount = |key: &str| {
+            data.metadata
+                .get(&format!("{key}_display"))
+                .or_else(|| data.metadata.get(key))
+                .cloned()
+                .unwrap_or_default()
+        };
+        let mut spans: Vec<Span> = Vec::new();
+        for piece in template.pieces() {
+            let (color, text) = match piece {
+                FormatPiece::Literal(text) => (pct_color, text.clone()),
+                FormatPiece::Placeholder(placeholder) => match placeholder {
+                    Placeholder::Icon => (pct_color, icon.to_string()),
+                    Placeholder::Percent => (pct_color, meta("percentage")),
+                    Placeholder::Bar => (
+                        pct_color,
+                        segment_bar(config, seg_config, data).unwrap_or_default(),
+                    ),
+                    Placeholder::Used => (pct_color, amount("used")),
+                    Placeholder::Limit => (pct_color, amount("limit")),
+                    Placeholder::Reset => (palette.secondary(), meta("reset")),
+                    Placeholder::Multiplier => {
+                        let m = data.multiplier.clone().unwrap_or_default();
+                        let value = m.trim_end_matches('x').parse::<f64>().unwrap_or(0.0);
+                        (palette.multiplier(value), m)
+                    }
+                    Placeholder::Primary => (pct_color, data.primary.clone()),
+                    Placeholder::Secondary => (palette.secondary(), data.secondary.clone()),
+                    Placeholder::Spike => (palette.spike(), meta("spike")),
+                },
+            };
+            match spans.last_mut() {
+                Some((last_color, last_text)) if *last_color == color => last_text.push_str(&text),
+                _ if text.is_empty() => {}
+                _ => spans.push((color, text)),
+            }
         }
 
-        parts.join(dot_sep)
+        // Drop whitespace left around placeholders that rendered empty
+        if let Some((_, first)) = spans.first_mut() {
+            *first = first.trim_start().to_string();
+        }
+        if let Some((_, last)) = spans.last_mut() {
+            *last = last.trim_end().to_string();
+        }
+        spans.retain(|(_, text)| !text.is_empty());
+        spans
     }
 
     /// Returns the icon string based on the display mode and segment config.
-    fn get_icon(config: &Config, seg_config: &SegmentConfig) -> String {
-        match config.style.resolved_mode() {
+    ///
+    /// The stale or error icon is used in those states, then the icon level
+    /// of the usage percentage; states and levels without an icon for the
+    /// display mode use the segment's icon. Nerd Font mode falls back to the
+    /// built-in glyph of the segment, then to the emoji, when the config sets
+    /// no glyph.
+    fn get_icon(config: &Config, seg_config: &SegmentConfig, data: &SegmentData) -> String {
+        let mode = config.style.resolved_mode();
+        let icon = &seg_config.icon;
+        let for_mode = |variants: &IconVariants| {
+            let text = match mode {
+                DisplayMode::Emoji => &variants.emoji,
+                DisplayMode::Ascii => &variants.ascii,
+                DisplayMode::NerdFont => &variants.nerd_font,
+                DisplayMode::Auto => unreachable!("resolved_mode() never returns Auto"),
+            };
+            Some(text.clone()).filter(|text| !text.is_empty())
+        };
+        let state = match data.metadata.get("state").map(String::as_str) {
+            Some("stale") => icon.stale.as_ref(),
+            Some("error") => icon.error.as_ref(),
+            _ => None,
+        };
+        let level = segment_percentage(data).and_then(|pct| icon.level(pct));
+        if let Some(text) = state
+            .and_then(for_mode)
+            .or_else(|| level.and_then(for_mode))
+        {
+            return text;
+        }
+
+        match mode {
             DisplayMode::Emoji => seg_config.icon.emoji.clone(),
             DisplayMode::Ascii => seg_config.icon.ascii.clone(),
+            DisplayMode::NerdFont => Some(seg_config.icon.nerd_font.clone())
+                .filter(|glyph| !glyph.is_empty())
+                .or_else(|| SegmentConfig::builtin(&seg_config.id).map(|s| s.icon.nerd_font))
+                .filter(|glyph| !glyph.is_empty())
+                .unwrap_or_else(|| seg_config.icon.emoji.clone()),
             DisplayMode::Auto => unreachable!("resolved_mode() never returns Auto"),
         }
     }
 
     /// Returns the formatted separator string with ANSI colors.
-    fn format_separator(config: &Config) -> String {
-        format!("{}{}{}{}", RESET, WHITE, config.style.separator, RESET)
+    fn format_separator(config: &Config, separator: &str) -> String {
+        let depth = config.style.resolved_color_depth();
+        match Palette::new(&ColorConfig::default(), &config.style.colors, depth).separator() {
+            Some(color) => format!("{RESET}{}{separator}{RESET}", color.fg()),
+            None => separator.to_string(),
+        }
+    }
+
+    /// Joins segments powerline style: each segment's text sits on its
+    /// primary color, and arrows blend one background into the next.
+    fn join_powerline(segments: &[&RenderedSegment]) -> String {
+        let mut output = String::new();
+        for (i, segment) in segments.iter().enumerate() {
+            let bg = segment.primary.map(Color::bg).unwrap_or_default();
+            write!(output, "{bg}{} {} ", POWERLINE_TEXT.fg(), segment.text()).unwrap();
+            let arrow_fg = segment.primary.map(Color::fg).unwrap_or_default();
+            let next_bg = segments
+                .get(i + 1)
+                .and_then(|next| next.primary)
+                .map(Color::bg)
+                .unwrap_or_default();
+            write!(output, "{RESET}{arrow_fg}{next_bg}{POWERLINE_ARROW}").unwrap();
+        }
+        output.push_str(RESET);
+        output
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::config::{IconConfig, StyleConfig};
+    use crate::config::{IconConfig, Locale, SegmentFormat, StyleConfig};
     use std::collections::HashMap;
 
+    const RED: &str = "\x1b[38;5;196m";
+
     fn test_config() -> Config {
         Config {
             style: StyleConfig {
                 mode: DisplayMode::Ascii,
+                theme: None,
                 separator: crate::config::DEFAULT_SEPARATOR.to_string(),
+                line_separators: Vec::new(),
+                color_depth: ColorDepth::Ansi256,
+                powerline: false,
+                locale: Locale::En,
+                max_width: Some(0),
+                colors: ColorConfig::default(),
             },
             ..Config::default()
         }
@@ -151,6 +684,12 @@ mod tests {
             enabled: true,
             icon: IconConfig::new("$", "$"),
             options: HashMap::new(),
+            colors: ColorConfig::default(),
+            format: None,
+            compact_format: None,
+            line: 1,
+            priority: 50,
+            show_when: crate::config::ShowWhen::default(),
         }
     }
 
@@ -193,4 +732,510 @@ mod tests {
         let output = StatusLineGenerator::generate(&config, &[(seg_config, data)]);
         assert!(output.contains("3x"));
     }
+
+    #[test]
+    fn test_generate_with_spike_marker() {
+        let config = test_config();
+        let seg_config = test_segment_config("token_usage");
+        let data = SegmentData::new("50%")
+            .with_metadata("percentage", "50")
+            .with_metadata("spike", "^ 3.0%/m");
+        let output = StatusLineGenerator::generate(&config, &[(seg_config, data)]);
+        assert!(output.contains(&format!("{RED}^ 3.0%/m{RESET}")));
+    }
+
+    #[test]
+    fn test_usage_level_matches_color_thresholds() {
+        assert_eq!(UsageLevel::from_percentage(80), UsageLevel::Ok);
+        assert_eq!(UsageLevel::from_percentage(81), UsageLevel::Warning);
+        assert_eq!(UsageLevel::from_percentage(90), UsageLevel::Warning);
+        assert_eq!(UsageLevel::from_percentage(91).as_str(), "critical");
+    }
+
+    #[test]
+    fn test_budget_relative() {
+        assert_eq!(budget_relative(63, Some(70)), 90);
+        assert_eq!(budget_relative(63, None), 63);
+        assert_eq!(budget_relative(100, Some(20)), u8::MAX);
+    }
+
+    #[test]
+    fn test_generate_colors_against_budget() {
+        let config = test_config();
+        let seg_config = test_segment_config("weekly_usage");
+        let data = SegmentData::new("65%")
+            .with_metadata("percentage", "65")
+            .with_metadata("budget", "70");
+        let output = StatusLineGenerator::generate(&config, &[(seg_config, data)]);
+        assert!(output.starts_with(RED));
+    }
+
+    fn thresholds(stops: &[(u8, &str)]) -> Vec<crate::config::ColorThreshold> {
+        stops
+            .iter()
+            .map(|(from, color)| crate::config::ColorThreshold {
+                from: *from,
+                color: (*color).to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_palette_defaults_match_builtin_colors() {
+        let empty = ColorConfig::default();
+        let palette = Palette::new(&empty, &empty, ColorDepth::Ansi256);
+        assert_eq!(palette.primary(Some(80)), Some(Color::Indexed(46)));
+        assert_eq!(palette.primary(Some(85)), Some(Color::Indexed(226)));
+        assert_eq!(palette.primary(Some(91)), Some(Color::Indexed(196)));
+        assert_eq!(palette.primary(None), Some(Color::Indexed(109)));
+        assert_eq!(palette.separator(), Some(Color::Named(37)));
+        assert_eq!(palette.multiplier(3.0), Some(Color::Indexed(196)));
+    }
+
+    #[test]
+    fn test_palette_segment_overrides_global() {
+        let global = ColorConfig {
+            thresholds: thresholds(&[(0, "green"), (50, "red")]),
+            secondary: Some("#010203".to_string()),
+            ..ColorConfig::default()
+        };
+        let segment = ColorConfig {
+            thresholds: thresholds(&[(0, "blue"), (70, "magenta")]),
+            ..ColorConfig::default()
+        };
+        let palette = Palette::new(&segment, &global, ColorDepth::Truecolor);
+        assert_eq!(palette.primary(Some(60)), Some(Color::Named(34)));
+        assert_eq!(palette.primary(Some(75)), Some(Color::Named(35)));
+        assert_eq!(palette.secondary(), Some(Color::Rgb(1, 2, 3)));
+
+        let unset = ColorConfig::default();
+        let global_only = Palette::new(&unset, &global, ColorDepth::Ansi256);
+        assert_eq!(global_only.primary(Some(60)), Some(Color::Named(31)));
+    }
+
+    #[test]
+    fn test_palette_multiplier_by_value() {
+        let global = ColorConfig {
+            multiplier: vec![
+                crate::config::MultiplierColor {
+                    from: 0.0,
+                    color: "yellow".to_string(),
+                },
+                crate::config::MultiplierColor {
+                    from: 3.0,
+                    color: "red".to_string(),
+                },
+            ],
+            ..ColorConfig::default()
+        };
+        let segment = ColorConfig::default();
+        let palette = Palette::new(&segment, &global, ColorDepth::Ansi256);
+        assert_eq!(palette.multiplier(2.0), Some(Color::Named(33)));
+        assert_eq!(palette.multiplier(3.0), Some(Color::Named(31)));
+    }
+
+    #[test]
+    fn test_show_bar_replaces_primary() {
+        let config = test_config();
+        let mut seg_config = test_segment_config("weekly_usage");
+        seg_config
+            .options
+            .insert("show_bar".to_string(), serde_json::json!(true));
+        seg_config
+            .options
+            .insert("bar_width".to_string(), serde_json::json!(4));
+        let data = SegmentData::new("95%").with_metadata("percentage", "95");
+        let output = StatusLineGenerator::generate(&config, &[(seg_config, data)]);
+        assert_eq!(output, format!("{RED}$ [####]{RESET}"));
+    }
+
+    #[test]
+    fn test_show_bar_without_percentage_keeps_primary() {
+        let mut config = test_config();
+        config.style.color_depth = ColorDepth::None;
+        let mut seg_config = test_segment_config("cost");
+        seg_config
+            .options
+            .insert("show_bar".to_string(), serde_json::json!(true));
+        let data = SegmentData::new("$1.20");
+        let output = StatusLineGenerator::generate(&config, &[(seg_config, data)]);
+        assert_eq!(output, "$ $1.20");
+    }
+
+    fn token_data() -> SegmentData {
+        SegmentData::new("85%")
+            .with_secondary("@ 14:30")
+            .with_multiplier("3x")
+            .with_metadata("percentage", "85")
+            .with_metadata("used", "850")
+            .with_metadata("limit", "1000")
+            .with_metadata("reset", "14:30")
+    }
+
+    #[test]
+    fn test_format_template_placeholders() {
+        let mut config = test_config();
+        config.style.color_depth = ColorDepth::None;
+        let mut seg_config = test_segment_config("token_usage");
+        seg_config.format = Some(SegmentFormat::new(
+            "{icon} {percent}% {bar} {used}/{limit} {reset} {multiplier}",
+        ));
+        let output = StatusLineGenerator::generate(&config, &[(seg_config, token_data())]);
+        assert_eq!(output, "$ 85% [#########-] 850/1000 14:30 3x");
+    }
+
+    #[test]
+    fn test_format_template_uses_formatted_amounts() {
+        let mut config = test_config();
+        config.style.color_depth = ColorDepth::None;
+        let mut seg_config = test_segment_config("weekly_usage");
+        seg_config.format = Some(SegmentFormat::new("{icon} {used}/{limit}"));
+        let data = SegmentData::new("10%")
+            .with_metadata("used", "40000000")
+            .with_metadata("limit", "400000000")
+            .with_metadata("used_display", "40M")
+            .with_metadata("limit_display", "400M");
+        let output = StatusLineGenerator::generate(&config, &[(seg_config, data)]);
+        assert!(output.ends_with("40M/400M"), "{output}");
+    }
+
+    #[test]
+    fn test_format_template_colors() {
+        let config = test_config();
+        let mut seg_config = test_segment_config("token_usage");
+        seg_config.format = Some(SegmentFormat::new("{percent}% {multiplier} ({reset})"));
+        let output = StatusLineGenerator::generate(&config, &[(seg_config, token_data())]);
+        assert_eq!(
+            output,
+            "\x1b[38;5;226m85% \x1b[0m\x1b[38;5;196m3x\x1b[0m\x1b[38;5;226m (\x1b[0m\
+             \x1b[38;5;109m14:30\x1b[0m\x1b[38;5;226m)\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test_format_template_missing_values_render_empty() {
+        let mut config = test_config();
+        config.style.color_depth = ColorDepth::None;
+        let mut seg_config = test_segment_config("weekly_usage");
+        seg_config.format = Some(SegmentFormat::new("{percent}% {multiplier}"));
+        let data = SegmentData::new("30%").with_metadata("percentage", "30");
+        let output = StatusLineGenerator::generate(&config, &[(seg_config, data)]);
+        assert_eq!(output, "30%");
+    }
+
+    #[test]
+    fn test_invalid_format_uses_default_layout() {
+        let mut config = test_config();
+        config.style.color_depth = ColorDepth::None;
+        let mut seg_config = test_segment_config("weekly_usage");
+        seg_config.format = Some(SegmentFormat::new("{nope}"));
+        let data = SegmentData::new("30%").with_metadata("percentage", "30");
+        let output = StatusLineGenerator::generate(&config, &[(seg_config, data)]);
+        assert_eq!(output, "$ 30%");
+    }
+
+    #[test]
+    fn test_powerline_join() {
+        let mut config = test_config();
+        config.style.powerline = true;
+        let token = SegmentData::new("85%")
+            .with_multiplier("3x")
+            .with_metadata("percentage", "85");
+        let weekly = SegmentData::new("30%").with_metadata("percentage", "30");
+        let output = StatusLineGenerator::generate(
+            &config,
+            &[
+                (test_segment_config("token_usage"), token),
+                (test_segment_config("weekly_usage"), weekly),
+            ],
+        );
+        assert_eq!(
+            output,
+            "\x1b[48;5;226m\x1b[30m $ 85% · 3x \x1b[0m\x1b[38;5;226m\x1b[48;5;46m\u{e0b0}\
+             \x1b[48;5;46m\x1b[30m $ 30% \x1b[0m\x1b[38;5;46m\u{e0b0}\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test_powerline_without_colors_uses_separator() {
+        let mut config = test_config();
+        config.style.powerline = true;
+        config.style.color_depth = ColorDepth::None;
+        let output = StatusLineGenerator::generate(
+            &config,
+            &[
+                (test_segment_config("token_usage"), SegmentData::new("85%")),
+                (test_segment_config("mcp_usage"), SegmentData::new("3/10")),
+            ],
+        );
+        assert_eq!(output, "$ 85% | $ 3/10");
+    }
+
+    #[test]
+    fn test_nerd_font_icons() {
+        let mut config = test_config();
+        config.style.mode = DisplayMode::NerdFont;
+        config.style.color_depth = ColorDepth::None;
+        let mut custom = test_segment_config("weekly_usage");
+        custom.icon = IconConfig::new("x", "x").with_nerd_font("\u{f133}");
+        let output = StatusLineGenerator::generate(
+            &config,
+            &[
+                (test_segment_config("token_usage"), SegmentData::new("85%")),
+                (custom, SegmentData::new("30%")),
+                (test_segment_config("unknown"), SegmentData::new("1")),
+            ],
+        );
+        assert_eq!(output, "\u{f0d6} 85% | \u{f133} 30% | $ 1");
+    }
+
+    /// Icon config with an ASCII ladder and stale/error icons.
+    fn ladder_icon() -> IconConfig {
+        let variants = |ascii: &str| crate::config::IconVariants {
+            ascii: ascii.to_string(),
+            ..crate::config::IconVariants::default()
+        };
+        let level = |from: u8, ascii: &str| crate::config::IconLevel {
+            from,
+            icon: variants(ascii),
+        };
+        IconConfig {
+            levels: vec![level(75, "#"), level(0, "."), level(50, "=")],
+            stale: Some(variants("~")),
+            error: Some(variants("!")),
+            ..IconConfig::new("$", "$")
+        }
+    }
+
+    #[test]
+    fn test_icon_levels() {
+        let mut config = test_config();
+        config.style.color_depth = ColorDepth::None;
+        let mut seg_config = test_segment_config("token_usage");
+        seg_config.icon = ladder_icon();
+        let render = |pct: u8| {
+            let data = SegmentData::new(format!("{pct}%")).with_metadata("percentage", pct);
+            StatusLineGenerator::generate(&config, &[(seg_config.clone(), data)])
+        };
+        assert_eq!(render(10), ". 10%");
+        assert_eq!(render(50), "= 50%");
+        assert_eq!(render(99), "# 99%");
+        let no_percentage = SegmentData::new("1.2M");
+        assert_eq!(
+            StatusLineGenerator::generate(&config, &[(seg_config.clone(), no_percentage)]),
+            "$ 1.2M"
+        );
+    }
+
+    #[test]
+    fn test_state_icons() {
+        let mut config = test_config();
+        config.style.color_depth = ColorDepth::None;
+        let mut seg_config = test_segment_config("token_usage");
+        seg_config.icon = ladder_icon();
+        let stale = SegmentData::new("90%")
+            .with_metadata("percentage", 90)
+            .with_metadata("state", "stale");
+        let error = SegmentData::new("--").with_metadata("state", "error");
+        assert_eq!(
+            StatusLineGenerator::generate(&config, &[(seg_config.clone(), stale.clone())]),
+            "~ 90%"
+        );
+        assert_eq!(
+            StatusLineGenerator::generate(&config, &[(seg_config.clone(), error)]),
+            "! --"
+        );
+
+        // States without an icon for the display mode keep the level icon
+        config.style.mode = DisplayMode::Emoji;
+        seg_config.icon.levels[0].icon.emoji = "🔥".to_string();
+        assert_eq!(
+            StatusLineGenerator::generate(&config, &[(seg_config, stale)]),
+            "🔥 90%"
+        );
+    }
+
+    /// Renders a token segment with every color part at `depth`.
+    fn render_at(depth: ColorDepth) -> String {
+        let mut config = test_config();
+        config.style.color_depth = depth;
+        config.style.colors.secondary = Some("#ff8800".to_string());
+        let seg_config = test_segment_config("token_usage");
+        let data = SegmentData::new("85%")
+            .with_secondary("14:30")
+            .with_multiplier("3x")
+            .with_metadata("percentage", "85");
+        let mcp = SegmentData::new("20/100");
+        StatusLineGenerator::generate(
+            &config,
+            &[(seg_config, data), (test_segment_config("mcp_usage"), mcp)],
+        )
+    }
+
+    #[test]
+    fn test_snapshot_depth_none() {
+        assert_eq!(render_at(ColorDepth::None), "$ 85% · 3x · 14:30 | $ 20/100");
+    }
+
+    #[test]
+    fn test_snapshot_depth_16() {
+        assert_eq!(
+            render_at(ColorDepth::Ansi16),
+            "\x1b[93m$ 85%\x1b[0m\x1b[37m · \x1b[0m\x1b[91m3x\x1b[0m\x1b[37m · \x1b[0m\
+             \x1b[33m14:30\x1b[0m\x1b[0m\x1b[37m | \x1b[0m\x1b[90m$ 20/100\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test_snapshot_depth_256() {
+        assert_eq!(
+            render_at(ColorDepth::Ansi256),
+            "\x1b[38;5;226m$ 85%\x1b[0m\x1b[37m · \x1b[0m\x1b[38;5;196m3x\x1b[0m\x1b[37m · \x1b[0m\
+             \x1b[38;5;208m14:30\x1b[0m\x1b[0m\x1b[37m | \x1b[0m\x1b[38;5;109m$ 20/100\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn test_snapshot_depth_truecolor() {
+        assert_eq!(
+            render_at(ColorDepth::Truecolor),
+            "\x1b[38;5;226m$ 85%\x1b[0m\x1b[37m · \x1b[0m\x1b[38;5;196m3x\x1b[0m\x1b[37m · \x1b[0m\
+             \x1b[38;2;255;136;0m14:30\x1b[0m\x1b[0m\x1b[37m | \x1b[0m\x1b[38;5;109m$ 20/100\x1b[0m"
+        );
+    }
+
+    /// Token, weekly, and MCP segments as in the default status line.
+    fn wide_line() -> Vec<(SegmentConfig, SegmentData)> {
+        let mut weekly = test_segment_config("weekly_usage");
+        weekly.icon = IconConfig::new("\u{1f5d3}\u{fe0f}", "*");
+        vec![
+            (test_segment_config("token_usage"), token_data()),
+            (weekly, SegmentData::new("30%").with_secondary("Mon 09:00")),
+            (test_segment_config("mcp_usage"), SegmentData::new("20/100")),
+        ]
+    }
+
+    fn render_within(max_width: u16, mode: DisplayMode) -> String {
+        let mut config = test_config();
+        config.style.color_depth = ColorDepth::None;
+        config.style.mode = mode;
+        config.style.max_width = Some(max_width);
+        StatusLineGenerator::generate(&config, &wide_line())
+    }
+
+    #[test]
+    fn test_max_width_fits_unchanged() {
+        let full = "$ 85% · 3x · @ 14:30 | * 30% · Mon 09:00 | $ 20/100";
+        assert_eq!(render_within(51, DisplayMode::Ascii), full);
+        assert_eq!(render_within(0, DisplayMode::Ascii), full);
+    }
+
+    #[test]
+    fn test_max_width_compacts_then_drops_from_the_right() {
+        assert_eq!(
+            render_within(50, DisplayMode::Ascii),
+            "$ 85% · 3x · @ 14:30 | * 30% | $ 20/100"
+        );
+        assert_eq!(
+            render_within(30, DisplayMode::Ascii),
+            "$ 85% | * 30% | $ 20/100"
+        );
+        assert_eq!(render_within(20, DisplayMode::Ascii), "$ 85% | * 30%");
+        assert_eq!(render_within(10, DisplayMode::Ascii), "$ 85%");
+        // The last segment stays even when it does not fit
+        assert_eq!(render_within(3, DisplayMode::Ascii), "$ 85%");
+    }
+
+    #[test]
+    fn test_max_width_counts_emoji_columns() {
+        // The weekly icon is U+1F5D3 with a variation selector, 2 columns wide
+        let full = "$ 85% · 3x · @ 14:30 | \u{1f5d3}\u{fe0f} 30% · Mon 09:00 | $ 20/100";
+        assert_eq!(render_within(52, DisplayMode::Emoji), full);
+        assert_eq!(
+            render_within(51, DisplayMode::Emoji),
+            "$ 85% · 3x · @ 14:30 | \u{1f5d3}\u{fe0f} 30% | $ 20/100"
+        );
+    }
+
+    #[test]
+    fn test_max_width_keeps_high_priority_segments() {
+        let mut config = test_config();
+        config.style.color_depth = ColorDepth::None;
+        config.style.max_width = Some(30);
+        let mut segments = wide_line();
+        segments[0].0.priority = 10;
+        segments[2].0.priority = 90;
+        segments[2].0.compact_format = Some(SegmentFormat::new("{primary}"));
+        let output = StatusLineGenerator::generate(&config, &segments);
+        assert_eq!(output, "$ 85% | * 30% | $ 20/100");
+
+        config.style.max_width = Some(16);
+        let output = StatusLineGenerator::generate(&config, &segments);
+        assert_eq!(output, "* 30% | 20/100");
+    }
+
+    #[test]
+    fn test_max_width_powerline_padding() {
+        let mut config = test_config();
+        config.style.powerline = true;
+        config.style.max_width = Some(16);
+        let output = StatusLineGenerator::generate(
+            &config,
+            &[
+                (test_segment_config("token_usage"), SegmentData::new("85%")),
+                (test_segment_config("mcp_usage"), SegmentData::new("3/10")),
+            ],
+        );
+        // " $ 85% " + arrow + " $ 3/10 " + arrow is 17 columns
+        assert!(output.contains("$ 85%"), "{output:?}");
+        assert!(!output.contains("3/10"), "{output:?}");
+    }
+
+    #[test]
+    fn test_segments_split_into_lines() {
+        let mut config = test_config();
+        config.style.color_depth = ColorDepth::None;
+        config.style.line_separators = vec![" | ".to_string(), " · ".to_string()];
+        let mut segments = wide_line();
+        segments[0].0.line = 3;
+        segments[1].0.line = 3;
+        segments[2].0.line = 1;
+        let output = StatusLineGenerator::generate(&config, &segments);
+        assert_eq!(output, "$ 20/100\n$ 85% · 3x · @ 14:30 | * 30% · Mon 09:00");
+
+        segments[2].0.line = 2;
+        segments.push((test_segment_config("cost"), SegmentData::new("$1.20")));
+        segments[3].0.line = 2;
+        let output = StatusLineGenerator::generate(&config, &segments);
+        assert_eq!(
+            output.lines().collect::<Vec<_>>(),
+            [
+                "$ 20/100 · $ $1.20",
+                "$ 85% · 3x · @ 14:30 | * 30% · Mon 09:00"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lines_fit_max_width_separately() {
+        let mut config = test_config();
+        config.style.color_depth = ColorDepth::None;
+        config.style.max_width = Some(20);
+        let mut segments = wide_line();
+        segments[1].0.line = 2;
+        segments[2].0.line = 2;
+        let output = StatusLineGenerator::generate(&config, &segments);
+        assert_eq!(output, "$ 85% · 3x · @ 14:30\n* 30% | $ 20/100");
+    }
+
+    #[test]
+    fn test_palette_invalid_color_falls_back() {
+        let global = ColorConfig {
+            separator: Some("nope".to_string()),
+            ..ColorConfig::default()
+        };
+        let segment = ColorConfig::default();
+        let palette = Palette::new(&segment, &global, ColorDepth::Ansi256);
+        assert_eq!(palette.separator(), Some(Color::Named(37)));
+    }
 }
@@ -0,0 +1,188 @@
+//! Conditional segment visibility (`[segments.show_when]`).
+//!
+//! Conditions are checked after a segment collected its data: the usage
+//! percentage comes from the segment's metadata, the model from Claude
+//! Code's input, and peak hours and multipliers from `[multiplier]`.
+
+use super::segments::token_usage::{calculate_multiplier, is_peak_time, is_premium_model};
+use super::segments::SegmentData;
+use crate::config::{Config, InputData, ShowWhen};
+
+/// Returns `true` if a segment with `data` meets every `show_when` condition.
+///
+/// A percentage condition hides segments that report no percentage, and a
+/// peak hours condition hides the segment while the peak hours are invalid.
+#[must_use]
+pub fn should_show(
+    show_when: &ShowWhen,
+    data: &SegmentData,
+    input: &InputData,
+    config: &Config,
+) -> bool {
+    if let Some(min) = show_when.min_percentage {
+        let percentage = data
+            .metadata
+            .get("percentage")
+            .and_then(|s| s.parse::<u8>().ok());
+        if percentage.is_none_or(|p| p < min) {
+            return false;
+        }
+    }
+    if let Some(peak) = show_when.peak_hours {
+        let mc = &config.multiplier;
+        if is_peak_time(&mc.peak_start, &mc.peak_end) != Some(peak) {
+            return false;
+        }
+    }
+    if let Some(premium) = show_when.premium_model {
+        if is_premium_model(input, config) != premium {
+            return false;
+        }
+    }
+    if let Some(above) = show_when.multiplier_above {
+        if calculate_multiplier(input, config) <= above {
+            return false;
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input_with_model(id: &str) -> InputData {
+        serde_json::from_value(serde_json::json!({ "model": { "id": id } })).unwrap()
+    }
+
+    /// Config whose peak hours cover the whole day.
+    fn always_peak() -> Config {
+        let mut config = Config::default();
+        config.multiplier.peak_start = "00:00".to_string();
+        config.multiplier.peak_end = "23:59".to_string();
+        config
+    }
+
+    #[test]
+    fn test_no_conditions() {
+        let data = SegmentData::new("30/100");
+        assert!(should_show(
+            &ShowWhen::default(),
+            &data,
+            &InputData::default(),
+            &Config::default()
+        ));
+    }
+
+    #[test]
+    fn test_min_percentage() {
+        let show_when = ShowWhen {
+            min_percentage: Some(50),
+            ..ShowWhen::default()
+        };
+        let input = InputData::default();
+        let config = Config::default();
+        let high = SegmentData::new("50%").with_metadata("percentage", 50);
+        let low = SegmentData::new("49%").with_metadata("percentage", 49);
+        assert!(should_show(&show_when, &high, &input, &config));
+        assert!(!should_show(&show_when, &low, &input, &config));
+        assert!(!should_show(
+            &show_when,
+            &SegmentData::new("$1.20"),
+            &input,
+            &config
+        ));
+    }
+
+    #[test]
+    fn test_peak_hours() {
+        let data = SegmentData::new("50%");
+        let input = InputData::default();
+        let during = ShowWhen {
+            peak_hours: Some(true),
+            ..ShowWhen::default()
+        };
+        let outside = ShowWhen {
+            peak_hours: Some(false),
+            ..ShowWhen::default()
+        };
+        assert!(should_show(&during, &data, &input, &always_peak()));
+        assert!(!should_show(&outside, &data, &input, &always_peak()));
+
+        let mut invalid = Config::default();
+        invalid.multiplier.peak_start = "soon".to_string();
+        assert!(!should_show(&during, &data, &input, &invalid));
+        assert!(!should_show(&outside, &data, &input, &invalid));
+    }
+
+    #[test]
+    fn test_premium_model() {
+        let show_when = ShowWhen {
+            premium_model: Some(true),
+            ..ShowWhen::default()
+        };
+        let data = SegmentData::new("50%");
+        let config = Config::default();
+        assert!(should_show(
+            &show_when,
+            &data,
+            &input_with_model("GLM-5.1"),
+            &config
+        ));
+        assert!(!should_show(
+            &show_when,
+            &data,
+            &input_with_model("glm-4.7"),
+            &config
+        ));
+        assert!(!should_show(
+            &show_when,
+            &data,
+            &InputData::default(),
+            &config
+        ));
+    }
+
+    #[test]
+    fn test_multiplier_above() {
+        let show_when = ShowWhen {
+            multiplier_above: Some(1.0),
+            ..ShowWhen::default()
+        };
+        let data = SegmentData::new("50%");
+        let config = always_peak();
+        assert!(should_show(
+            &show_when,
+            &data,
+            &input_with_model("glm-5"),
+            &config
+        ));
+        assert!(!should_show(
+            &show_when,
+            &data,
+            &input_with_model("glm-4.7"),
+            &config
+        ));
+    }
+
+    #[test]
+    fn test_all_conditions_must_hold() {
+        let show_when = ShowWhen {
+            min_percentage: Some(80),
+            premium_model: Some(true),
+            ..ShowWhen::default()
+        };
+        let config = Config::default();
+        let input = input_with_model("glm-5");
+        let high = SegmentData::new("85%").with_metadata("percentage", 85);
+        let low = SegmentData::new("20%").with_metadata("percentage", 20);
+        assert!(should_show(&show_when, &high, &input, &config));
+        assert!(!should_show(&show_when, &low, &input, &config));
+        assert!(!should_show(
+            &show_when,
+            &high,
+            &input_with_model("glm-4.7"),
+            &config
+        ));
+    }
+}
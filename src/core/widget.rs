@@ -0,0 +1,227 @@
+//! Desktop bar output (`bar` command) for Waybar and i3bar.
+//!
+//! Waybar gets one JSON object with the status line as `text`, a quota
+//! breakdown as `tooltip`, and a `class` of ok/warning/critical from the
+//! highest quota percentage. i3bar gets one block per segment, colored
+//! like the status line; [`I3BAR_HEADER`] starts the stream.
+
+use crate::api::{QuotaUsage, UsageStats};
+use crate::config::{ColorDepth, Config, Locale, SegmentConfig};
+use crate::core::segments::SegmentData;
+use crate::core::statusline::{StatusLineGenerator, UsageLevel};
+use serde::Serialize;
+
+/// Header and opening bracket of the i3bar protocol stream.
+pub const I3BAR_HEADER: &str = "{\"version\":1}\n[";
+
+/// A Waybar custom module update (`return-type = "json"`).
+#[derive(Debug, Serialize)]
+struct WaybarOutput {
+    text: String,
+    tooltip: String,
+    class: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    percentage: Option<u8>,
+}
+
+/// An i3bar status block.
+#[derive(Debug, Serialize)]
+struct I3barBlock<'a> {
+    name: &'a str,
+    full_text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    color: Option<String>,
+}
+
+/// Returns `config` rendering plain text at any width.
+///
+/// Bars have no terminal, so only an explicit `max_width` shortens the text.
+fn plain_config(config: &Config) -> Config {
+    let mut plain = config.clone();
+    plain.style.color_depth = ColorDepth::None;
+    plain.style.max_width.get_or_insert(0);
+    plain
+}
+
+/// Returns one tooltip line for a quota, e.g. `"Weekly tokens: 50% (250000 / 500000)"`.
+fn quota_line(label: &str, quota: &QuotaUsage, locale: Locale) -> String {
+    let mut line = format!(
+        "{label}{}{}% ({} / {})",
+        locale.pick(": ", "："),
+        quota.percentage,
+        quota.used,
+        quota.limit
+    );
+    if let Some(reset) = quota.reset_at.and_then(crate::util::format_timestamp) {
+        line.push_str(locale.pick(", resets ", "，重置于 "));
+        line.push_str(&reset);
+    }
+    line
+}
+
+/// Serializes a Waybar update from the segments and usage statistics.
+#[must_use]
+pub fn generate_waybar(
+    config: &Config,
+    segments: &[(SegmentConfig, SegmentData)],
+    usage: Option<&UsageStats>,
+) -> String {
+    let locale = config.style.resolved_locale();
+    let quotas: Vec<(&str, &QuotaUsage)> = usage
+        .map(|u| {
+            [
+                (
+                    locale.pick("5-hour tokens", "5 小时 Tokens"),
+                    &u.token_usage,
+                ),
+                (locale.pick("Weekly tokens", "每周 Tokens"), &u.weekly_usage),
+                (locale.pick("MCP calls", "MCP 调用"), &u.mcp_usage),
+            ]
+            .into_iter()
+            .filter_map(|(label, quota)| Some((label, quota.as_ref()?)))
+            .collect()
+        })
+        .unwrap_or_default();
+    let percentage = quotas.iter().map(|(_, q)| q.percentage).max();
+    let tooltip = if quotas.is_empty() {
+        locale.pick("Usage unavailable", "用量不可用").to_string()
+    } else {
+        quotas
+            .iter()
+            .map(|(label, quota)| quota_line(label, quota, locale))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    let output = WaybarOutput {
+        text: StatusLineGenerator::generate(&plain_config(config), segments),
+        tooltip,
+        class: percentage.map_or("unavailable", |p| UsageLevel::from_percentage(p).as_str()),
+        percentage,
+    };
+    serde_json::to_string(&output).unwrap_or_default()
+}
+
+/// Serializes one i3bar status line: a JSON array with a block per segment.
+#[must_use]
+pub fn generate_i3bar(config: &Config, segments: &[(SegmentConfig, SegmentData)]) -> String {
+    let plain = plain_config(config);
+    let mut colored = config.clone();
+    if colored.style.resolved_color_depth() != ColorDepth::None {
+        colored.style.color_depth = ColorDepth::Truecolor;
+    }
+    let blocks: Vec<I3barBlock> = segments
+        .iter()
+        .enumerate()
+        .map(|(i, (seg_config, data))| I3barBlock {
+            name: &seg_config.id,
+            full_text: StatusLineGenerator::generate(&plain, &segments[i..=i]),
+            color: StatusLineGenerator::segment_color(&colored, seg_config, data)
+                .map(crate::config::Color::hex),
+        })
+        .filter(|block| !block.full_text.is_empty())
+        .collect();
+    serde_json::to_string(&blocks).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    fn quota(used: i64, limit: i64, percentage: u8) -> QuotaUsage {
+        QuotaUsage {
+            used,
+            limit,
+            percentage,
+            time_window: String::new(),
+            reset_at: None,
+        }
+    }
+
+    fn test_config() -> Config {
+        let mut config = Config::default();
+        config.style.mode = crate::config::DisplayMode::Ascii;
+        config.style.locale = Locale::En;
+        config.style.color_depth = ColorDepth::Ansi256;
+        config
+    }
+
+    fn segments() -> Vec<(SegmentConfig, SegmentData)> {
+        vec![
+            (
+                SegmentConfig::token_usage(),
+                SegmentData::new("85%").with_metadata("percentage", 85),
+            ),
+            (SegmentConfig::mcp_usage(), SegmentData::new("30/100")),
+        ]
+    }
+
+    #[test]
+    fn test_generate_waybar() {
+        let usage = UsageStats {
+            token_usage: Some(quota(850, 1000, 85)),
+            weekly_usage: Some(quota(50, 100, 50)),
+            mcp_usage: None,
+        };
+        let output: Value =
+            serde_json::from_str(&generate_waybar(&test_config(), &segments(), Some(&usage)))
+                .unwrap();
+        assert_eq!(
+            output,
+            json!({
+                "text": "$ 85% | # 30/100",
+                "tooltip": "5-hour tokens: 85% (850 / 1000)\nWeekly tokens: 50% (50 / 100)",
+                "class": "warning",
+                "percentage": 85
+            })
+        );
+    }
+
+    #[test]
+    fn test_generate_waybar_chinese_tooltip() {
+        let usage = UsageStats {
+            token_usage: Some(quota(850, 1000, 85)),
+            weekly_usage: None,
+            mcp_usage: Some(quota(30, 100, 30)),
+        };
+        let mut config = test_config();
+        config.style.locale = Locale::Zh;
+        let output: Value =
+            serde_json::from_str(&generate_waybar(&config, &segments(), Some(&usage))).unwrap();
+        assert_eq!(
+            output["tooltip"],
+            "5 小时 Tokens：85% (850 / 1000)\nMCP 调用：30% (30 / 100)"
+        );
+    }
+
+    #[test]
+    fn test_generate_waybar_without_usage() {
+        let output: Value =
+            serde_json::from_str(&generate_waybar(&test_config(), &[], None)).unwrap();
+        assert_eq!(
+            output,
+            json!({ "text": "", "tooltip": "Usage unavailable", "class": "unavailable" })
+        );
+    }
+
+    #[test]
+    fn test_generate_i3bar() {
+        let output: Value =
+            serde_json::from_str(&generate_i3bar(&test_config(), &segments())).unwrap();
+        assert_eq!(
+            output,
+            json!([
+                { "name": "token_usage", "full_text": "$ 85%", "color": "#ffff00" },
+                { "name": "mcp_usage", "full_text": "# 30/100", "color": "#87afaf" }
+            ])
+        );
+
+        let mut config = test_config();
+        config.style.color_depth = ColorDepth::None;
+        let output: Value = serde_json::from_str(&generate_i3bar(&config, &segments())).unwrap();
+        assert_eq!(
+            output[0],
+            json!({ "name": "token_usage", "full_text": "$ 85%" })
+        );
+    }
+}
@@ -0,0 +1,158 @@
+//! Terminal display width of status line text.
+//!
+//! Emoji and CJK characters take two columns, combining marks and joiners
+//! take none, and a variation selector 16 (`U+FE0F`) widens the preceding
+//! character to two columns, so `🗓️` measures 2 like the terminal draws it.
+
+/// Zero-width characters: combining marks, joiners, and selectors.
+const ZERO_WIDTH: [(char, char); 11] = [
+    ('\u{0300}', '\u{036f}'),
+    ('\u{1ab0}', '\u{1aff}'),
+    ('\u{1dc0}', '\u{1dff}'),
+    ('\u{200b}', '\u{200f}'),
+    ('\u{20d0}', '\u{20ff}'),
+    ('\u{fe00}', '\u{fe0f}'),
+    ('\u{fe20}', '\u{fe2f}'),
+    ('\u{feff}', '\u{feff}'),
+    ('\u{1f3fb}', '\u{1f3ff}'),
+    ('\u{e0000}', '\u{e007f}'),
+    ('\u{e0100}', '\u{e01ef}'),
+];
+
+/// Double-width characters: East Asian wide and emoji presentation ranges.
+const WIDE: [(char, char); 34] = [
+    ('\u{1100}', '\u{115f}'),
+    ('\u{231a}', '\u{231b}'),
+    ('\u{23e9}', '\u{23ec}'),
+    ('\u{23f0}', '\u{23f0}'),
+    ('\u{23f3}', '\u{23f3}'),
+    ('\u{25fd}', '\u{25fe}'),
+    ('\u{2614}', '\u{2615}'),
+    ('\u{2648}', '\u{2653}'),
+    ('\u{267f}', '\u{267f}'),
+    ('\u{2693}', '\u{2693}'),
+    ('\u{26a1}', '\u{26a1}'),
+    ('\u{26aa}', '\u{26ab}'),
+    ('\u{26bd}', '\u{26be}'),
+    ('\u{26c4}', '\u{26c5}'),
+    ('\u{26d4}', '\u{26d4}'),
+    ('\u{26ea}', '\u{26ea}'),
+    ('\u{26f2}', '\u{26f5}'),
+    ('\u{26fa}', '\u{26fd}'),
+    ('\u{2705}', '\u{2705}'),
+    ('\u{270a}', '\u{270b}'),
+    ('\u{2728}', '\u{2728}'),
+    ('\u{274c}', '\u{274e}'),
+    ('\u{2753}', '\u{2757}'),
+    ('\u{2795}', '\u{2797}'),
+    ('\u{2b1b}', '\u{2b1c}'),
+    ('\u{2b50}', '\u{2b55}'),
+    ('\u{2e80}', '\u{a4cf}'),
+    ('\u{ac00}', '\u{d7a3}'),
+    ('\u{f900}', '\u{faff}'),
+    ('\u{fe30}', '\u{fe4f}'),
+    ('\u{ff00}', '\u{ff60}'),
+    ('\u{ffe0}', '\u{ffe6}'),
+    ('\u{1f300}', '\u{1faff}'),
+    ('\u{20000}', '\u{3fffd}'),
+];
+
+/// Variation selector 16, which requests emoji presentation.
+const EMOJI_PRESENTATION: char = '\u{fe0f}';
+
+/// Zero width joiner, which glues emoji into one glyph.
+const ZWJ: char = '\u{200d}';
+
+fn in_ranges(c: char, ranges: &[(char, char)]) -> bool {
+    ranges
+        .iter()
+        .any(|(start, end)| (*start..=*end).contains(&c))
+}
+
+/// Returns the number of columns `c` takes on its own.
+fn char_width(c: char) -> usize {
+    if c.is_control() || in_ranges(c, &ZERO_WIDTH) {
+        0
+    } else if in_ranges(c, &WIDE) {
+        2
+    } else {
+        1
+    }
+}
+
+/// Returns the number of terminal columns `text` takes.
+#[must_use]
+pub fn display_width(text: &str) -> usize {
+    let mut width = 0;
+    let mut last = 0;
+    let mut joined = false;
+    for c in text.chars() {
+        if c == EMOJI_PRESENTATION && last == 1 {
+            width += 1;
+            last = 2;
+            continue;
+        }
+        if c == ZWJ {
+            joined = true;
+            continue;
+        }
+        let w = char_width(c);
+        if w == 0 {
+            continue;
+        }
+        // The character after a joiner is drawn inside the previous emoji
+        if std::mem::take(&mut joined) && last > 0 {
+            continue;
+        }
+        width += w;
+        last = w;
+    }
+    width
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ascii_width() {
+        assert_eq!(display_width(""), 0);
+        assert_eq!(display_width("$ 85% · 3x | # 30/100"), 21);
+        assert_eq!(display_width("[####----]"), 10);
+    }
+
+    #[test]
+    fn test_emoji_width() {
+        assert_eq!(display_width("🪙 85%"), 6);
+        assert_eq!(display_width("🌐"), 2);
+        // U+1F5D3 followed by the emoji variation selector
+        assert_eq!(display_width("\u{1f5d3}\u{fe0f}"), 2);
+        assert_eq!(display_width("⚡"), 2);
+        // Text symbol widened by the variation selector
+        assert_eq!(display_width("\u{2764}\u{fe0f}"), 2);
+        assert_eq!(display_width("\u{2764}"), 1);
+    }
+
+    #[test]
+    fn test_joined_emoji_width() {
+        // Family: man + ZWJ + woman + ZWJ + girl
+        assert_eq!(
+            display_width("\u{1f468}\u{200d}\u{1f469}\u{200d}\u{1f467}"),
+            2
+        );
+        // Thumbs up with a skin tone modifier
+        assert_eq!(display_width("\u{1f44d}\u{1f3fd}"), 2);
+    }
+
+    #[test]
+    fn test_cjk_and_combining_width() {
+        assert_eq!(display_width("周限额"), 6);
+        assert_eq!(display_width("e\u{301}"), 1);
+    }
+
+    #[test]
+    fn test_bar_and_nerd_font_width() {
+        assert_eq!(display_width("▕███▌░░░▏"), 9);
+        assert_eq!(display_width("\u{f0d6} 85%\u{e0b0}"), 6);
+    }
+}
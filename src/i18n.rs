@@ -0,0 +1,239 @@
+//! Chinese and English messages printed by the CLI commands.
+//!
+//! Segment labels pick their text in place with [`Locale::pick`]; the
+//! longer command messages live here so both languages stay side by side.
+
+use crate::config::Locale;
+use std::fmt::Display;
+
+/// A message printed by a CLI command.
+#[derive(Clone, Copy)]
+pub enum Message<'a> {
+    /// Reading the status line input from stdin failed.
+    ReadStdinFailed(&'a dyn Display),
+    /// The status line input is not valid JSON.
+    ParseInputFailed(&'a dyn Display),
+    /// Recording usage attribution failed.
+    AttributionFailed(&'a dyn Display),
+    /// Recording a usage spike failed.
+    SpikeFailed(&'a dyn Display),
+    /// Recording daily budget usage failed.
+    BudgetFailed(&'a dyn Display),
+    /// Checking or running alerts failed.
+    AlertsFailed(&'a dyn Display),
+    /// Delivering webhook events failed.
+    WebhookFailed(&'a dyn Display),
+    /// `init` found an existing config file at the path.
+    ConfigExists(&'a dyn Display),
+    /// `init` wrote a new config file at the path.
+    ConfigCreatedAt(&'a dyn Display),
+    /// Writing a new config file failed.
+    InitFailed(&'a dyn Display),
+    /// Printing the config failed.
+    PrintFailed(&'a dyn Display),
+    /// `check` found no config file at the path.
+    ConfigNotFound(&'a dyn Display),
+    /// `check` found an invalid configuration.
+    ConfigInvalid(&'a dyn Display),
+    /// `check` found a valid configuration.
+    ConfigValid,
+    /// `update` created a config file because none existed.
+    ConfigCreated,
+    /// `update` found nothing to migrate.
+    UpToDate,
+    /// `update` migrated the config with this many changes.
+    ConfigMigrated(usize),
+    /// A command failed with an error.
+    Error(&'a dyn Display),
+    /// The home directory could not be determined.
+    NoHomeDir,
+    /// `setup` is switching from simple to combined mode.
+    ReconfigureCombined,
+    /// `setup` is switching from combined to simple mode.
+    ReconfigureSimple,
+    /// `setup` found the status line already configured.
+    AlreadyConfigured,
+    /// `setup --with-ccline` found no `CCometixLine` binary at the path.
+    CclineNotFound(&'a dyn Display),
+    /// `setup` wrote the combined status line script to the path.
+    ScriptCreated(&'a dyn Display),
+    /// `setup` updated the Claude Code settings file at the path.
+    SettingsConfigured(&'a dyn Display),
+    /// Claude Code must be restarted for `setup` to take effect.
+    RestartClaude,
+    /// `setup` found a custom status line command and changed nothing.
+    CustomCommandConflict,
+    /// Hint for `setup --with-ccline` when a custom command is configured.
+    CombinedConflictHint,
+}
+
+impl Message<'_> {
+    /// Returns the message text in `locale`.
+    #[must_use]
+    pub fn text(self, locale: Locale) -> String {
+        if locale.resolve() == Locale::Zh {
+            self.zh()
+        } else {
+            self.en()
+        }
+    }
+
+    fn en(self) -> String {
+        match self {
+            Message::ReadStdinFailed(e) => format!("Error reading stdin: {e}"),
+            Message::ParseInputFailed(e) => format!("Error parsing input JSON: {e}"),
+            Message::AttributionFailed(e) => format!("Error recording usage attribution: {e}"),
+            Message::SpikeFailed(e) => format!("Error recording usage spike: {e}"),
+            Message::BudgetFailed(e) => format!("Error recording daily budget usage: {e}"),
+            Message::AlertsFailed(e) => format!("Error running alerts: {e}"),
+            Message::WebhookFailed(e) => format!("Error delivering webhook events: {e}"),
+            Message::ConfigExists(path) => format!(
+                "Config already exists at {path}\n\
+                 Run `glm-plan-usage update` to migrate to latest format."
+            ),
+            Message::ConfigCreatedAt(path) => format!("Created config at {path}"),
+            Message::InitFailed(e) => format!("Error initializing config: {e}"),
+            Message::PrintFailed(e) => format!("Error printing config: {e}"),
+            Message::ConfigNotFound(path) => format!("Config file not found at {path}"),
+            Message::ConfigInvalid(e) => format!("Configuration invalid: {e}"),
+            Message::ConfigValid => "✓ Configuration valid".to_string(),
+            Message::ConfigCreated => "Config created".to_string(),
+            Message::UpToDate => "Already up to date".to_string(),
+            Message::ConfigMigrated(changes) => format!("Config migrated ({changes} changes)"),
+            Message::Error(e) => format!("Error: {e}"),
+            Message::NoHomeDir => "Error: No home directory found".to_string(),
+            Message::ReconfigureCombined => {
+                "ℹ Statusline is configured for glm-plan-usage (simple mode).\n  \
+                 Reconfiguring for combined mode with `CCometixLine`..."
+                    .to_string()
+            }
+            Message::ReconfigureSimple => "ℹ Statusline is configured for combined mode.\n  \
+                 Reconfiguring for simple mode..."
+                .to_string(),
+            Message::AlreadyConfigured => {
+                "ℹ Statusline already configured for glm-plan-usage. No changes needed.".to_string()
+            }
+            Message::CclineNotFound(path) => format!(
+                "✗ CCometixLine not found at {path}\n  \
+                 Install CCometixLine first: https://github.com/Haleclipse/CCometixLine"
+            ),
+            Message::ScriptCreated(path) => {
+                format!("✓ Created combined statusline script at {path}")
+            }
+            Message::SettingsConfigured(path) => {
+                format!("✓ Configured Claude Code statusline in {path}")
+            }
+            Message::RestartClaude => "  Restart Claude Code to see changes.".to_string(),
+            Message::CustomCommandConflict => {
+                "⚠ Statusline is already configured with a custom command.\n  \
+                 To avoid overwriting your configuration, no changes were made.\n\n  \
+                 To add glm-plan-usage alongside your existing setup, add this to your\n  \
+                 Claude Code settings.json (~/.claude/settings.json) manually:\n"
+                    .to_string()
+            }
+            Message::CombinedConflictHint => {
+                "  Run `glm-plan-usage setup` (without --with-ccline) for simple mode,\n  \
+                 or refer to the README for combined setup instructions."
+                    .to_string()
+            }
+        }
+    }
+
+    fn zh(self) -> String {
+        match self {
+            Message::ReadStdinFailed(e) => format!("读取标准输入失败：{e}"),
+            Message::ParseInputFailed(e) => format!("解析输入 JSON 失败：{e}"),
+            Message::AttributionFailed(e) => format!("记录用量归属失败：{e}"),
+            Message::SpikeFailed(e) => format!("记录用量激增失败：{e}"),
+            Message::BudgetFailed(e) => format!("记录每日预算用量失败：{e}"),
+            Message::AlertsFailed(e) => format!("执行告警失败：{e}"),
+            Message::WebhookFailed(e) => format!("投递 Webhook 事件失败：{e}"),
+            Message::ConfigExists(path) => format!(
+                "配置文件已存在：{path}\n\
+                 运行 `glm-plan-usage update` 迁移到最新格式。"
+            ),
+            Message::ConfigCreatedAt(path) => format!("已创建配置文件：{path}"),
+            Message::InitFailed(e) => format!("初始化配置失败：{e}"),
+            Message::PrintFailed(e) => format!("打印配置失败：{e}"),
+            Message::ConfigNotFound(path) => format!("未找到配置文件：{path}"),
+            Message::ConfigInvalid(e) => format!("配置无效：{e}"),
+            Message::ConfigValid => "✓ 配置有效".to_string(),
+            Message::ConfigCreated => "已创建配置文件".to_string(),
+            Message::UpToDate => "配置已是最新".to_string(),
+            Message::ConfigMigrated(changes) => format!("配置已迁移（{changes} 处更改）"),
+            Message::Error(e) => format!("错误：{e}"),
+            Message::NoHomeDir => "错误：找不到用户主目录".to_string(),
+            Message::ReconfigureCombined => "ℹ 状态栏已配置为 glm-plan-usage（简单模式）。\n  \
+                 正在重新配置为与 `CCometixLine` 组合的模式..."
+                .to_string(),
+            Message::ReconfigureSimple => "ℹ 状态栏已配置为组合模式。\n  \
+                 正在重新配置为简单模式..."
+                .to_string(),
+            Message::AlreadyConfigured => "ℹ 状态栏已配置为 glm-plan-usage，无需更改。".to_string(),
+            Message::CclineNotFound(path) => format!(
+                "✗ 未找到 CCometixLine：{path}\n  \
+                 请先安装 CCometixLine：https://github.com/Haleclipse/CCometixLine"
+            ),
+            Message::ScriptCreated(path) => format!("✓ 已创建组合状态栏脚本：{path}"),
+            Message::SettingsConfigured(path) => {
+                format!("✓ 已在 {path} 中配置 Claude Code 状态栏")
+            }
+            Message::RestartClaude => "  重启 Claude Code 后生效。".to_string(),
+            Message::CustomCommandConflict => "⚠ 状态栏已配置了自定义命令。\n  \
+                 为避免覆盖你的配置，未做任何更改。\n\n  \
+                 如需在现有配置之外添加 glm-plan-usage，请手动将以下内容加入\n  \
+                 Claude Code 的 settings.json（~/.claude/settings.json）：\n"
+                .to_string(),
+            Message::CombinedConflictHint => {
+                "  运行 `glm-plan-usage setup`（不带 --with-ccline）使用简单模式，\n  \
+                 或参考 README 中的组合模式配置说明。"
+                    .to_string()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_english_messages() {
+        assert_eq!(
+            Message::ConfigMigrated(3).text(Locale::En),
+            "Config migrated (3 changes)"
+        );
+        assert_eq!(
+            Message::ConfigNotFound(&"/tmp/config.toml").text(Locale::En),
+            "Config file not found at /tmp/config.toml"
+        );
+        assert_eq!(
+            Message::ConfigValid.text(Locale::En),
+            "✓ Configuration valid"
+        );
+    }
+
+    #[test]
+    fn test_chinese_messages() {
+        assert_eq!(
+            Message::ConfigMigrated(3).text(Locale::Zh),
+            "配置已迁移（3 处更改）"
+        );
+        assert_eq!(
+            Message::ConfigNotFound(&"/tmp/config.toml").text(Locale::Zh),
+            "未找到配置文件：/tmp/config.toml"
+        );
+        assert_eq!(Message::ConfigValid.text(Locale::Zh), "✓ 配置有效");
+    }
+
+    #[test]
+    fn test_multiline_messages_keep_indentation() {
+        for locale in [Locale::En, Locale::Zh] {
+            let text = Message::CclineNotFound(&"/x/ccline").text(locale);
+            let lines: Vec<&str> = text.lines().collect();
+            assert_eq!(lines.len(), 2);
+            assert!(lines[0].starts_with('✗'));
+            assert!(lines[1].starts_with("  ") && !lines[1].starts_with("   "));
+        }
+    }
+}
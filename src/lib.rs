@@ -3,8 +3,13 @@
 //! This plugin displays GLM (ZHIPU/ZAI) coding plan usage statistics
 //! in the Claude Code status bar.
 
+pub mod alerts;
 pub mod api;
 pub mod cli;
 pub mod config;
 pub mod core;
+pub mod report;
+pub mod state;
+pub mod transcript;
 pub mod util;
+pub mod webhook;
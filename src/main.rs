@@ -8,17 +8,30 @@ use mimalloc::MiMalloc;
 #[global_allocator]
 static GLOBAL: MiMalloc = MiMalloc;
 
+mod alerts;
 mod api;
 mod cli;
 mod config;
 mod core;
+mod i18n;
+mod report;
 mod setup;
+mod state;
+mod transcript;
 mod util;
+mod webhook;
 
 use clap::Parser;
-use cli::Commands;
-use config::{Config, ConfigLoader, InputData, SegmentConfig};
-use core::{McpUsageSegment, Segment, StatusLineGenerator, TokenUsageSegment, WeeklyUsageSegment};
+use cli::{BarProtocol, Commands, OutputFormat, PromptTarget, ReportBy, ThemeAction};
+use config::{
+    ColorDepth, Config, ConfigLoader, InputData, Locale, SegmentConfig, StyleConfig, Theme,
+};
+use core::{
+    BudgetSegment, ContextSegment, CostSegment, McpUsageSegment, Segment, SessionSegment,
+    StatusLineGenerator, TokenUsageSegment, ValueSegment, WeeklyUsageSegment,
+};
+use i18n::Message;
+use std::io::Write;
 
 fn main() {
     let args = cli::Args::parse();
@@ -30,22 +43,29 @@ fn main() {
             Commands::Check => handle_check(),
             Commands::Update => handle_update(),
             Commands::Setup { with_ccline } => handle_setup(with_ccline),
+            Commands::Report { by } => handle_report(by),
+            Commands::Prompt { target } => handle_prompt(target, args.no_cache),
+            Commands::Bar { protocol } => handle_bar(protocol, args.no_cache),
+            Commands::Theme { action } => handle_theme(action),
+            Commands::Roi { days } => handle_roi(days),
         }
         return;
     }
 
     let mut config = Config::load();
+    let locale = config.style.locale;
 
     if args.no_cache {
         config.cache.enabled = false;
     }
+    if args.format == OutputFormat::Plain {
+        config.style.color_depth = ColorDepth::None;
+    }
 
     let input_text = match read_stdin() {
         Ok(text) => text,
         Err(e) => {
-            if args.verbose {
-                eprintln!("Error reading stdin: {e}");
-            }
+            log_verbose(args.verbose, locale, Message::ReadStdinFailed(&e));
             return;
         }
     };
@@ -53,39 +73,92 @@ fn main() {
     let input: InputData = match serde_json::from_str(&input_text) {
         Ok(data) => data,
         Err(e) => {
-            if args.verbose {
-                eprintln!("Error parsing input JSON: {e}");
-            }
-            InputData {
-                model: None,
-                workspace: None,
-                transcript_path: None,
-                cost_info: None,
-            }
+            log_verbose(args.verbose, locale, Message::ParseInputFailed(&e));
+            InputData::default()
         }
     };
 
-    let segments = collect_segments(&config, &input);
+    let shared_cache = api::SharedCache::new();
+    let segments = collect_segments(&config, &input, &shared_cache);
+    if config.tracking.transcripts {
+        // Keep the daily ledger filled even when no segment reads transcripts.
+        let _ = transcript::observe(&config, &input);
+    }
 
-    let output = StatusLineGenerator::generate(&config, &segments);
+    let stats = shared_cache.peek();
+    let mut triggered = Vec::new();
+    if let Some(stats) = &stats {
+        if let Err(e) = state::record_attribution(&config, stats, &input) {
+            log_verbose(args.verbose, locale, Message::AttributionFailed(&e));
+        }
+        match alerts::check_alerts(&config, stats) {
+            Ok(fired) => triggered = fired,
+            Err(e) => log_verbose(args.verbose, locale, Message::AlertsFailed(&e)),
+        }
+        match state::record_spike(&config, stats) {
+            Ok(Some(spike)) => {
+                triggered.push(alerts::TriggeredAlert::from_spike(&config.spike, spike));
+            }
+            Ok(None) => {}
+            Err(e) => log_verbose(args.verbose, locale, Message::SpikeFailed(&e)),
+        }
+        if let Err(e) = state::record_daily_usage(&config, stats) {
+            log_verbose(args.verbose, locale, Message::BudgetFailed(&e));
+        }
+        for alert in &triggered {
+            if let Err(e) = alerts::dispatch(alert) {
+                log_verbose(args.verbose, locale, Message::AlertsFailed(&e));
+            }
+        }
+    }
+
+    let output = match args.format {
+        OutputFormat::Json => core::generate_json(&segments, stats.as_ref()),
+        OutputFormat::Ansi | OutputFormat::Plain => {
+            StatusLineGenerator::generate(&config, &segments)
+        }
+    };
 
     if !output.is_empty() {
         print!("{output}");
+        let _ = std::io::stdout().flush();
+    }
+
+    // Deliver webhook events after printing. Delivery is capped at
+    // `webhook.timeout_ms` per run, so a slow endpoint delays the exit by at
+    // most that long.
+    if let Err(e) = webhook::process(
+        &config,
+        stats.as_ref(),
+        shared_cache.auth_failed(),
+        &triggered,
+    ) {
+        log_verbose(args.verbose, locale, Message::WebhookFailed(&e));
+    }
+}
+
+/// Print a diagnostic message to stderr when `--verbose` is set.
+fn log_verbose(verbose: bool, locale: Locale, message: Message) {
+    if verbose {
+        eprintln!("{}", message.text(locale));
     }
 }
 
 /// Initialize a new config file at the default location.
 fn handle_init() {
+    let locale = Config::load().style.locale;
     let config_path = Config::config_path();
     if config_path.exists() {
-        println!("Config already exists at {}", config_path.display());
-        println!("Run `glm-plan-usage update` to migrate to latest format.");
+        println!(
+            "{}",
+            Message::ConfigExists(&config_path.display()).text(locale)
+        );
         return;
     }
     match Config::init_config() {
-        Ok(path) => println!("Created config at {}", path.display()),
+        Ok(path) => println!("{}", Message::ConfigCreatedAt(&path.display()).text(locale)),
         Err(e) => {
-            eprintln!("Error initializing config: {e}");
+            eprintln!("{}", Message::InitFailed(&e).text(locale));
             std::process::exit(1);
         }
     }
@@ -95,34 +168,43 @@ fn handle_init() {
 fn handle_print() {
     let config = Config::load();
     if let Err(e) = config.print() {
-        eprintln!("Error printing config: {e}");
+        eprintln!("{}", Message::PrintFailed(&e).text(config.style.locale));
         std::process::exit(1);
     }
 }
 
 /// Validate the current configuration and report any errors.
 fn handle_check() {
+    let config = Config::load();
+    let locale = config.style.locale;
     let config_path = Config::config_path();
     if !config_path.exists() {
-        eprintln!("Config file not found at {}", config_path.display());
+        eprintln!(
+            "{}",
+            Message::ConfigNotFound(&config_path.display()).text(locale)
+        );
         std::process::exit(1);
     }
-    let config = Config::load();
     if let Err(e) = config.check() {
-        eprintln!("Configuration invalid: {e}");
+        eprintln!("{}", Message::ConfigInvalid(&e).text(locale));
         std::process::exit(1);
     }
-    println!("✓ Configuration valid");
+    println!("{}", Message::ConfigValid.text(locale));
 }
 
 /// Update the config file to the latest format version.
 fn handle_update() {
     match Config::load_for_update() {
-        Ok((_, None)) => println!("Config created"),
-        Ok((_, Some(r))) if r.changes == 0 => println!("Already up to date"),
-        Ok((_, Some(r))) => println!("Config migrated ({} changes)", r.changes),
+        Ok((_, None)) => println!("{}", Message::ConfigCreated.text(Locale::Auto)),
+        Ok((config, Some(r))) if r.changes == 0 => {
+            println!("{}", Message::UpToDate.text(config.style.locale));
+        }
+        Ok((config, Some(r))) => println!(
+            "{}",
+            Message::ConfigMigrated(r.changes).text(config.style.locale)
+        ),
         Err(e) => {
-            eprintln!("Error: {e}");
+            eprintln!("{}", Message::Error(&e).text(Locale::Auto));
             std::process::exit(1);
         }
     }
@@ -130,12 +212,181 @@ fn handle_update() {
 
 /// Configure Claude Code statusline settings.
 fn handle_setup(with_ccline: bool) {
-    if let Err(e) = setup::run(with_ccline) {
-        eprintln!("Error: {e}");
+    let locale = Config::load().style.locale;
+    if let Err(e) = setup::run(with_ccline, locale) {
+        eprintln!("{}", Message::Error(&e).text(locale));
+        std::process::exit(1);
+    }
+}
+
+/// Print the status line for tmux or a shell prompt.
+///
+/// Uses the same config, segments, and cache as the Claude Code status
+/// line, but reads no stdin and records no usage state.
+fn handle_prompt(target: PromptTarget, no_cache: bool) {
+    let mut config = Config::load();
+    if no_cache {
+        config.cache.enabled = false;
+    }
+    if target == PromptTarget::Plain {
+        config.style.color_depth = ColorDepth::None;
+    }
+
+    let shared_cache = api::SharedCache::new();
+    let segments = collect_segments(&config, &InputData::default(), &shared_cache);
+    let output = StatusLineGenerator::generate(&config, &segments);
+    if !output.is_empty() {
+        print!("{}", core::prompt_escape(&output, target));
+        let _ = std::io::stdout().flush();
+    }
+}
+
+/// Print usage for Waybar, or stream it to i3bar.
+///
+/// The i3bar stream refetches usage every `cache.ttl_seconds` and ends when
+/// the bar closes its end of the pipe.
+fn handle_bar(protocol: BarProtocol, no_cache: bool) {
+    let mut config = Config::load();
+    if no_cache {
+        config.cache.enabled = false;
+    }
+    let shared_cache = api::SharedCache::new();
+
+    if protocol == BarProtocol::Waybar {
+        let segments = collect_segments(&config, &InputData::default(), &shared_cache);
+        let stats = shared_cache.peek();
+        println!(
+            "{}",
+            core::generate_waybar(&config, &segments, stats.as_ref())
+        );
+        return;
+    }
+
+    let interval = std::time::Duration::from_secs(config.cache.ttl_seconds.max(1));
+    let mut stdout = std::io::stdout();
+    if writeln!(stdout, "{}", core::I3BAR_HEADER).is_err() {
+        return;
+    }
+    loop {
+        let segments = collect_segments(&config, &InputData::default(), &shared_cache);
+        let line = core::generate_i3bar(&config, &segments);
+        if writeln!(stdout, "{line},")
+            .and_then(|()| stdout.flush())
+            .is_err()
+        {
+            return;
+        }
+        std::thread::sleep(interval);
+    }
+}
+
+/// Print quota consumption grouped by workspace or model.
+fn handle_report(by: ReportBy) {
+    let Some(path) = state::state_path(state::LEDGER_FILE) else {
+        eprintln!("{}", Message::NoHomeDir.text(Locale::Auto));
         std::process::exit(1);
+    };
+    let ledger: state::AttributionLedger = state::load(&path);
+    print!("{}", report::render_attribution(&ledger, by));
+}
+
+/// List themes, or preview one with sample usage.
+fn handle_theme(action: ThemeAction) {
+    let config = Config::load();
+    match action {
+        ThemeAction::List => {
+            let current = config.style.theme.as_deref().unwrap_or("default");
+            let themes = config::list_themes();
+            let width = themes.iter().map(|t| t.name.len()).max().unwrap_or(0);
+            for theme in themes {
+                let marker = if theme.name == current { '*' } else { ' ' };
+                let mut line = format!("{marker} {:<width$}  {}", theme.name, theme.description);
+                if let Some(path) = &theme.path {
+                    line = format!("{} ({})", line.trim_end(), path.display());
+                }
+                println!("{}", line.trim_end());
+            }
+        }
+        ThemeAction::Preview { name } => {
+            let name = name
+                .or_else(|| config.style.theme.clone())
+                .unwrap_or_else(|| "default".to_string());
+            match Theme::load(&name) {
+                Ok(theme) => print!("{}", preview_theme(&config, &name, &theme)),
+                Err(e) => {
+                    eprintln!("{}", Message::Error(&e).text(config.style.locale));
+                    std::process::exit(1);
+                }
+            }
+        }
     }
 }
 
+/// Renders the default segments with `theme` at low, medium, and high usage.
+///
+/// Only the display mode, color depth, locale, and multiplier settings of
+/// `config` are kept, so the preview shows the theme itself. A premium
+/// model is assumed so the multiplier is shown when it applies.
+fn preview_theme(config: &Config, name: &str, theme: &Theme) -> String {
+    let mut preview = Config {
+        style: StyleConfig {
+            mode: config.style.mode,
+            theme: Some(name.to_string()),
+            color_depth: config.style.color_depth,
+            locale: config.style.locale,
+            max_width: Some(0),
+            ..StyleConfig::default()
+        },
+        multiplier: config.multiplier.clone(),
+        ..Config::default()
+    };
+    preview.spike.enabled = false;
+    theme.apply(&mut preview);
+
+    let model = preview.multiplier.premium_models.first().cloned();
+    let input: InputData =
+        serde_json::from_value(serde_json::json!({ "model": { "id": model } })).unwrap_or_default();
+
+    let mut output = String::new();
+    for percentage in [25, 60, 90] {
+        let shared_cache = api::SharedCache::new();
+        shared_cache.put(sample_stats(percentage));
+        let segments = collect_segments(&preview, &input, &shared_cache);
+        output.push_str(&StatusLineGenerator::generate(&preview, &segments));
+        output.push('\n');
+    }
+    output
+}
+
+/// Returns made-up usage statistics at `percentage` of the 5-hour quota.
+fn sample_stats(percentage: u8) -> api::UsageStats {
+    let now = util::unix_now();
+    let quota = |percentage: u8, limit: i64, window: &str, reset_in: i64| api::QuotaUsage {
+        used: limit * i64::from(percentage) / 100,
+        limit,
+        percentage,
+        time_window: window.to_string(),
+        reset_at: Some(now + reset_in),
+    };
+    api::UsageStats {
+        token_usage: Some(quota(percentage, 40_000_000, "5h", 2 * 3600 + 15 * 60)),
+        weekly_usage: Some(quota(percentage / 2, 400_000_000, "weekly", 3 * 86_400)),
+        mcp_usage: Some(quota(percentage, 100, "30d", 12 * 86_400)),
+    }
+}
+
+/// Print the metered value of recent usage against the plan price.
+fn handle_roi(days: Option<u32>) {
+    let config = Config::load();
+    let days = days.unwrap_or(config.pricing.plan_period_days);
+    let ledger = transcript::load_daily();
+    let today = transcript::day_of(util::unix_now());
+    print!(
+        "{}",
+        report::render_roi(&config.pricing, &ledger, today, days)
+    );
+}
+
 /// Read all input from stdin.
 fn read_stdin() -> Result<String, std::io::Error> {
     use std::io::Read;
@@ -145,18 +396,31 @@ fn read_stdin() -> Result<String, std::io::Error> {
 }
 
 /// Collect active segments with their data for rendering.
-fn collect_segments(config: &Config, input: &InputData) -> Vec<(SegmentConfig, core::SegmentData)> {
+fn collect_segments(
+    config: &Config,
+    input: &InputData,
+    shared_cache: &api::SharedCache,
+) -> Vec<(SegmentConfig, core::SegmentData)> {
     let mut results = Vec::new();
 
-    let shared_cache = api::SharedCache::new();
     let token_segment = TokenUsageSegment::with_cache(shared_cache.clone());
     let weekly_segment = WeeklyUsageSegment::with_cache(shared_cache.clone());
     let mcp_segment = McpUsageSegment::with_cache(shared_cache.clone());
+    let session_segment = SessionSegment::with_cache(shared_cache.clone());
+    let context_segment = ContextSegment::new();
+    let cost_segment = CostSegment::new();
+    let value_segment = ValueSegment::with_cache(shared_cache.clone());
+    let budget_segment = BudgetSegment::with_cache(shared_cache.clone());
 
-    let segment_lookup: [(&str, &dyn Segment); 3] = [
+    let segment_lookup: [(&str, &dyn Segment); 8] = [
         ("token_usage", &token_segment),
         ("weekly_usage", &weekly_segment),
         ("mcp_usage", &mcp_segment),
+        ("session", &session_segment),
+        ("context", &context_segment),
+        ("cost", &cost_segment),
+        ("value", &value_segment),
+        ("budget", &budget_segment),
     ];
 
     for seg_config in &config.segments {
@@ -167,7 +431,8 @@ fn collect_segments(config: &Config, input: &InputData) -> Vec<(SegmentConfig, c
         let data = segment_lookup
             .iter()
             .find(|(id, _)| *id == seg_config.id.as_str())
-            .and_then(|(_, seg)| seg.collect(input, config));
+            .and_then(|(_, seg)| seg.collect(input, config))
+            .filter(|d| core::should_show(&seg_config.show_when, d, input, config));
 
         if let Some(d) = data {
             results.push((seg_config.clone(), d));
@@ -0,0 +1,110 @@
+//! Attribution report rendering.
+//!
+//! Renders the attribution ledger as a table of quota consumption
+//! per workspace or model, heaviest consumers first.
+
+use crate::cli::ReportBy;
+use crate::state::{AttributionLedger, Consumption};
+use crate::util::format_timestamp;
+
+/// Renders the attribution ledger grouped by workspace or model.
+#[must_use]
+pub fn render_attribution(ledger: &AttributionLedger, by: ReportBy) -> String {
+    let (label, entries) = match by {
+        ReportBy::Workspace => ("WORKSPACE", &ledger.workspaces),
+        ReportBy::Model => ("MODEL", &ledger.models),
+    };
+
+    if entries.is_empty() {
+        return "No quota consumption recorded yet.\n".to_string();
+    }
+
+    let mut rows: Vec<(&String, &Consumption)> = entries.iter().collect();
+    rows.sort_by(|(a_key, a), (b_key, b)| {
+        (b.five_hour_pct, b.weekly_pct)
+            .cmp(&(a.five_hour_pct, a.weekly_pct))
+            .then_with(|| a_key.cmp(b_key))
+    });
+
+    let key_width = rows
+        .iter()
+        .map(|(key, _)| key.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(label.len());
+
+    let group = match by {
+        ReportBy::Workspace => "workspace",
+        ReportBy::Model => "model",
+    };
+    let title = match ledger.since.and_then(format_timestamp) {
+        Some(since) => format!("Quota consumption by {group} since {since}"),
+        None => format!("Quota consumption by {group}"),
+    };
+    let mut lines = vec![
+        title,
+        String::new(),
+        format!("{label:<key_width$}  {:>8}  {:>8}", "5H", "WEEKLY"),
+    ];
+    lines.extend(rows.into_iter().map(|(key, consumption)| {
+        format!(
+            "{key:<key_width$}  {:>8}  {:>8}",
+            format!("+{}%", consumption.five_hour_pct),
+            format!("+{}%", consumption.weekly_pct),
+        )
+    }));
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn consumption(five_hour_pct: u64, weekly_pct: u64) -> Consumption {
+        Consumption {
+            five_hour_pct,
+            weekly_pct,
+            ..Consumption::default()
+        }
+    }
+
+    #[test]
+    fn test_render_empty_ledger() {
+        let ledger = AttributionLedger::default();
+        let output = render_attribution(&ledger, ReportBy::Workspace);
+        assert!(output.contains("No quota consumption"));
+    }
+
+    #[test]
+    fn test_render_sorted_by_five_hour_consumption() {
+        let mut ledger = AttributionLedger::default();
+        ledger
+            .workspaces
+            .insert("/small".to_string(), consumption(2, 1));
+        ledger
+            .workspaces
+            .insert("/large".to_string(), consumption(40, 9));
+
+        let output = render_attribution(&ledger, ReportBy::Workspace);
+        let large = output.find("/large").unwrap();
+        let small = output.find("/small").unwrap();
+        assert!(
+            large < small,
+            "heaviest consumer must come first:\n{output}"
+        );
+        assert!(output.contains("+40%"));
+        assert!(output.contains("WORKSPACE"));
+    }
+
+    #[test]
+    fn test_render_by_model() {
+        let mut ledger = AttributionLedger::default();
+        ledger.models.insert("glm-5".to_string(), consumption(7, 3));
+
+        let output = render_attribution(&ledger, ReportBy::Model);
+        assert!(output.contains("MODEL"));
+        assert!(output.contains("glm-5"));
+        assert!(output.contains("+7%"));
+    }
+}
@@ -0,0 +1,9 @@
+//! Reports built from locally persisted usage state.
+
+mod attribution;
+mod roi;
+
+#[doc(inline)]
+pub use attribution::render_attribution;
+#[doc(inline)]
+pub use roi::render_roi;
@@ -0,0 +1,157 @@
+//! Plan ROI report rendering.
+//!
+//! Compares the metered (pay-as-you-go) value of recent token usage with
+//! the configured plan price.
+
+use crate::config::PricingConfig;
+use crate::transcript::{DailyLedger, ValueSummary};
+use crate::util::format_compact;
+use time::{format_description, Date, Duration};
+
+/// Renders the ROI report for the `days` days ending on `today`.
+///
+/// `today` is a UTC day number as returned by [`crate::transcript::day_of`].
+#[must_use]
+pub fn render_roi(pricing: &PricingConfig, ledger: &DailyLedger, today: i64, days: u32) -> String {
+    let first_day = today - i64::from(days.max(1)) + 1;
+    let summary = crate::transcript::summarize(pricing, &ledger.since(first_day));
+
+    if summary.models.is_empty() {
+        return "No token usage recorded yet.\n".to_string();
+    }
+
+    let symbol = &pricing.currency_symbol;
+    let title = match format_day(first_day) {
+        Some(since) => format!("Metered value of the last {days} days (since {since})"),
+        None => format!("Metered value of the last {days} days"),
+    };
+    let mut lines = vec![title, String::new()];
+    lines.extend(model_table(&summary, symbol));
+    lines.push(String::new());
+
+    let total = format!("{symbol}{:.2}", summary.total);
+    lines.push(format!("{:<20}{total:>12}", "Metered equivalent"));
+    if let Some(plan) = pricing.prorated_plan_price(days) {
+        lines.push(format!(
+            "{:<20}{:>12}",
+            "Plan price",
+            format!("{symbol}{plan:.2}")
+        ));
+        lines.push(format!(
+            "{:<20}{:>12}",
+            "Return",
+            format!("{:.2}x", summary.total / plan)
+        ));
+    } else {
+        lines.push("Set [pricing] plan_price to compare against your plan.".to_string());
+    }
+    if summary.models.iter().any(|m| m.cost.is_none()) {
+        lines.push(
+            "Models without a price are excluded; add them under [pricing] models.".to_string(),
+        );
+    }
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+/// Renders the per-model token and value table.
+fn model_table(summary: &ValueSummary, symbol: &str) -> Vec<String> {
+    let key_width = summary
+        .models
+        .iter()
+        .map(|m| m.model.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("MODEL".len());
+
+    let mut lines = vec![format!(
+        "{:<key_width$}  {:>8}  {:>10}",
+        "MODEL", "TOKENS", "VALUE"
+    )];
+    lines.extend(summary.models.iter().map(|m| {
+        let value = m
+            .cost
+            .map_or_else(|| "-".to_string(), |cost| format!("{symbol}{cost:.2}"));
+        format!(
+            "{:<key_width$}  {:>8}  {value:>10}",
+            m.model,
+            format_compact(m.tokens.total())
+        )
+    }));
+    lines
+}
+
+/// Formats a UTC day number as "YYYY-MM-DD".
+fn format_day(day: i64) -> Option<String> {
+    let date = Date::from_calendar_date(1970, time::Month::January, 1)
+        .ok()?
+        .checked_add(Duration::days(day))?;
+    let format = format_description::parse("[year]-[month]-[day]").ok()?;
+    date.format(&format).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transcript::TokenTotals;
+    use std::collections::BTreeMap;
+
+    fn ledger_with(day: i64, model: &str, input: u64) -> DailyLedger {
+        let mut ledger = DailyLedger::default();
+        let tokens = TokenTotals {
+            input,
+            ..TokenTotals::default()
+        };
+        ledger.add(day, &BTreeMap::from([(model.to_string(), tokens)]));
+        ledger
+    }
+
+    #[test]
+    fn test_render_empty_ledger() {
+        let output = render_roi(&PricingConfig::default(), &DailyLedger::default(), 100, 30);
+        assert!(output.contains("No token usage recorded yet"));
+    }
+
+    #[test]
+    fn test_render_without_plan_price() {
+        let ledger = ledger_with(100, "glm-5.1", 2_000_000);
+        let output = render_roi(&PricingConfig::default(), &ledger, 100, 30);
+        assert!(output.contains("glm-5.1"));
+        assert!(output.contains("2M"));
+        assert!(output.contains("$2.00"));
+        assert!(output.contains("plan_price"));
+    }
+
+    #[test]
+    fn test_render_with_plan_price() {
+        let pricing = PricingConfig {
+            plan_price: 3.0,
+            ..PricingConfig::default()
+        };
+        let ledger = ledger_with(100, "glm-5.1", 6_000_000);
+        let output = render_roi(&pricing, &ledger, 100, 30);
+        assert!(output.contains("$6.00"));
+        assert!(output.contains("$3.00"));
+        assert!(output.contains("2.00x"));
+    }
+
+    #[test]
+    fn test_render_marks_unpriced_models() {
+        let ledger = ledger_with(100, "mystery-model", 1_000);
+        let output = render_roi(&PricingConfig::default(), &ledger, 100, 30);
+        assert!(output.contains("without a price"));
+    }
+
+    #[test]
+    fn test_render_excludes_days_outside_window() {
+        let ledger = ledger_with(50, "glm-5.1", 1_000);
+        let output = render_roi(&PricingConfig::default(), &ledger, 100, 30);
+        assert!(output.contains("No token usage recorded yet"));
+    }
+
+    #[test]
+    fn test_format_day() {
+        assert_eq!(format_day(0).unwrap(), "1970-01-01");
+        assert_eq!(format_day(20_000).unwrap(), "2024-10-04");
+    }
+}
@@ -3,11 +3,13 @@
 mod script;
 mod settings;
 
+use crate::config::Locale;
+use crate::i18n::Message;
 use anyhow::Result;
 use std::path::PathBuf;
 
-/// Run the setup command.
-pub fn run(with_ccline: bool) -> Result<()> {
+/// Run the setup command, printing messages in `locale`.
+pub fn run(with_ccline: bool, locale: Locale) -> Result<()> {
     let settings_path = claude_settings_path();
 
     let mut doc = settings::read_or_create(&settings_path)?;
@@ -17,17 +19,15 @@ pub fn run(with_ccline: bool) -> Result<()> {
     if let Some(ref cmd) = current_command {
         if is_our_command(cmd) {
             if with_ccline && !is_combined_command(cmd) {
-                println!("ℹ Statusline is configured for glm-plan-usage (simple mode).");
-                println!("  Reconfiguring for combined mode with `CCometixLine`...");
+                println!("{}", Message::ReconfigureCombined.text(locale));
             } else if !with_ccline && is_combined_command(cmd) {
-                println!("ℹ Statusline is configured for combined mode.");
-                println!("  Reconfiguring for simple mode...");
+                println!("{}", Message::ReconfigureSimple.text(locale));
             } else {
-                println!("ℹ Statusline already configured for glm-plan-usage. No changes needed.");
+                println!("{}", Message::AlreadyConfigured.text(locale));
                 return Ok(());
             }
         } else {
-            print_conflict_guidance(with_ccline);
+            print_conflict_guidance(with_ccline, locale);
             return Ok(());
         }
     }
@@ -35,8 +35,10 @@ pub fn run(with_ccline: bool) -> Result<()> {
     if with_ccline {
         let ccline_path = ccline_binary_path();
         if !ccline_path.exists() {
-            eprintln!("✗ CCometixLine not found at {}", ccline_path.display());
-            eprintln!("  Install CCometixLine first: https://github.com/Haleclipse/CCometixLine");
+            eprintln!(
+                "{}",
+                Message::CclineNotFound(&ccline_path.display()).text(locale)
+            );
             std::process::exit(1);
         }
 
@@ -47,24 +49,24 @@ pub fn run(with_ccline: bool) -> Result<()> {
         settings::write(&settings_path, &doc)?;
 
         println!(
-            "✓ Created combined statusline script at {}",
-            script_path.display()
+            "{}",
+            Message::ScriptCreated(&script_path.display()).text(locale)
         );
         println!(
-            "✓ Configured Claude Code statusline in {}",
-            settings_path.display()
+            "{}",
+            Message::SettingsConfigured(&settings_path.display()).text(locale)
         );
     } else {
         doc.set_statusline_command(&glm_command_path());
         settings::write(&settings_path, &doc)?;
 
         println!(
-            "✓ Configured Claude Code statusline in {}",
-            settings_path.display()
+            "{}",
+            Message::SettingsConfigured(&settings_path.display()).text(locale)
         );
     }
 
-    println!("  Restart Claude Code to see changes.");
+    println!("{}", Message::RestartClaude.text(locale));
     Ok(())
 }
 
@@ -125,17 +127,11 @@ fn is_combined_command(cmd: &str) -> bool {
 }
 
 /// Print guidance when a custom statusline is already configured.
-fn print_conflict_guidance(with_ccline: bool) {
-    println!("⚠ Statusline is already configured with a custom command.");
-    println!("  To avoid overwriting your configuration, no changes were made.");
-    println!();
-    println!("  To add glm-plan-usage alongside your existing setup, add this to your");
-    println!("  Claude Code settings.json (~/.claude/settings.json) manually:");
-    println!();
+fn print_conflict_guidance(with_ccline: bool, locale: Locale) {
+    println!("{}", Message::CustomCommandConflict.text(locale));
 
     if with_ccline {
-        println!("  Run `glm-plan-usage setup` (without --with-ccline) for simple mode,");
-        println!("  or refer to the README for combined setup instructions.");
+        println!("{}", Message::CombinedConflictHint.text(locale));
     } else {
         let cmd = glm_command_path();
         let snippet = format!(
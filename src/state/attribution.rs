@@ -0,0 +1,329 @@
+//! Per-workspace and per-model quota attribution.
+//!
+//! Each fetch is compared with the previous snapshot, and the quota
+//! consumed in between is attributed to the active workspace and model.
+
+use crate::api::{QuotaUsage, UsageStats};
+use crate::config::InputData;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// State file name for the attribution ledger.
+pub const LEDGER_FILE: &str = "attribution.json";
+
+/// Key used when the workspace or model is not reported by Claude Code.
+pub const UNKNOWN_KEY: &str = "(unknown)";
+
+/// Point-in-time view of a single quota used to compute deltas.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuotaSnapshot {
+    /// Usage percentage at the time of the snapshot.
+    pub percentage: u8,
+    /// Raw amount used at the time of the snapshot.
+    pub used: i64,
+    /// Reset timestamp in seconds, identifying the quota window.
+    pub reset_at: Option<i64>,
+}
+
+impl From<&QuotaUsage> for QuotaSnapshot {
+    fn from(quota: &QuotaUsage) -> Self {
+        Self {
+            percentage: quota.percentage,
+            used: quota.used,
+            reset_at: quota.reset_at,
+        }
+    }
+}
+
+impl QuotaSnapshot {
+    /// Returns the `(percentage points, raw amount)` consumed since `previous`.
+    ///
+    /// When the quota window has rolled over, everything used in the new
+    /// window counts as consumption. Decreases within a window are ignored.
+    #[must_use]
+    pub fn delta_since(&self, previous: &QuotaSnapshot) -> (u64, u64) {
+        let window_changed = self.reset_at.is_some()
+            && previous.reset_at.is_some()
+            && self.reset_at != previous.reset_at;
+        let base = if window_changed {
+            QuotaSnapshot::default()
+        } else {
+            *previous
+        };
+        let pct = u64::from(self.percentage.saturating_sub(base.percentage));
+        let used = u64::try_from(self.used.saturating_sub(base.used)).unwrap_or(0);
+        (pct, used)
+    }
+}
+
+/// Accumulated quota consumption for one workspace or model.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Consumption {
+    /// Percentage points of the 5-hour quota consumed.
+    #[serde(default)]
+    pub five_hour_pct: u64,
+    /// Percentage points of the weekly quota consumed.
+    #[serde(default)]
+    pub weekly_pct: u64,
+    /// Raw amount of the 5-hour quota consumed.
+    #[serde(default)]
+    pub five_hour_used: u64,
+    /// Raw amount of the weekly quota consumed.
+    #[serde(default)]
+    pub weekly_used: u64,
+}
+
+impl Consumption {
+    fn add(&mut self, other: &Consumption) {
+        self.five_hour_pct += other.five_hour_pct;
+        self.weekly_pct += other.weekly_pct;
+        self.five_hour_used += other.five_hour_used;
+        self.weekly_used += other.weekly_used;
+    }
+
+    fn is_zero(&self) -> bool {
+        *self == Consumption::default()
+    }
+}
+
+/// Persisted attribution ledger.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AttributionLedger {
+    /// Timestamp (seconds) of the first recorded fetch.
+    #[serde(default)]
+    pub since: Option<i64>,
+    /// Last observed 5-hour quota.
+    #[serde(default)]
+    pub last_five_hour: Option<QuotaSnapshot>,
+    /// Last observed weekly quota.
+    #[serde(default)]
+    pub last_weekly: Option<QuotaSnapshot>,
+    /// Consumption keyed by workspace directory.
+    #[serde(default)]
+    pub workspaces: BTreeMap<String, Consumption>,
+    /// Consumption keyed by model ID.
+    #[serde(default)]
+    pub models: BTreeMap<String, Consumption>,
+}
+
+impl AttributionLedger {
+    /// Records a fetch, attributing consumption since the last one.
+    ///
+    /// Returns `true` if the ledger changed and should be saved.
+    pub fn record(&mut self, stats: &UsageStats, input: &InputData, now: i64) -> bool {
+        let five_hour = stats.token_usage.as_ref().map(QuotaSnapshot::from);
+        let weekly = stats.weekly_usage.as_ref().map(QuotaSnapshot::from);
+
+        let (five_hour_pct, five_hour_used) = delta(five_hour, self.last_five_hour);
+        let (weekly_pct, weekly_used) = delta(weekly, self.last_weekly);
+        let consumed = Consumption {
+            five_hour_pct,
+            weekly_pct,
+            five_hour_used,
+            weekly_used,
+        };
+
+        let mut changed = false;
+        if self.since.is_none() {
+            self.since = Some(now);
+            changed = true;
+        }
+        if five_hour.is_some() && five_hour != self.last_five_hour {
+            self.last_five_hour = five_hour;
+            changed = true;
+        }
+        if weekly.is_some() && weekly != self.last_weekly {
+            self.last_weekly = weekly;
+            changed = true;
+        }
+        if !consumed.is_zero() {
+            self.workspaces
+                .entry(workspace_key(input))
+                .or_default()
+                .add(&consumed);
+            self.models
+                .entry(model_key(input))
+                .or_default()
+                .add(&consumed);
+            changed = true;
+        }
+        changed
+    }
+}
+
+/// Computes the delta between an optional current and previous snapshot.
+fn delta(current: Option<QuotaSnapshot>, previous: Option<QuotaSnapshot>) -> (u64, u64) {
+    match (current, previous) {
+        (Some(cur), Some(prev)) => cur.delta_since(&prev),
+        _ => (0, 0),
+    }
+}
+
+/// Returns the ledger key for the active workspace.
+fn workspace_key(input: &InputData) -> String {
+    input
+        .workspace
+        .as_ref()
+        .and_then(|w| w.current_dir.clone())
+        .filter(|dir| !dir.is_empty())
+        .unwrap_or_else(|| UNKNOWN_KEY.to_string())
+}
+
+/// Returns the ledger key for the active model.
+fn model_key(input: &InputData) -> String {
+    input
+        .model
+        .as_ref()
+        .map(|m| m.id.clone())
+        .filter(|id| !id.is_empty())
+        .unwrap_or_else(|| UNKNOWN_KEY.to_string())
+}
+
+/// Loads the ledger at `path`, records the fetch, and saves it back if changed.
+///
+/// # Errors
+///
+/// Returns an error if the updated ledger cannot be written.
+pub fn record_at(path: &Path, stats: &UsageStats, input: &InputData) -> anyhow::Result<()> {
+    let mut ledger: AttributionLedger = crate::state::store::load(path);
+    if ledger.record(stats, input, crate::util::unix_now()) {
+        crate::state::store::save(path, &ledger)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quota(percentage: u8, used: i64, reset_at: i64) -> QuotaUsage {
+        QuotaUsage {
+            used,
+            limit: 100_000,
+            percentage,
+            time_window: "5h".to_string(),
+            reset_at: Some(reset_at),
+        }
+    }
+
+    fn stats(five_hour: u8, weekly: u8, reset_at: i64) -> UsageStats {
+        UsageStats {
+            token_usage: Some(quota(five_hour, i64::from(five_hour) * 1000, reset_at)),
+            weekly_usage: Some(quota(weekly, i64::from(weekly) * 5000, reset_at + 1)),
+            mcp_usage: None,
+        }
+    }
+
+    fn input(dir: &str, model: &str) -> InputData {
+        serde_json::from_value(serde_json::json!({
+            "model": { "id": model },
+            "workspace": { "current_dir": dir }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_delta_within_window() {
+        let prev = QuotaSnapshot {
+            percentage: 10,
+            used: 1000,
+            reset_at: Some(100),
+        };
+        let cur = QuotaSnapshot {
+            percentage: 15,
+            used: 1500,
+            reset_at: Some(100),
+        };
+        assert_eq!(cur.delta_since(&prev), (5, 500));
+    }
+
+    #[test]
+    fn test_delta_after_window_reset() {
+        let prev = QuotaSnapshot {
+            percentage: 90,
+            used: 9000,
+            reset_at: Some(100),
+        };
+        let cur = QuotaSnapshot {
+            percentage: 3,
+            used: 300,
+            reset_at: Some(200),
+        };
+        assert_eq!(cur.delta_since(&prev), (3, 300));
+    }
+
+    #[test]
+    fn test_delta_decrease_is_ignored() {
+        let prev = QuotaSnapshot {
+            percentage: 20,
+            used: 2000,
+            reset_at: Some(100),
+        };
+        let cur = QuotaSnapshot {
+            percentage: 18,
+            used: 1800,
+            reset_at: Some(100),
+        };
+        assert_eq!(cur.delta_since(&prev), (0, 0));
+    }
+
+    #[test]
+    fn test_first_record_sets_baseline_only() {
+        let mut ledger = AttributionLedger::default();
+        assert!(ledger.record(&stats(10, 5, 1000), &input("/a", "glm-5"), 1));
+        assert!(ledger.workspaces.is_empty());
+        assert_eq!(ledger.since, Some(1));
+        assert_eq!(ledger.last_five_hour.map(|s| s.percentage), Some(10));
+    }
+
+    #[test]
+    fn test_record_attributes_to_workspace_and_model() {
+        let mut ledger = AttributionLedger::default();
+        ledger.record(&stats(10, 5, 1000), &input("/a", "glm-5"), 1);
+        ledger.record(&stats(14, 6, 1000), &input("/a", "glm-5"), 2);
+        ledger.record(&stats(20, 8, 1000), &input("/b", "glm-4.7"), 3);
+
+        let a = ledger.workspaces["/a"];
+        assert_eq!(a.five_hour_pct, 4);
+        assert_eq!(a.weekly_pct, 1);
+        assert_eq!(a.five_hour_used, 4000);
+        let b = ledger.workspaces["/b"];
+        assert_eq!(b.five_hour_pct, 6);
+        assert_eq!(b.weekly_pct, 2);
+        assert_eq!(ledger.models["glm-5"].five_hour_pct, 4);
+        assert_eq!(ledger.models["glm-4.7"].five_hour_pct, 6);
+    }
+
+    #[test]
+    fn test_record_unchanged_stats_is_noop() {
+        let mut ledger = AttributionLedger::default();
+        ledger.record(&stats(10, 5, 1000), &input("/a", "glm-5"), 1);
+        assert!(!ledger.record(&stats(10, 5, 1000), &input("/a", "glm-5"), 2));
+    }
+
+    #[test]
+    fn test_record_missing_workspace_uses_unknown_key() {
+        let mut ledger = AttributionLedger::default();
+        let empty: InputData = serde_json::from_str("{}").unwrap();
+        ledger.record(&stats(10, 5, 1000), &empty, 1);
+        ledger.record(&stats(12, 5, 1000), &empty, 2);
+        assert_eq!(ledger.workspaces[UNKNOWN_KEY].five_hour_pct, 2);
+        assert_eq!(ledger.models[UNKNOWN_KEY].five_hour_pct, 2);
+    }
+
+    #[test]
+    fn test_record_at_persists_ledger() {
+        let dir = std::env::temp_dir().join("glm-plan-usage-test-attribution-record");
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = dir.join(LEDGER_FILE);
+
+        record_at(&path, &stats(10, 5, 1000), &input("/a", "glm-5")).unwrap();
+        record_at(&path, &stats(13, 5, 1000), &input("/a", "glm-5")).unwrap();
+
+        let ledger: AttributionLedger = crate::state::store::load(&path);
+        assert_eq!(ledger.workspaces["/a"].five_hour_pct, 3);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,148 @@
+//! Daily usage tracking for the `[budget]` daily window.
+//!
+//! The API only reports 5-hour and weekly quotas, so daily usage is the
+//! weekly percentage consumed since the start of the local calendar day.
+
+use crate::api::QuotaUsage;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use time::{OffsetDateTime, UtcOffset};
+
+/// State file name for daily budget tracking.
+pub const BUDGET_FILE: &str = "budget.json";
+
+/// Weekly quota usage observed during the current local day.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DailyUsage {
+    /// Local day number the usage belongs to.
+    #[serde(default)]
+    pub day: Option<i64>,
+    /// Weekly percentage at the start of the day or of the current window.
+    #[serde(default)]
+    pub start_percentage: u8,
+    /// Last observed weekly percentage.
+    #[serde(default)]
+    pub last_percentage: u8,
+    /// Reset timestamp of the weekly window being tracked.
+    #[serde(default)]
+    pub reset_at: Option<i64>,
+    /// Points used today in weekly windows that have since reset.
+    #[serde(default)]
+    pub carried: u8,
+}
+
+impl DailyUsage {
+    /// Folds a weekly quota observation in and returns the points used today.
+    pub fn observe(&mut self, weekly: &QuotaUsage, day: i64) -> u8 {
+        if self.day != Some(day) {
+            *self = DailyUsage {
+                day: Some(day),
+                start_percentage: weekly.percentage,
+                last_percentage: weekly.percentage,
+                reset_at: weekly.reset_at,
+                carried: 0,
+            };
+        } else if self.reset_at != weekly.reset_at {
+            // The weekly window rolled over mid-day: keep what was used
+            // before it and count the new window from zero.
+            self.carried = self.used();
+            self.start_percentage = 0;
+            self.reset_at = weekly.reset_at;
+        }
+        self.last_percentage = weekly.percentage;
+        self.used()
+    }
+
+    /// Returns the points of the weekly quota used today.
+    #[must_use]
+    pub fn used(&self) -> u8 {
+        self.carried
+            .saturating_add(self.last_percentage.saturating_sub(self.start_percentage))
+    }
+}
+
+/// Returns the local calendar day number of a Unix timestamp.
+#[must_use]
+pub fn local_day(timestamp: i64) -> i64 {
+    let offset = OffsetDateTime::from_unix_timestamp(timestamp)
+        .ok()
+        .and_then(|at| UtcOffset::local_offset_at(at).ok())
+        .map_or(0, |offset| i64::from(offset.whole_seconds()));
+    crate::transcript::day_of(timestamp + offset)
+}
+
+/// Returns the points used today with the observation folded in, without saving it.
+#[must_use]
+pub fn used_at(path: &Path, weekly: &QuotaUsage, now: i64) -> u8 {
+    let mut daily: DailyUsage = crate::state::store::load(path);
+    daily.observe(weekly, local_day(now))
+}
+
+/// Loads the daily usage at `path`, records the observation, and saves it if changed.
+///
+/// # Errors
+///
+/// Returns an error if the updated state cannot be written.
+pub fn record_at(path: &Path, weekly: &QuotaUsage, now: i64) -> anyhow::Result<()> {
+    let mut daily: DailyUsage = crate::state::store::load(path);
+    let previous = daily.clone();
+    daily.observe(weekly, local_day(now));
+    if daily != previous {
+        crate::state::store::save(path, &daily)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn weekly(percentage: u8, reset_at: i64) -> QuotaUsage {
+        QuotaUsage {
+            used: i64::from(percentage),
+            limit: 100,
+            percentage,
+            time_window: "7d".to_string(),
+            reset_at: Some(reset_at),
+        }
+    }
+
+    #[test]
+    fn test_first_observation_starts_the_day() {
+        let mut daily = DailyUsage::default();
+        assert_eq!(daily.observe(&weekly(40, 1_000), 10), 0);
+        assert_eq!(daily.observe(&weekly(46, 1_000), 10), 6);
+    }
+
+    #[test]
+    fn test_new_day_resets_usage() {
+        let mut daily = DailyUsage::default();
+        daily.observe(&weekly(40, 1_000), 10);
+        daily.observe(&weekly(50, 1_000), 10);
+        assert_eq!(daily.observe(&weekly(52, 1_000), 11), 0);
+    }
+
+    #[test]
+    fn test_window_reset_carries_usage() {
+        let mut daily = DailyUsage::default();
+        daily.observe(&weekly(90, 1_000), 10);
+        daily.observe(&weekly(95, 1_000), 10);
+        assert_eq!(daily.observe(&weekly(3, 2_000), 10), 8);
+    }
+
+    #[test]
+    fn test_record_at_persists_usage() {
+        let dir = std::env::temp_dir().join("glm-plan-usage-test-budget-observe");
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = dir.join(BUDGET_FILE);
+
+        record_at(&path, &weekly(20, 1_000), 100).unwrap();
+        assert_eq!(used_at(&path, &weekly(25, 1_000), 200), 5);
+        // Reading does not record the observation.
+        assert_eq!(used_at(&path, &weekly(22, 1_000), 200), 2);
+        record_at(&path, &weekly(25, 1_000), 200).unwrap();
+        assert_eq!(used_at(&path, &weekly(25, 1_000), 300), 5);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
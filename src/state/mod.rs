@@ -0,0 +1,123 @@
+//! Persisted state shared between invocations.
+//!
+//! Every invocation is a fresh process, so anything that must outlive a
+//! single status line render is stored as JSON under
+//! `~/.claude/glm-plan-usage/state/`.
+
+mod attribution;
+mod budget;
+mod session;
+mod spike;
+mod store;
+
+#[doc(inline)]
+#[allow(unused_imports, reason = "re-exported for public API")]
+pub use attribution::{AttributionLedger, Consumption, QuotaSnapshot, LEDGER_FILE};
+#[doc(inline)]
+#[allow(unused_imports, reason = "re-exported for public API")]
+pub use budget::{local_day, DailyUsage, BUDGET_FILE};
+#[doc(inline)]
+#[allow(unused_imports, reason = "re-exported for public API")]
+pub use session::{SessionEntry, SessionStore, SESSIONS_FILE};
+#[doc(inline)]
+#[allow(unused_imports, reason = "re-exported for public API")]
+pub use spike::{Spike, SpikeDetector, UsageSample, SPIKE_FILE};
+#[doc(inline)]
+#[allow(unused_imports, reason = "re-exported for public API")]
+pub use store::{load, save, state_dir, state_path};
+
+use crate::api::UsageStats;
+use crate::config::{Config, InputData};
+
+/// Records quota consumption for the active workspace and model.
+///
+/// Does nothing when attribution tracking is disabled.
+///
+/// # Errors
+///
+/// Returns an error if the attribution ledger cannot be written.
+pub fn record_attribution(
+    config: &Config,
+    stats: &UsageStats,
+    input: &InputData,
+) -> anyhow::Result<()> {
+    if !config.tracking.attribution {
+        return Ok(());
+    }
+    let Some(path) = state_path(LEDGER_FILE) else {
+        return Ok(());
+    };
+    attribution::record_at(&path, stats, input)
+}
+
+/// Records a fetch for the given session and returns its consumption so far.
+///
+/// Returns `None` if the state directory is unavailable or cannot be written.
+#[must_use]
+pub fn observe_session(
+    config: &Config,
+    session_id: &str,
+    stats: &UsageStats,
+) -> Option<SessionEntry> {
+    let path = state_path(SESSIONS_FILE)?;
+    session::observe_at(&path, session_id, stats, config.tracking.session_ttl_secs()).ok()
+}
+
+/// Returns the ongoing spike of the 5-hour quota, if any.
+///
+/// Read-only: the observation is recorded separately by [`record_spike`].
+#[must_use]
+pub fn current_spike(config: &Config, stats: &UsageStats) -> Option<Spike> {
+    if !config.spike.enabled {
+        return None;
+    }
+    let quota = stats.token_usage.as_ref()?;
+    let detector: SpikeDetector = load(&state_path(SPIKE_FILE)?);
+    detector.evaluate(quota, &config.spike, crate::util::unix_now())
+}
+
+/// Records a 5-hour quota observation and returns the spike if one just started.
+///
+/// Does nothing when spike detection is disabled.
+///
+/// # Errors
+///
+/// Returns an error if the detector state cannot be written.
+pub fn record_spike(config: &Config, stats: &UsageStats) -> anyhow::Result<Option<Spike>> {
+    if !config.spike.enabled {
+        return Ok(None);
+    }
+    let (Some(quota), Some(path)) = (stats.token_usage.as_ref(), state_path(SPIKE_FILE)) else {
+        return Ok(None);
+    };
+    spike::record_at(&path, quota, &config.spike, crate::util::unix_now())
+}
+
+/// Returns the points of the weekly quota used today.
+///
+/// Read-only: the observation is recorded separately by [`record_daily_usage`].
+/// Returns `None` if the weekly quota is not reported or the state
+/// directory is unavailable.
+#[must_use]
+pub fn daily_usage(stats: &UsageStats) -> Option<u8> {
+    let weekly = stats.weekly_usage.as_ref()?;
+    let path = state_path(BUDGET_FILE)?;
+    Some(budget::used_at(&path, weekly, crate::util::unix_now()))
+}
+
+/// Records a weekly quota observation for the daily budget.
+///
+/// Does nothing when no daily budget is set.
+///
+/// # Errors
+///
+/// Returns an error if the daily usage state cannot be written.
+pub fn record_daily_usage(config: &Config, stats: &UsageStats) -> anyhow::Result<()> {
+    if config.budget.daily.is_none() {
+        return Ok(());
+    }
+    let (Some(weekly), Some(path)) = (stats.weekly_usage.as_ref(), state_path(BUDGET_FILE)) else {
+        return Ok(());
+    };
+    budget::record_at(&path, weekly, crate::util::unix_now())
+}
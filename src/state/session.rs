@@ -0,0 +1,195 @@
+//! Per-session quota consumption tracking.
+//!
+//! The first time a session is seen, the current quota usage becomes its
+//! baseline. Every later fetch adds the consumption since the previous one,
+//! so a quota window rolling over mid-session is handled correctly.
+
+use crate::api::UsageStats;
+use crate::state::attribution::QuotaSnapshot;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// State file name for per-session consumption.
+pub const SESSIONS_FILE: &str = "sessions.json";
+
+/// Quota consumption recorded for a single Claude Code session.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionEntry {
+    /// Timestamp (seconds) when the session was first seen.
+    #[serde(default)]
+    pub started_at: i64,
+    /// Timestamp (seconds) when the session was last seen.
+    #[serde(default)]
+    pub last_seen: i64,
+    /// Last observed 5-hour quota.
+    #[serde(default)]
+    pub last_five_hour: Option<QuotaSnapshot>,
+    /// Last observed weekly quota.
+    #[serde(default)]
+    pub last_weekly: Option<QuotaSnapshot>,
+    /// Percentage points of the 5-hour quota consumed by this session.
+    #[serde(default)]
+    pub five_hour_pct: u64,
+    /// Percentage points of the weekly quota consumed by this session.
+    #[serde(default)]
+    pub weekly_pct: u64,
+}
+
+impl SessionEntry {
+    /// Folds a new fetch into the session totals.
+    fn observe(&mut self, stats: &UsageStats, now: i64) {
+        let five_hour = stats.token_usage.as_ref().map(QuotaSnapshot::from);
+        let weekly = stats.weekly_usage.as_ref().map(QuotaSnapshot::from);
+
+        if let (Some(cur), Some(prev)) = (five_hour, self.last_five_hour) {
+            self.five_hour_pct += cur.delta_since(&prev).0;
+        }
+        if let (Some(cur), Some(prev)) = (weekly, self.last_weekly) {
+            self.weekly_pct += cur.delta_since(&prev).0;
+        }
+
+        self.last_five_hour = five_hour.or(self.last_five_hour);
+        self.last_weekly = weekly.or(self.last_weekly);
+        self.last_seen = now;
+    }
+}
+
+/// Persisted per-session consumption, keyed by session ID.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SessionStore {
+    /// Tracked sessions.
+    #[serde(default)]
+    pub sessions: BTreeMap<String, SessionEntry>,
+}
+
+impl SessionStore {
+    /// Records a fetch for `session_id` and returns its updated entry.
+    ///
+    /// Sessions idle for longer than `ttl_secs` are dropped first.
+    pub fn observe(
+        &mut self,
+        session_id: &str,
+        stats: &UsageStats,
+        now: i64,
+        ttl_secs: i64,
+    ) -> SessionEntry {
+        self.expire(now, ttl_secs);
+        let entry = self
+            .sessions
+            .entry(session_id.to_string())
+            .or_insert_with(|| SessionEntry {
+                started_at: now,
+                ..SessionEntry::default()
+            });
+        entry.observe(stats, now);
+        entry.clone()
+    }
+
+    /// Drops sessions not seen within `ttl_secs` of `now`.
+    fn expire(&mut self, now: i64, ttl_secs: i64) {
+        self.sessions
+            .retain(|_, entry| now.saturating_sub(entry.last_seen) <= ttl_secs);
+    }
+}
+
+/// Loads the session store at `path`, records the fetch, and saves it back.
+///
+/// # Errors
+///
+/// Returns an error if the updated store cannot be written.
+pub fn observe_at(
+    path: &Path,
+    session_id: &str,
+    stats: &UsageStats,
+    ttl_secs: i64,
+) -> anyhow::Result<SessionEntry> {
+    let mut store: SessionStore = crate::state::store::load(path);
+    let entry = store.observe(session_id, stats, crate::util::unix_now(), ttl_secs);
+    crate::state::store::save(path, &store)?;
+    Ok(entry)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::QuotaUsage;
+
+    fn quota(percentage: u8, reset_at: i64) -> QuotaUsage {
+        QuotaUsage {
+            used: i64::from(percentage) * 1000,
+            limit: 100_000,
+            percentage,
+            time_window: "5h".to_string(),
+            reset_at: Some(reset_at),
+        }
+    }
+
+    fn stats(five_hour: u8, weekly: u8, reset_at: i64) -> UsageStats {
+        UsageStats {
+            token_usage: Some(quota(five_hour, reset_at)),
+            weekly_usage: Some(quota(weekly, 9_999)),
+            mcp_usage: None,
+        }
+    }
+
+    #[test]
+    fn test_first_observation_is_baseline() {
+        let mut store = SessionStore::default();
+        let entry = store.observe("s1", &stats(40, 10, 100), 1_000, 3600);
+        assert_eq!(entry.five_hour_pct, 0);
+        assert_eq!(entry.weekly_pct, 0);
+        assert_eq!(entry.started_at, 1_000);
+    }
+
+    #[test]
+    fn test_consumption_accumulates() {
+        let mut store = SessionStore::default();
+        store.observe("s1", &stats(40, 10, 100), 1_000, 3600);
+        store.observe("s1", &stats(45, 11, 100), 1_010, 3600);
+        let entry = store.observe("s1", &stats(52, 13, 100), 1_020, 3600);
+        assert_eq!(entry.five_hour_pct, 12);
+        assert_eq!(entry.weekly_pct, 3);
+    }
+
+    #[test]
+    fn test_consumption_survives_window_reset() {
+        let mut store = SessionStore::default();
+        store.observe("s1", &stats(95, 10, 100), 1_000, 3600);
+        store.observe("s1", &stats(98, 10, 100), 1_010, 3600);
+        let entry = store.observe("s1", &stats(4, 10, 200), 1_020, 3600);
+        assert_eq!(entry.five_hour_pct, 7);
+    }
+
+    #[test]
+    fn test_sessions_are_independent() {
+        let mut store = SessionStore::default();
+        store.observe("s1", &stats(10, 10, 100), 1_000, 3600);
+        store.observe("s2", &stats(15, 10, 100), 1_005, 3600);
+        let s1 = store.observe("s1", &stats(20, 10, 100), 1_010, 3600);
+        assert_eq!(s1.five_hour_pct, 10);
+        assert_eq!(store.sessions["s2"].five_hour_pct, 0);
+    }
+
+    #[test]
+    fn test_idle_sessions_expire() {
+        let mut store = SessionStore::default();
+        store.observe("old", &stats(10, 10, 100), 1_000, 3600);
+        store.observe("new", &stats(10, 10, 100), 10_000, 3600);
+        assert!(!store.sessions.contains_key("old"));
+        assert!(store.sessions.contains_key("new"));
+    }
+
+    #[test]
+    fn test_observe_at_persists() {
+        let dir = std::env::temp_dir().join("glm-plan-usage-test-session-observe");
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = dir.join(SESSIONS_FILE);
+
+        observe_at(&path, "s1", &stats(10, 5, 100), 3600).unwrap();
+        let entry = observe_at(&path, "s1", &stats(16, 5, 100), 3600).unwrap();
+        assert_eq!(entry.five_hour_pct, 6);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,248 @@
+//! Usage spike detection for the 5-hour quota.
+//!
+//! Successive quota samples are kept for a rolling window. The consumption
+//! rate over the most recent minutes is compared with the rate over the
+//! older history, so a runaway session stands out against normal use.
+
+use crate::api::QuotaUsage;
+use crate::config::SpikeConfig;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// State file name for spike detection samples.
+pub const SPIKE_FILE: &str = "spike.json";
+
+/// Minimum spacing between stored samples, in seconds.
+const SAMPLE_INTERVAL_SECS: i64 = 30;
+
+/// Minimum span of the recent window before a rate is computed, in seconds.
+const MIN_SPAN_SECS: i64 = 60;
+
+/// A single observation of the 5-hour quota.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UsageSample {
+    /// Timestamp (seconds) of the observation.
+    pub at: i64,
+    /// Usage percentage at the time of the observation.
+    pub percentage: u8,
+    /// Reset timestamp in seconds, identifying the quota window.
+    #[serde(default)]
+    pub reset_at: Option<i64>,
+}
+
+impl UsageSample {
+    fn new(quota: &QuotaUsage, now: i64) -> Self {
+        Self {
+            at: now,
+            percentage: quota.percentage,
+            reset_at: quota.reset_at,
+        }
+    }
+
+    /// Returns the consumption rate in percentage points per minute since `earlier`.
+    fn rate_since(&self, earlier: &UsageSample) -> f64 {
+        let points = f64::from(self.percentage.saturating_sub(earlier.percentage));
+        #[expect(
+            clippy::cast_precision_loss,
+            reason = "sample spans are far below f64 precision limits"
+        )]
+        let minutes = (self.at - earlier.at) as f64 / 60.0;
+        points / minutes
+    }
+}
+
+/// A detected consumption spike.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Spike {
+    /// Recent consumption rate in percentage points per minute.
+    pub rate: f64,
+    /// Baseline consumption rate, if enough history is available.
+    pub baseline: Option<f64>,
+    /// Current usage percentage.
+    pub percentage: u8,
+    /// Reset timestamp (seconds) of the quota window.
+    pub reset_at: Option<i64>,
+}
+
+/// Persisted spike detector state.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpikeDetector {
+    /// Samples of the current quota window, oldest first.
+    #[serde(default)]
+    pub samples: Vec<UsageSample>,
+    /// Whether the last recorded observation was a spike.
+    #[serde(default)]
+    pub active: bool,
+}
+
+impl SpikeDetector {
+    /// Evaluates the current quota against the stored samples.
+    ///
+    /// Returns the spike if the recent rate is at least `min_rate` and
+    /// `factor` times the baseline. Without enough history for a baseline,
+    /// only `min_rate` applies.
+    #[must_use]
+    pub fn evaluate(&self, quota: &QuotaUsage, config: &SpikeConfig, now: i64) -> Option<Spike> {
+        let current = UsageSample::new(quota, now);
+        let history: Vec<&UsageSample> = self
+            .samples
+            .iter()
+            .filter(|s| s.reset_at == current.reset_at && s.at < now)
+            .filter(|s| now - s.at <= config.baseline_secs())
+            .collect();
+
+        let reference = history
+            .iter()
+            .find(|s| now - s.at <= config.window_secs())?;
+        if now - reference.at < MIN_SPAN_SECS {
+            return None;
+        }
+        let rate = current.rate_since(reference);
+
+        let oldest = history.first()?;
+        let baseline = (reference.at - oldest.at >= config.window_secs())
+            .then(|| reference.rate_since(oldest));
+
+        let spiking = rate >= config.min_rate && baseline.is_none_or(|b| rate >= b * config.factor);
+        spiking.then_some(Spike {
+            rate,
+            baseline,
+            percentage: quota.percentage,
+            reset_at: quota.reset_at,
+        })
+    }
+
+    /// Records an observation and returns the spike if one just started.
+    ///
+    /// Samples from other quota windows or older than the baseline are
+    /// dropped, and samples closer together than 30 seconds are skipped.
+    pub fn record(&mut self, quota: &QuotaUsage, config: &SpikeConfig, now: i64) -> Option<Spike> {
+        let spike = self.evaluate(quota, config, now);
+        let started = spike.filter(|_| !self.active);
+        self.active = spike.is_some();
+
+        let sample = UsageSample::new(quota, now);
+        self.samples
+            .retain(|s| s.reset_at == sample.reset_at && now - s.at <= config.baseline_secs());
+        let due = self
+            .samples
+            .last()
+            .is_none_or(|last| now - last.at >= SAMPLE_INTERVAL_SECS);
+        if due {
+            self.samples.push(sample);
+        }
+        started
+    }
+}
+
+/// Loads the detector at `path`, records the observation, and saves it if changed.
+///
+/// # Errors
+///
+/// Returns an error if the updated state cannot be written.
+pub fn record_at(
+    path: &Path,
+    quota: &QuotaUsage,
+    config: &SpikeConfig,
+    now: i64,
+) -> anyhow::Result<Option<Spike>> {
+    let mut detector: SpikeDetector = crate::state::store::load(path);
+    let previous = detector.clone();
+    let started = detector.record(quota, config, now);
+    if detector != previous {
+        crate::state::store::save(path, &detector)?;
+    }
+    Ok(started)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quota(percentage: u8, reset_at: i64) -> QuotaUsage {
+        QuotaUsage {
+            used: i64::from(percentage),
+            limit: 100,
+            percentage,
+            time_window: "5h".to_string(),
+            reset_at: Some(reset_at),
+        }
+    }
+
+    /// Records one sample per minute from `start` with the given percentages.
+    fn feed(detector: &mut SpikeDetector, start: i64, percentages: &[u8]) -> Vec<Option<Spike>> {
+        let config = SpikeConfig::default();
+        percentages
+            .iter()
+            .zip(0..)
+            .map(|(&pct, i)| detector.record(&quota(pct, 100_000), &config, start + i * 60))
+            .collect()
+    }
+
+    #[test]
+    fn test_steady_usage_is_not_a_spike() {
+        let mut detector = SpikeDetector::default();
+        let pcts: Vec<u8> = (0..40).map(|i| i / 4).collect();
+        let results = feed(&mut detector, 0, &pcts);
+        assert!(results.iter().all(Option::is_none));
+        assert!(!detector.active);
+    }
+
+    #[test]
+    fn test_burst_against_baseline_is_flagged_once() {
+        let mut detector = SpikeDetector::default();
+        let mut pcts: Vec<u8> = (0..30).map(|i| i / 10).collect();
+        pcts.extend((2..=9).map(|i| i * 3));
+        let results = feed(&mut detector, 0, &pcts);
+        let started: Vec<&Spike> = results.iter().flatten().collect();
+        assert_eq!(started.len(), 1);
+        assert!(started[0].rate >= 2.0);
+        assert!(started[0].baseline.is_some());
+        assert!(detector.active);
+    }
+
+    #[test]
+    fn test_burst_without_history_uses_min_rate() {
+        let mut detector = SpikeDetector::default();
+        let results = feed(&mut detector, 0, &[10, 13, 16]);
+        assert!(results[1].is_some());
+        assert!(results[1].unwrap().baseline.is_none());
+    }
+
+    #[test]
+    fn test_window_reset_clears_samples() {
+        let mut detector = SpikeDetector::default();
+        feed(&mut detector, 0, &[10, 20]);
+        let config = SpikeConfig::default();
+        let spike = detector.record(&quota(25, 200_000), &config, 180);
+        assert!(spike.is_none());
+        assert_eq!(detector.samples.len(), 1);
+    }
+
+    #[test]
+    fn test_samples_are_spaced() {
+        let mut detector = SpikeDetector::default();
+        let config = SpikeConfig::default();
+        detector.record(&quota(1, 100_000), &config, 0);
+        detector.record(&quota(1, 100_000), &config, 10);
+        assert_eq!(detector.samples.len(), 1);
+    }
+
+    #[test]
+    fn test_record_at_persists_samples() {
+        let dir = std::env::temp_dir().join("glm-plan-usage-test-spike-record");
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = dir.join(SPIKE_FILE);
+        let config = SpikeConfig::default();
+
+        record_at(&path, &quota(10, 100_000), &config, 0).unwrap();
+        let started = record_at(&path, &quota(14, 100_000), &config, 60).unwrap();
+        assert!(started.is_some());
+
+        let saved: SpikeDetector = crate::state::store::load(&path);
+        assert_eq!(saved.samples.len(), 2);
+        assert!(saved.active);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,92 @@
+//! JSON state file storage.
+//!
+//! State files live next to the config file and are always optional:
+//! a missing or corrupt file simply yields the default state.
+
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Returns the directory holding persisted state files.
+///
+/// Returns `None` if the home directory cannot be determined.
+#[must_use]
+pub fn state_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".claude").join("glm-plan-usage").join("state"))
+}
+
+/// Returns the path of a named state file inside [`state_dir`].
+#[must_use]
+pub fn state_path(file_name: &str) -> Option<PathBuf> {
+    state_dir().map(|dir| dir.join(file_name))
+}
+
+/// Loads a JSON state file, falling back to the default value.
+///
+/// Missing, unreadable, or unparsable files are treated as empty state.
+#[must_use]
+pub fn load<T: DeserializeOwned + Default>(path: &Path) -> T {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+/// Saves a value as JSON, creating the parent directory if needed.
+///
+/// # Errors
+///
+/// Returns an error if the directory cannot be created or the file cannot be written.
+pub fn save<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create state directory: {}", parent.display()))?;
+    }
+    let content = serde_json::to_string_pretty(value).context("Failed to serialize state")?;
+    crate::util::atomic_write(path, &content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_load_missing_file_returns_default() {
+        let path = std::env::temp_dir().join("glm-plan-usage-test-state-missing.json");
+        let _ = fs::remove_file(&path);
+        let value: BTreeMap<String, u32> = load(&path);
+        assert!(value.is_empty());
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let dir = std::env::temp_dir().join("glm-plan-usage-test-state-roundtrip");
+        let _ = fs::remove_dir_all(&dir);
+        let path = dir.join("nested").join("state.json");
+
+        let mut value = BTreeMap::new();
+        value.insert("a".to_string(), 1_u32);
+        save(&path, &value).unwrap();
+
+        let loaded: BTreeMap<String, u32> = load(&path);
+        assert_eq!(loaded, value);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_load_corrupt_file_returns_default() {
+        let dir = std::env::temp_dir().join("glm-plan-usage-test-state-corrupt");
+        let _ = fs::create_dir_all(&dir);
+        let path = dir.join("state.json");
+        fs::write(&path, "not json").unwrap();
+
+        let value: BTreeMap<String, u32> = load(&path);
+        assert!(value.is_empty());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
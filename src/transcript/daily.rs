@@ -0,0 +1,100 @@
+//! Daily token ledger.
+//!
+//! Transcript accounting is per session and eventually discarded, so
+//! tokens are also added to per-day, per-model buckets that outlive the
+//! session and back weekly and per-plan-period summaries.
+
+use crate::transcript::types::TokenTotals;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// State file name for the daily token ledger.
+pub const DAILY_FILE: &str = "daily_tokens.json";
+
+/// Number of days kept in the ledger.
+const RETENTION_DAYS: i64 = 62;
+
+/// Seconds in a day.
+const SECS_PER_DAY: i64 = 86_400;
+
+/// Returns the UTC day number of a Unix timestamp.
+#[must_use]
+pub fn day_of(timestamp: i64) -> i64 {
+    timestamp.div_euclid(SECS_PER_DAY)
+}
+
+/// Token totals per model, bucketed by UTC day number.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DailyLedger {
+    /// Per-model totals keyed by UTC day number.
+    #[serde(default)]
+    pub days: BTreeMap<i64, BTreeMap<String, TokenTotals>>,
+}
+
+impl DailyLedger {
+    /// Adds per-model token counts to the bucket for `day`.
+    ///
+    /// Buckets older than the retention window are dropped.
+    pub fn add(&mut self, day: i64, models: &BTreeMap<String, TokenTotals>) {
+        let bucket = self.days.entry(day).or_default();
+        for (model, tokens) in models {
+            bucket.entry(model.clone()).or_default().add(tokens);
+        }
+        self.days.retain(|&d, _| d > day - RETENTION_DAYS);
+    }
+
+    /// Returns per-model totals for all days from `first_day` onwards.
+    #[must_use]
+    pub fn since(&self, first_day: i64) -> BTreeMap<String, TokenTotals> {
+        let mut totals: BTreeMap<String, TokenTotals> = BTreeMap::new();
+        for models in self.days.range(first_day..).map(|(_, models)| models) {
+            for (model, tokens) in models {
+                totals.entry(model.clone()).or_default().add(tokens);
+            }
+        }
+        totals
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn models(model: &str, input: u64) -> BTreeMap<String, TokenTotals> {
+        BTreeMap::from([(
+            model.to_string(),
+            TokenTotals {
+                input,
+                ..TokenTotals::default()
+            },
+        )])
+    }
+
+    #[test]
+    fn test_day_of() {
+        assert_eq!(day_of(0), 0);
+        assert_eq!(day_of(86_399), 0);
+        assert_eq!(day_of(86_400), 1);
+        assert_eq!(day_of(-1), -1);
+    }
+
+    #[test]
+    fn test_add_and_since() {
+        let mut ledger = DailyLedger::default();
+        ledger.add(100, &models("glm-5", 10));
+        ledger.add(100, &models("glm-5", 5));
+        ledger.add(102, &models("glm-4.7", 7));
+
+        assert_eq!(ledger.since(100)["glm-5"].input, 15);
+        assert_eq!(ledger.since(101).len(), 1);
+        assert_eq!(ledger.since(101)["glm-4.7"].input, 7);
+    }
+
+    #[test]
+    fn test_add_prunes_old_days() {
+        let mut ledger = DailyLedger::default();
+        ledger.add(1, &models("glm-5", 10));
+        ledger.add(1 + RETENTION_DAYS, &models("glm-5", 10));
+        assert_eq!(ledger.days.len(), 1);
+    }
+}
@@ -0,0 +1,59 @@
+//! Local token accounting from Claude Code session transcripts.
+//!
+//! Independent of the GLM monitor API: token counts are summed from the
+//! usage blocks Claude Code records for every assistant message.
+
+mod daily;
+mod reader;
+mod types;
+mod value;
+
+#[doc(inline)]
+#[allow(unused_imports, reason = "re-exported for public API")]
+pub use daily::{day_of, DailyLedger, DAILY_FILE};
+#[doc(inline)]
+#[allow(unused_imports, reason = "re-exported for public API")]
+pub use reader::{read_incremental, stored_at, TranscriptStore, TRANSCRIPTS_FILE};
+#[doc(inline)]
+#[allow(unused_imports, reason = "re-exported for public API")]
+pub use types::{TokenTotals, TranscriptState};
+#[doc(inline)]
+#[allow(unused_imports, reason = "re-exported for public API")]
+pub use value::{metered_cost, summarize, ModelValue, ValueSummary};
+
+use crate::config::{Config, InputData};
+use std::path::Path;
+
+/// Reads the active session transcript and returns its accounting so far.
+///
+/// With transcript tracking disabled the transcript is not read, and the
+/// accounting last recorded for it (if any) is returned instead.
+///
+/// Returns `None` if Claude Code did not provide a transcript path or the
+/// transcript cannot be read.
+#[must_use]
+pub fn observe(config: &Config, input: &InputData) -> Option<TranscriptState> {
+    let transcript = input.transcript_path.as_deref().filter(|p| !p.is_empty())?;
+    let store_path = crate::state::state_path(TRANSCRIPTS_FILE)?;
+    if !config.tracking.transcripts {
+        return stored_at(&store_path, Path::new(transcript));
+    }
+    let daily_path = crate::state::state_path(DAILY_FILE)?;
+    reader::observe_at(
+        &store_path,
+        &daily_path,
+        Path::new(transcript),
+        crate::util::unix_now(),
+    )
+    .ok()
+}
+
+/// Loads the daily token ledger.
+///
+/// Returns an empty ledger if nothing has been recorded yet.
+#[must_use]
+pub fn load_daily() -> DailyLedger {
+    crate::state::state_path(DAILY_FILE)
+        .map(|path| crate::state::load(&path))
+        .unwrap_or_default()
+}
@@ -0,0 +1,368 @@
+//! Incremental transcript reader.
+//!
+//! Claude Code appends one JSON object per line to the session transcript.
+//! The reader remembers the byte offset it stopped at, so each invocation
+//! only parses the lines appended since the previous one.
+
+use crate::transcript::daily::{day_of, DailyLedger};
+use crate::transcript::types::{TokenTotals, TranscriptEntry, TranscriptState};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Seek, SeekFrom};
+use std::path::Path;
+
+/// State file name for transcript offsets and totals.
+pub const TRANSCRIPTS_FILE: &str = "transcripts.json";
+
+/// Model key used when a message does not report its model.
+const UNKNOWN_MODEL: &str = "(unknown)";
+
+/// Seconds an untouched transcript is remembered.
+///
+/// Forgetting a transcript means re-reading it from the start if it is
+/// ever resumed, which would count its tokens into the daily ledger again,
+/// so this is kept well beyond a typical session.
+const RETENTION_SECS: i64 = 35 * 86_400;
+
+/// Persisted transcript accounting, keyed by transcript path.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TranscriptStore {
+    /// Tracked transcripts.
+    #[serde(default)]
+    pub transcripts: BTreeMap<String, TranscriptState>,
+}
+
+/// Parses lines appended to the transcript since `state.offset`.
+///
+/// A trailing line without a newline is left for the next call, since
+/// Claude Code may still be writing it. If the file shrank, accounting
+/// restarts from the beginning.
+///
+/// # Errors
+///
+/// Returns an error if the transcript cannot be opened or read.
+pub fn read_incremental(path: &Path, state: &mut TranscriptState) -> io::Result<()> {
+    let mut file = File::open(path)?;
+    if file.metadata()?.len() < state.offset {
+        *state = TranscriptState::default();
+    }
+    file.seek(SeekFrom::Start(state.offset))?;
+
+    let mut reader = BufReader::new(file);
+    let mut line = Vec::new();
+    loop {
+        line.clear();
+        let read = reader.read_until(b'\n', &mut line)?;
+        if read == 0 || line.last() != Some(&b'\n') {
+            break;
+        }
+        state.offset += read as u64;
+        apply_line(state, &line);
+    }
+    Ok(())
+}
+
+/// Folds a single transcript line into the accounting state.
+fn apply_line(state: &mut TranscriptState, line: &[u8]) {
+    let Ok(entry) = serde_json::from_slice::<TranscriptEntry>(line) else {
+        return;
+    };
+    if entry.kind.as_deref() != Some("assistant") {
+        return;
+    }
+    let Some(message) = entry.message else {
+        return;
+    };
+    for server in message.tool_use_names().filter_map(mcp_server) {
+        *state.mcp_calls.entry(server.to_string()).or_default() += 1;
+    }
+    let Some(usage) = message.usage.as_ref() else {
+        return;
+    };
+    // One message is written as several lines (one per content block),
+    // each repeating the same usage block.
+    if message.id.is_some() && message.id == state.last_message_id {
+        return;
+    }
+    state.last_message_id.clone_from(&message.id);
+
+    let tokens = TokenTotals::from(usage);
+    state.totals.add(&tokens);
+    state
+        .models
+        .entry(message.model.unwrap_or_else(|| UNKNOWN_MODEL.to_string()))
+        .or_default()
+        .add(&tokens);
+    if !entry.is_sidechain {
+        state.context_tokens = tokens.prompt();
+    }
+}
+
+/// Returns the MCP server name of a tool, e.g. `web-reader` for
+/// `mcp__web-reader__webReader`.
+fn mcp_server(tool_name: &str) -> Option<&str> {
+    let rest = tool_name.strip_prefix("mcp__")?;
+    rest.split_once("__").map(|(server, _)| server)
+}
+
+/// Returns the accounting last recorded for `transcript` without reading it.
+#[must_use]
+pub fn stored_at(store_path: &Path, transcript: &Path) -> Option<TranscriptState> {
+    let mut store: TranscriptStore = crate::state::load(store_path);
+    store
+        .transcripts
+        .remove(transcript.to_string_lossy().as_ref())
+}
+
+/// Loads the store, updates the transcript at `transcript`, and saves it back.
+///
+/// Newly counted tokens are also added to the daily ledger at `daily_path`.
+/// Transcripts not updated within the retention window are dropped.
+///
+/// # Errors
+///
+/// Returns an error if the transcript cannot be read or a state file cannot be written.
+pub fn observe_at(
+    store_path: &Path,
+    daily_path: &Path,
+    transcript: &Path,
+    now: i64,
+) -> anyhow::Result<TranscriptState> {
+    let mut store: TranscriptStore = crate::state::load(store_path);
+    store
+        .transcripts
+        .retain(|_, state| now.saturating_sub(state.updated_at) <= RETENTION_SECS);
+
+    let key = transcript.to_string_lossy().into_owned();
+    let mut state = store.transcripts.remove(&key).unwrap_or_default();
+    let previous = state.clone();
+    read_incremental(transcript, &mut state)?;
+    if state != previous || state.updated_at == 0 {
+        state.updated_at = now;
+    }
+
+    let added = state.models_since(&previous);
+    if !added.is_empty() {
+        let mut ledger: DailyLedger = crate::state::load(daily_path);
+        ledger.add(day_of(now), &added);
+        crate::state::save(daily_path, &ledger)?;
+    }
+
+    store.transcripts.insert(key, state.clone());
+    crate::state::save(store_path, &store)?;
+    Ok(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn assistant_line(id: &str, model: &str, input: u64, output: u64, cache_read: u64) -> String {
+        format!(
+            r#"{{"type":"assistant","message":{{"id":"{id}","model":"{model}","usage":{{"input_tokens":{input},"output_tokens":{output},"cache_creation_input_tokens":0,"cache_read_input_tokens":{cache_read}}}}}}}"#
+        )
+    }
+
+    fn temp_transcript(name: &str, lines: &[String]) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("glm-plan-usage-test-transcript-{name}"));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("session.jsonl");
+        let mut content = lines.join("\n");
+        content.push('\n');
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_read_sums_assistant_usage() {
+        let path = temp_transcript(
+            "sum",
+            &[
+                r#"{"type":"user","message":{"content":"hi"}}"#.to_string(),
+                assistant_line("m1", "glm-5", 100, 20, 1000),
+                assistant_line("m2", "glm-5", 50, 10, 1200),
+            ],
+        );
+        let mut state = TranscriptState::default();
+        read_incremental(&path, &mut state).unwrap();
+
+        assert_eq!(state.totals.input, 150);
+        assert_eq!(state.totals.output, 30);
+        assert_eq!(state.totals.cache_read, 2200);
+        assert_eq!(state.context_tokens, 1250);
+        assert_eq!(state.models["glm-5"].output, 30);
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_read_skips_repeated_message_lines() {
+        let path = temp_transcript(
+            "repeat",
+            &[
+                assistant_line("m1", "glm-5", 100, 20, 0),
+                assistant_line("m1", "glm-5", 100, 20, 0),
+            ],
+        );
+        let mut state = TranscriptState::default();
+        read_incremental(&path, &mut state).unwrap();
+        assert_eq!(state.totals.input, 100);
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_read_is_incremental() {
+        let path = temp_transcript("incremental", &[assistant_line("m1", "glm-5", 100, 20, 0)]);
+        let mut state = TranscriptState::default();
+        read_incremental(&path, &mut state).unwrap();
+        let first_offset = state.offset;
+
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap();
+        writeln!(file, "{}", assistant_line("m2", "glm-4.7", 10, 5, 0)).unwrap();
+        read_incremental(&path, &mut state).unwrap();
+
+        assert!(state.offset > first_offset);
+        assert_eq!(state.totals.input, 110);
+        assert_eq!(state.models["glm-4.7"].input, 10);
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_read_leaves_partial_line_for_later() {
+        let path = temp_transcript("partial", &[assistant_line("m1", "glm-5", 100, 20, 0)]);
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap();
+        write!(file, "{}", &assistant_line("m2", "glm-5", 1, 1, 0)[..20]).unwrap();
+
+        let mut state = TranscriptState::default();
+        read_incremental(&path, &mut state).unwrap();
+        assert_eq!(state.totals.input, 100);
+        let complete_len =
+            u64::try_from(assistant_line("m1", "glm-5", 100, 20, 0).len() + 1).unwrap();
+        assert_eq!(state.offset, complete_len);
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_read_restarts_after_truncation() {
+        let path = temp_transcript("truncate", &[assistant_line("m1", "glm-5", 100, 20, 0)]);
+        let mut state = TranscriptState {
+            offset: 1_000_000,
+            ..TranscriptState::default()
+        };
+        read_incremental(&path, &mut state).unwrap();
+        assert_eq!(state.totals.input, 100);
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_sidechain_does_not_change_context() {
+        let sidechain = r#"{"type":"assistant","isSidechain":true,"message":{"id":"s1","usage":{"input_tokens":9999}}}"#;
+        let path = temp_transcript(
+            "sidechain",
+            &[
+                assistant_line("m1", "glm-5", 100, 20, 0),
+                sidechain.to_string(),
+            ],
+        );
+        let mut state = TranscriptState::default();
+        read_incremental(&path, &mut state).unwrap();
+        assert_eq!(state.context_tokens, 100);
+        assert_eq!(state.totals.input, 10_099);
+        assert_eq!(state.models[UNKNOWN_MODEL].input, 9999);
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_mcp_server() {
+        assert_eq!(
+            mcp_server("mcp__web-search-prime__webSearchPrime"),
+            Some("web-search-prime")
+        );
+        assert_eq!(mcp_server("Bash"), None);
+        assert_eq!(mcp_server("mcp__broken"), None);
+    }
+
+    #[test]
+    fn test_read_counts_mcp_tool_calls() {
+        let tool_use = |id: &str, name: &str| {
+            format!(
+                r#"{{"type":"assistant","message":{{"id":"{id}","content":[{{"type":"tool_use","id":"t-{id}","name":"{name}","input":{{}}}}],"usage":{{"input_tokens":1}}}}}}"#
+            )
+        };
+        let path = temp_transcript(
+            "mcp",
+            &[
+                tool_use("m1", "mcp__web-reader__webReader"),
+                tool_use("m1", "mcp__web-search-prime__webSearchPrime"),
+                tool_use("m2", "mcp__web-reader__webReader"),
+                tool_use("m3", "Read"),
+            ],
+        );
+        let mut state = TranscriptState::default();
+        read_incremental(&path, &mut state).unwrap();
+
+        assert_eq!(state.mcp_calls["web-reader"], 2);
+        assert_eq!(state.mcp_calls["web-search-prime"], 1);
+        assert_eq!(state.mcp_call_count(&[]), 3);
+        assert_eq!(state.mcp_call_count(&["web-reader".to_string()]), 2);
+        assert_eq!(state.totals.input, 3);
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_observe_at_persists_offset() {
+        let path = temp_transcript("observe", &[assistant_line("m1", "glm-5", 100, 20, 0)]);
+        let store_path = path.parent().unwrap().join(TRANSCRIPTS_FILE);
+        let daily_path = path.parent().unwrap().join(crate::transcript::DAILY_FILE);
+
+        let state = observe_at(&store_path, &daily_path, &path, 1_000).unwrap();
+        assert_eq!(state.totals.input, 100);
+        assert_eq!(state.updated_at, 1_000);
+
+        let store: TranscriptStore = crate::state::load(&store_path);
+        let saved = &store.transcripts[&path.to_string_lossy().into_owned()];
+        assert_eq!(saved.offset, state.offset);
+
+        // Re-observing without new lines adds nothing to the daily ledger.
+        observe_at(&store_path, &daily_path, &path, 2_000).unwrap();
+        let ledger: DailyLedger = crate::state::load(&daily_path);
+        assert_eq!(ledger.since(0)["glm-5"].input, 100);
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_stored_at_does_not_read_transcript() {
+        let path = temp_transcript("stored", &[assistant_line("m1", "glm-5", 100, 20, 0)]);
+        let store_path = path.parent().unwrap().join(TRANSCRIPTS_FILE);
+        let daily_path = path.parent().unwrap().join(crate::transcript::DAILY_FILE);
+        assert!(stored_at(&store_path, &path).is_none());
+
+        observe_at(&store_path, &daily_path, &path, 1_000).unwrap();
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap();
+        writeln!(file, "{}", assistant_line("m2", "glm-5", 50, 5, 0)).unwrap();
+
+        let state = stored_at(&store_path, &path).unwrap();
+        assert_eq!(state.totals.input, 100);
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+}
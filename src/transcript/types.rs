@@ -0,0 +1,172 @@
+//! Token accounting types for Claude Code transcripts.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Token counts summed over transcript messages.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenTotals {
+    /// Uncached input tokens.
+    #[serde(default)]
+    pub input: u64,
+    /// Output tokens.
+    #[serde(default)]
+    pub output: u64,
+    /// Tokens written to the prompt cache.
+    #[serde(default)]
+    pub cache_creation: u64,
+    /// Tokens read from the prompt cache.
+    #[serde(default)]
+    pub cache_read: u64,
+}
+
+impl TokenTotals {
+    /// Adds another set of counts to this one.
+    pub fn add(&mut self, other: &TokenTotals) {
+        self.input += other.input;
+        self.output += other.output;
+        self.cache_creation += other.cache_creation;
+        self.cache_read += other.cache_read;
+    }
+
+    /// Returns the counts added since `earlier`, clamped at zero.
+    #[must_use]
+    pub fn since(&self, earlier: &TokenTotals) -> TokenTotals {
+        TokenTotals {
+            input: self.input.saturating_sub(earlier.input),
+            output: self.output.saturating_sub(earlier.output),
+            cache_creation: self.cache_creation.saturating_sub(earlier.cache_creation),
+            cache_read: self.cache_read.saturating_sub(earlier.cache_read),
+        }
+    }
+
+    /// Returns the sum of all token kinds.
+    #[must_use]
+    pub fn total(&self) -> u64 {
+        self.input + self.output + self.cache_creation + self.cache_read
+    }
+
+    /// Returns the prompt size: input plus cached prompt tokens.
+    #[must_use]
+    pub fn prompt(&self) -> u64 {
+        self.input + self.cache_creation + self.cache_read
+    }
+}
+
+/// Accumulated accounting for one transcript file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TranscriptState {
+    /// Byte offset up to which the transcript has been parsed.
+    #[serde(default)]
+    pub offset: u64,
+    /// ID of the last counted message, used to skip repeated entries.
+    #[serde(default)]
+    pub last_message_id: Option<String>,
+    /// Token totals across all messages.
+    #[serde(default)]
+    pub totals: TokenTotals,
+    /// Token totals per model ID.
+    #[serde(default)]
+    pub models: BTreeMap<String, TokenTotals>,
+    /// Prompt size of the latest main-thread message (context window fill).
+    #[serde(default)]
+    pub context_tokens: u64,
+    /// MCP tool calls per server name.
+    #[serde(default)]
+    pub mcp_calls: BTreeMap<String, u64>,
+    /// Timestamp (seconds) of the last update.
+    #[serde(default)]
+    pub updated_at: i64,
+}
+
+impl TranscriptState {
+    /// Returns per-model tokens added since `earlier`, omitting unchanged models.
+    #[must_use]
+    pub fn models_since(&self, earlier: &TranscriptState) -> BTreeMap<String, TokenTotals> {
+        self.models
+            .iter()
+            .map(|(model, tokens)| {
+                let before = earlier.models.get(model).copied().unwrap_or_default();
+                (model.clone(), tokens.since(&before))
+            })
+            .filter(|(_, delta)| delta.total() > 0)
+            .collect()
+    }
+
+    /// Returns MCP calls summed over `servers`, or over all servers if empty.
+    #[must_use]
+    pub fn mcp_call_count(&self, servers: &[String]) -> u64 {
+        self.mcp_calls
+            .iter()
+            .filter(|(server, _)| servers.is_empty() || servers.contains(server))
+            .map(|(_, calls)| calls)
+            .sum()
+    }
+}
+
+/// A single transcript line, reduced to the fields we account for.
+#[derive(Debug, Deserialize)]
+pub(crate) struct TranscriptEntry {
+    #[serde(rename = "type", default)]
+    pub kind: Option<String>,
+    #[serde(rename = "isSidechain", default)]
+    pub is_sidechain: bool,
+    #[serde(default)]
+    pub message: Option<TranscriptMessage>,
+}
+
+/// The message payload of a transcript line.
+#[derive(Debug, Deserialize)]
+pub(crate) struct TranscriptMessage {
+    #[serde(default)]
+    pub id: Option<String>,
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub usage: Option<MessageUsage>,
+    #[serde(default)]
+    pub content: Option<serde_json::Value>,
+}
+
+impl TranscriptMessage {
+    /// Returns the names of the tools invoked by this message.
+    pub fn tool_use_names(&self) -> impl Iterator<Item = &str> {
+        self.content
+            .as_ref()
+            .and_then(serde_json::Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter(|block| {
+                block.get("type").and_then(serde_json::Value::as_str) == Some("tool_use")
+            })
+            .filter_map(|block| block.get("name").and_then(serde_json::Value::as_str))
+    }
+}
+
+/// Usage block reported for an assistant message.
+#[derive(Debug, Default, Deserialize)]
+#[allow(
+    clippy::struct_field_names,
+    reason = "field names mirror the transcript JSON schema"
+)]
+pub(crate) struct MessageUsage {
+    #[serde(default)]
+    pub input_tokens: u64,
+    #[serde(default)]
+    pub output_tokens: u64,
+    #[serde(default)]
+    pub cache_creation_input_tokens: u64,
+    #[serde(default)]
+    pub cache_read_input_tokens: u64,
+}
+
+impl From<&MessageUsage> for TokenTotals {
+    fn from(usage: &MessageUsage) -> Self {
+        Self {
+            input: usage.input_tokens,
+            output: usage.output_tokens,
+            cache_creation: usage.cache_creation_input_tokens,
+            cache_read: usage.cache_read_input_tokens,
+        }
+    }
+}
@@ -0,0 +1,114 @@
+//! Metered value of token usage.
+//!
+//! Prices token counts with the configured per-model price table to show
+//! what the same usage would have cost on pay-as-you-go API pricing.
+
+use crate::config::{ModelPrice, PricingConfig};
+use crate::transcript::types::TokenTotals;
+use std::collections::BTreeMap;
+
+/// Tokens per price unit (prices are per million tokens).
+const TOKENS_PER_PRICE_UNIT: f64 = 1_000_000.0;
+
+/// Metered value of one model's usage.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelValue {
+    /// Model ID as recorded in the transcript.
+    pub model: String,
+    /// Tokens used by the model.
+    pub tokens: TokenTotals,
+    /// Metered cost, or `None` if the model has no price.
+    pub cost: Option<f64>,
+}
+
+/// Metered value of usage across models.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ValueSummary {
+    /// Per-model values, most valuable first.
+    pub models: Vec<ModelValue>,
+    /// Total metered cost of priced models.
+    pub total: f64,
+}
+
+/// Returns the metered cost of `tokens` at `price`.
+#[must_use]
+#[allow(
+    clippy::cast_precision_loss,
+    reason = "token counts stay far below 2^52"
+)]
+pub fn metered_cost(price: &ModelPrice, tokens: &TokenTotals) -> f64 {
+    let cache_read = price.cache_read.unwrap_or(price.input);
+    let cache_creation = price.cache_creation.unwrap_or(price.input);
+    (tokens.input as f64 * price.input
+        + tokens.output as f64 * price.output
+        + tokens.cache_read as f64 * cache_read
+        + tokens.cache_creation as f64 * cache_creation)
+        / TOKENS_PER_PRICE_UNIT
+}
+
+/// Prices per-model token totals with the configured price table.
+#[must_use]
+pub fn summarize(pricing: &PricingConfig, models: &BTreeMap<String, TokenTotals>) -> ValueSummary {
+    let mut values: Vec<ModelValue> = models
+        .iter()
+        .map(|(model, tokens)| ModelValue {
+            model: model.clone(),
+            tokens: *tokens,
+            cost: pricing
+                .price_for(model)
+                .map(|price| metered_cost(&price, tokens)),
+        })
+        .collect();
+    values.sort_by(|a, b| {
+        b.cost
+            .unwrap_or(-1.0)
+            .total_cmp(&a.cost.unwrap_or(-1.0))
+            .then_with(|| a.model.cmp(&b.model))
+    });
+    let total = values.iter().filter_map(|v| v.cost).sum();
+    ValueSummary {
+        models: values,
+        total,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(input: u64, output: u64, cache_read: u64) -> TokenTotals {
+        TokenTotals {
+            input,
+            output,
+            cache_creation: 0,
+            cache_read,
+        }
+    }
+
+    #[test]
+    fn test_metered_cost() {
+        let price = ModelPrice {
+            input: 1.0,
+            output: 4.0,
+            cache_read: Some(0.1),
+            cache_creation: None,
+        };
+        let cost = metered_cost(&price, &tokens(1_000_000, 500_000, 2_000_000));
+        assert!((cost - 3.2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_summarize_sorts_and_skips_unpriced() {
+        let models = BTreeMap::from([
+            ("glm-4.7".to_string(), tokens(1_000_000, 0, 0)),
+            ("glm-5.1".to_string(), tokens(1_000_000, 0, 0)),
+            ("mystery".to_string(), tokens(1_000_000, 0, 0)),
+        ]);
+        let summary = summarize(&PricingConfig::default(), &models);
+
+        assert_eq!(summary.models[0].model, "glm-5.1");
+        assert_eq!(summary.models[1].model, "glm-4.7");
+        assert_eq!(summary.models[2].cost, None);
+        assert!((summary.total - 1.6).abs() < 1e-9);
+    }
+}
@@ -6,6 +6,8 @@
 use anyhow::Context;
 use std::fs;
 use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+use time::{format_description, OffsetDateTime, UtcOffset};
 
 /// Atomically writes content to a file using a temporary file and rename.
 ///
@@ -17,7 +19,10 @@ use std::path::Path;
 /// Returns an error if the temporary file cannot be written or renamed.
 pub(crate) fn atomic_write(path: impl AsRef<Path>, content: &str) -> anyhow::Result<()> {
     let path = path.as_ref();
-    let new_path = path.with_extension("toml.new");
+    let new_path = path.with_extension(match path.extension() {
+        Some(ext) => format!("{}.new", ext.to_string_lossy()),
+        None => "new".to_string(),
+    });
     fs::write(&new_path, content)
         .with_context(|| format!("Failed to write temporary file: {}", new_path.display()))?;
     fs::rename(&new_path, path)
@@ -25,6 +30,65 @@ pub(crate) fn atomic_write(path: impl AsRef<Path>, content: &str) -> anyhow::Res
     Ok(())
 }
 
+/// Returns the current time as seconds since the Unix epoch.
+pub(crate) fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .ok()
+        .and_then(|d| i64::try_from(d.as_secs()).ok())
+        .unwrap_or_default()
+}
+
+/// Formats a Unix timestamp as local "YYYY-MM-DD HH:MM".
+pub(crate) fn format_timestamp(secs: i64) -> Option<String> {
+    let utc = OffsetDateTime::from_unix_timestamp(secs).ok()?;
+    let local = utc.to_offset(UtcOffset::local_offset_at(utc).unwrap_or(UtcOffset::UTC));
+    let format = format_description::parse("[year]-[month]-[day] [hour]:[minute]").ok()?;
+    local.format(&format).ok()
+}
+
+/// Formats a count compactly with K/M/B suffixes (e.g., "1.2M").
+pub(crate) fn format_compact(value: u64) -> String {
+    const UNITS: [(u64, &str); 3] = [(1_000_000_000, "B"), (1_000_000, "M"), (1_000, "K")];
+    compact_with(value, &UNITS)
+}
+
+/// Formats a count compactly with the Chinese 万/亿 suffixes (e.g., "120万").
+pub(crate) fn format_compact_zh(value: u64) -> String {
+    const UNITS: [(u64, &str); 2] = [(100_000_000, "亿"), (10_000, "万")];
+    compact_with(value, &UNITS)
+}
+
+/// Formats a count with comma thousands separators (e.g., "1,234,567").
+pub(crate) fn format_grouped(value: u64) -> String {
+    let digits = value.to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+/// Scales `value` to the largest unit it reaches, with at most one decimal
+/// below 100 units.
+fn compact_with(value: u64, units: &[(u64, &str)]) -> String {
+    for &(scale, suffix) in units {
+        if value >= scale {
+            let whole = value / scale;
+            let tenth = (value % scale) * 10 / scale;
+            return if whole >= 100 || tenth == 0 {
+                format!("{whole}{suffix}")
+            } else {
+                format!("{whole}.{tenth}{suffix}")
+            };
+        }
+    }
+    value.to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -63,4 +127,39 @@ mod tests {
         let _ = fs::remove_file(&path);
         let _ = fs::remove_dir_all(&dir);
     }
+
+    #[test]
+    fn test_format_timestamp_valid() {
+        let formatted = format_timestamp(1_800_000_000).unwrap();
+        assert_eq!(formatted.len(), "2027-01-15 08:00".len());
+    }
+
+    #[test]
+    fn test_format_compact() {
+        assert_eq!(format_compact(0), "0");
+        assert_eq!(format_compact(999), "999");
+        assert_eq!(format_compact(1_000), "1K");
+        assert_eq!(format_compact(1_250), "1.2K");
+        assert_eq!(format_compact(1_234_567), "1.2M");
+        assert_eq!(format_compact(5_000_000), "5M");
+        assert_eq!(format_compact(250_400_000), "250M");
+        assert_eq!(format_compact(3_100_000_000), "3.1B");
+    }
+
+    #[test]
+    fn test_format_compact_zh() {
+        assert_eq!(format_compact_zh(9_999), "9999");
+        assert_eq!(format_compact_zh(10_000), "1万");
+        assert_eq!(format_compact_zh(250_000), "25万");
+        assert_eq!(format_compact_zh(1_234_567), "123万");
+        assert_eq!(format_compact_zh(150_000_000), "1.5亿");
+    }
+
+    #[test]
+    fn test_format_grouped() {
+        assert_eq!(format_grouped(0), "0");
+        assert_eq!(format_grouped(999), "999");
+        assert_eq!(format_grouped(1_000), "1,000");
+        assert_eq!(format_grouped(1_234_567), "1,234,567");
+    }
 }
@@ -0,0 +1,71 @@
+//! Quota events delivered to the webhook.
+
+use crate::alerts::TriggeredAlert;
+use crate::config::{AlertQuota, WebhookEventKind};
+use serde::{Deserialize, Serialize};
+
+/// A quota event waiting for delivery.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WebhookEvent {
+    /// Event kind.
+    pub kind: WebhookEventKind,
+    /// Quota the event refers to (`token_usage`, `weekly_usage`, `mcp_usage`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quota: Option<String>,
+    /// Usage percentage at the time of the event.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub percentage: Option<u8>,
+    /// Threshold of the alert rule that fired.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub threshold: Option<u8>,
+    /// Human-readable event message.
+    pub message: String,
+    /// Timestamp (seconds) when the event occurred.
+    pub timestamp: i64,
+}
+
+impl WebhookEvent {
+    /// Creates a threshold or spike event from a fired alert.
+    #[must_use]
+    pub fn alert(alert: &TriggeredAlert, now: i64) -> Self {
+        let (kind, threshold) = if alert.spike.is_some() {
+            (WebhookEventKind::Spike, None)
+        } else {
+            (WebhookEventKind::Threshold, Some(alert.rule.threshold))
+        };
+        Self {
+            kind,
+            quota: Some(alert.rule.quota.as_str().to_string()),
+            percentage: Some(alert.percentage),
+            threshold,
+            message: alert.message(),
+            timestamp: now,
+        }
+    }
+
+    /// Creates a quota reset event.
+    #[must_use]
+    pub fn reset(quota: AlertQuota, percentage: u8, now: i64) -> Self {
+        Self {
+            kind: WebhookEventKind::Reset,
+            quota: Some(quota.as_str().to_string()),
+            percentage: Some(percentage),
+            threshold: None,
+            message: format!("GLM {} has reset", quota.label()),
+            timestamp: now,
+        }
+    }
+
+    /// Creates an authentication failure event.
+    #[must_use]
+    pub fn auth_failure(now: i64) -> Self {
+        Self {
+            kind: WebhookEventKind::AuthFailure,
+            quota: None,
+            percentage: None,
+            threshold: None,
+            message: "GLM monitor API rejected the auth token".to_string(),
+            timestamp: now,
+        }
+    }
+}
@@ -0,0 +1,115 @@
+//! Webhook delivery of quota events.
+//!
+//! Threshold alerts, quota resets, and auth failures are posted to the
+//! `[webhook]` URL. Undelivered events are kept in a persisted outbox and
+//! retried on later runs, so a slow or offline endpoint delays each run by
+//! at most `timeout_ms`.
+
+mod event;
+mod outbox;
+mod payload;
+
+#[doc(inline)]
+#[allow(unused_imports, reason = "re-exported for public API")]
+pub use event::WebhookEvent;
+#[doc(inline)]
+#[allow(unused_imports, reason = "re-exported for public API")]
+pub use outbox::{WebhookState, WEBHOOK_FILE};
+#[doc(inline)]
+#[allow(unused_imports, reason = "re-exported for public API")]
+pub use payload::payload;
+
+use crate::alerts::TriggeredAlert;
+use crate::api::UsageStats;
+use crate::config::Config;
+use std::path::Path;
+use std::time::Duration;
+
+/// Queues this run's quota events and delivers the outbox.
+///
+/// Does nothing when no webhook URL is configured.
+///
+/// # Errors
+///
+/// Returns an error if the webhook state cannot be written.
+pub fn process(
+    config: &Config,
+    stats: Option<&UsageStats>,
+    auth_failed: bool,
+    triggered: &[TriggeredAlert],
+) -> anyhow::Result<()> {
+    let Some(url) = config.webhook.enabled_url() else {
+        return Ok(());
+    };
+    let Some(path) = crate::state::state_path(WEBHOOK_FILE) else {
+        return Ok(());
+    };
+    let format = config.webhook.format;
+    process_at(
+        &path,
+        config,
+        stats,
+        auth_failed,
+        triggered,
+        crate::util::unix_now(),
+        |event, timeout| {
+            ureq::post(url)
+                .timeout(timeout)
+                .send_json(payload(format, event))?;
+            Ok(())
+        },
+    )
+}
+
+/// Processes webhook events using the state file at `path`.
+fn process_at<F>(
+    path: &Path,
+    config: &Config,
+    stats: Option<&UsageStats>,
+    auth_failed: bool,
+    triggered: &[TriggeredAlert],
+    now: i64,
+    send: F,
+) -> anyhow::Result<()>
+where
+    F: FnMut(&WebhookEvent, Duration) -> anyhow::Result<()>,
+{
+    let mut webhook_state: WebhookState = crate::state::load(path);
+    let previous = webhook_state.clone();
+    webhook_state.observe(&config.webhook, stats, auth_failed, triggered, now);
+    webhook_state.flush(&config.webhook, now, send);
+    if webhook_state != previous {
+        crate::state::save(path, &webhook_state)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::WebhookConfig;
+
+    #[test]
+    fn test_process_at_persists_undelivered_events() {
+        let dir = std::env::temp_dir().join("glm-plan-usage-test-webhook-process");
+        let _ = std::fs::remove_dir_all(&dir);
+        let path = dir.join(WEBHOOK_FILE);
+        let config = Config {
+            webhook: WebhookConfig {
+                url: Some("http://localhost/hook".to_string()),
+                ..WebhookConfig::default()
+            },
+            ..Config::default()
+        };
+
+        process_at(&path, &config, None, true, &[], 1, |_, _| {
+            anyhow::bail!("down")
+        })
+        .unwrap();
+        let saved: WebhookState = crate::state::load(&path);
+        assert_eq!(saved.outbox.len(), 1);
+        assert!(saved.auth_failing);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
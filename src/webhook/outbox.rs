@@ -0,0 +1,254 @@
+//! Persisted webhook outbox.
+//!
+//! Events are queued in a state file and delivered in order, each posted
+//! at most once per run within a short time budget. Failed deliveries stay
+//! in the outbox and are retried on a later run once the retry interval has
+//! passed.
+
+use crate::alerts::{watched_quota, TriggeredAlert};
+use crate::api::UsageStats;
+use crate::config::{AlertQuota, WebhookConfig};
+use crate::webhook::event::WebhookEvent;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+/// State file name for the webhook outbox.
+pub const WEBHOOK_FILE: &str = "webhook.json";
+
+/// Persisted webhook state.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WebhookState {
+    /// Events waiting for delivery, oldest first.
+    #[serde(default)]
+    pub outbox: Vec<WebhookEvent>,
+    /// Last seen reset timestamp per quota, used to detect resets.
+    #[serde(default)]
+    pub windows: BTreeMap<String, i64>,
+    /// Whether an auth failure was already reported.
+    #[serde(default)]
+    pub auth_failing: bool,
+    /// Timestamp (seconds) before which delivery is not retried.
+    #[serde(default)]
+    pub retry_after: i64,
+}
+
+impl WebhookState {
+    /// Queues the events observed in this run.
+    ///
+    /// Reset events fire when a quota's window moved on and the previous
+    /// reset time has passed. The first observation of a quota only
+    /// records its window. An auth failure is reported once until a
+    /// successful fetch clears it.
+    pub fn observe(
+        &mut self,
+        config: &WebhookConfig,
+        stats: Option<&UsageStats>,
+        auth_failed: bool,
+        triggered: &[TriggeredAlert],
+        now: i64,
+    ) {
+        if auth_failed {
+            if !self.auth_failing {
+                self.auth_failing = true;
+                self.enqueue(config, WebhookEvent::auth_failure(now));
+            }
+        } else if stats.is_some() {
+            self.auth_failing = false;
+        }
+
+        if let Some(stats) = stats {
+            for quota in AlertQuota::ALL {
+                let Some(usage) = watched_quota(stats, quota) else {
+                    continue;
+                };
+                let Some(reset_at) = usage.reset_at else {
+                    continue;
+                };
+                let previous = self.windows.insert(quota.as_str().to_string(), reset_at);
+                if previous.is_some_and(|prev| prev != reset_at && now >= prev) {
+                    self.enqueue(config, WebhookEvent::reset(quota, usage.percentage, now));
+                }
+            }
+        }
+
+        for alert in triggered {
+            self.enqueue(config, WebhookEvent::alert(alert, now));
+        }
+    }
+
+    /// Adds an event to the outbox, dropping the oldest when full.
+    fn enqueue(&mut self, config: &WebhookConfig, event: WebhookEvent) {
+        if !config.wants(event.kind) {
+            return;
+        }
+        self.outbox.push(event);
+        let excess = self.outbox.len().saturating_sub(config.max_outbox);
+        self.outbox.drain(..excess);
+    }
+
+    /// Delivers queued events in order using `send`.
+    ///
+    /// Each event is posted once, and all requests share the `timeout_ms`
+    /// budget, passed to `send` as the time left. Delivery stops at the
+    /// first failure, which is retried after `retry_interval_secs`, or when
+    /// the budget runs out, leaving the rest for the next run.
+    pub fn flush<F>(&mut self, config: &WebhookConfig, now: i64, mut send: F)
+    where
+        F: FnMut(&WebhookEvent, Duration) -> anyhow::Result<()>,
+    {
+        if self.outbox.is_empty() || now < self.retry_after {
+            return;
+        }
+        let deadline = Instant::now() + Duration::from_millis(config.timeout_ms);
+        while let Some(event) = self.outbox.first() {
+            let left = deadline.saturating_duration_since(Instant::now());
+            if left.is_zero() {
+                return;
+            }
+            if send(event, left).is_err() {
+                let interval = i64::try_from(config.retry_interval_secs).unwrap_or(i64::MAX);
+                self.retry_after = now.saturating_add(interval);
+                return;
+            }
+            self.outbox.remove(0);
+        }
+        self.retry_after = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::QuotaUsage;
+    use crate::config::{AlertDirection, AlertRule, WebhookEventKind};
+
+    fn config() -> WebhookConfig {
+        WebhookConfig {
+            url: Some("http://localhost/hook".to_string()),
+            ..WebhookConfig::default()
+        }
+    }
+
+    fn stats(percentage: u8, reset_at: i64) -> UsageStats {
+        UsageStats {
+            token_usage: Some(QuotaUsage {
+                used: i64::from(percentage),
+                limit: 100,
+                percentage,
+                time_window: "5h".to_string(),
+                reset_at: Some(reset_at),
+            }),
+            weekly_usage: None,
+            mcp_usage: None,
+        }
+    }
+
+    fn alert() -> TriggeredAlert {
+        TriggeredAlert {
+            rule: AlertRule {
+                quota: AlertQuota::TokenUsage,
+                threshold: 80,
+                direction: AlertDirection::Above,
+                command: None,
+                notify: false,
+            },
+            percentage: 85,
+            reset_at: Some(1_000),
+            spike: None,
+        }
+    }
+
+    fn kinds(state: &WebhookState) -> Vec<WebhookEventKind> {
+        state.outbox.iter().map(|event| event.kind).collect()
+    }
+
+    #[test]
+    fn test_reset_detected_after_window_passes() {
+        let mut state = WebhookState::default();
+        state.observe(&config(), Some(&stats(90, 1_000)), false, &[], 500);
+        assert!(state.outbox.is_empty());
+        state.observe(&config(), Some(&stats(2, 19_000)), false, &[], 1_001);
+        assert_eq!(kinds(&state), vec![WebhookEventKind::Reset]);
+        assert_eq!(state.outbox[0].quota.as_deref(), Some("token_usage"));
+    }
+
+    #[test]
+    fn test_auth_failure_reported_once() {
+        let mut state = WebhookState::default();
+        state.observe(&config(), None, true, &[], 1);
+        state.observe(&config(), None, true, &[], 2);
+        assert_eq!(kinds(&state), vec![WebhookEventKind::AuthFailure]);
+        state.observe(&config(), Some(&stats(10, 1_000)), false, &[], 3);
+        assert!(!state.auth_failing);
+    }
+
+    #[test]
+    fn test_unwanted_events_are_skipped() {
+        let config = WebhookConfig {
+            events: vec![WebhookEventKind::Reset],
+            ..config()
+        };
+        let mut state = WebhookState::default();
+        state.observe(&config, None, true, &[alert()], 1);
+        assert!(state.outbox.is_empty());
+    }
+
+    #[test]
+    fn test_outbox_drops_oldest_when_full() {
+        let config = WebhookConfig {
+            max_outbox: 2,
+            ..config()
+        };
+        let mut state = WebhookState::default();
+        for now in 0..3 {
+            state.observe(&config, None, false, &[alert()], now);
+        }
+        let stamps: Vec<i64> = state.outbox.iter().map(|event| event.timestamp).collect();
+        assert_eq!(stamps, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_flush_keeps_failed_events_until_retry() {
+        let mut state = WebhookState::default();
+        state.observe(&config(), None, false, &[alert()], 1);
+
+        let mut attempts = 0;
+        state.flush(&config(), 10, |_, _| {
+            attempts += 1;
+            anyhow::bail!("down")
+        });
+        assert_eq!(attempts, 1);
+        assert_eq!(state.outbox.len(), 1);
+        assert_eq!(state.retry_after, 70);
+
+        let mut sent = 0;
+        state.flush(&config(), 20, |_, _| {
+            sent += 1;
+            Ok(())
+        });
+        assert_eq!(sent, 0);
+
+        state.flush(&config(), 70, |_, _| {
+            sent += 1;
+            Ok(())
+        });
+        assert_eq!(sent, 1);
+        assert!(state.outbox.is_empty());
+        assert_eq!(state.retry_after, 0);
+    }
+
+    #[test]
+    fn test_flush_stops_when_budget_runs_out() {
+        let config = WebhookConfig {
+            timeout_ms: 0,
+            ..config()
+        };
+        let mut state = WebhookState::default();
+        state.observe(&config, None, false, &[alert()], 1);
+
+        state.flush(&config, 10, |_, _| panic!("no time left to send"));
+        assert_eq!(state.outbox.len(), 1);
+        assert_eq!(state.retry_after, 0);
+    }
+}
@@ -0,0 +1,56 @@
+//! Webhook payload templates.
+
+use crate::config::WebhookFormat;
+use crate::webhook::event::WebhookEvent;
+use serde_json::{json, Value};
+
+/// Builds the request body for an event in the given format.
+///
+/// The generic JSON format carries every event field; chat formats send
+/// the event message as a plain text message.
+#[must_use]
+pub fn payload(format: WebhookFormat, event: &WebhookEvent) -> Value {
+    match format {
+        WebhookFormat::Json => json!(event),
+        WebhookFormat::Feishu => json!({
+            "msg_type": "text",
+            "content": { "text": event.message },
+        }),
+        WebhookFormat::Dingtalk => json!({
+            "msgtype": "text",
+            "text": { "content": event.message },
+        }),
+        WebhookFormat::Slack => json!({ "text": event.message }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event() -> WebhookEvent {
+        WebhookEvent::auth_failure(1_000)
+    }
+
+    #[test]
+    fn test_json_payload_has_all_fields() {
+        let body = payload(WebhookFormat::Json, &event());
+        assert_eq!(body["kind"], "auth_failure");
+        assert_eq!(body["timestamp"], 1_000);
+        assert!(body.get("quota").is_none());
+    }
+
+    #[test]
+    fn test_chat_payloads() {
+        let message = event().message;
+        assert_eq!(
+            payload(WebhookFormat::Feishu, &event())["content"]["text"],
+            message
+        );
+        assert_eq!(
+            payload(WebhookFormat::Dingtalk, &event())["text"]["content"],
+            message
+        );
+        assert_eq!(payload(WebhookFormat::Slack, &event())["text"], message);
+    }
+}